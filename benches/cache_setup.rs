@@ -0,0 +1,43 @@
+//! Commitment-key setup with a warm vs a cold cache
+//!
+//! The warm path only reads the key back from disk, the cold path generates it from scratch, so
+//! expect the warm path to be two to three orders of magnitude faster (milliseconds vs seconds).
+//! Each cold iteration runs in a fresh temporary directory, so the benchmark is hermetic.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use sirius::prelude::{bn256::C1Affine, CommitmentKey};
+
+/// Smaller than the example's key, to keep the cold path bearable
+const KEY_SIZE: usize = 16;
+
+fn load_or_setup(dir: &std::path::Path) -> CommitmentKey<C1Affine> {
+    // Safety: the cache is only ever written by `load_or_setup_cache` itself
+    unsafe { CommitmentKey::<C1Affine>::load_or_setup_cache(dir, "bn256", KEY_SIZE).unwrap() }
+}
+
+fn cache_setup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("commitment_key_cache");
+    group.sample_size(10);
+
+    let warm = tempfile::tempdir().unwrap();
+    load_or_setup(warm.path());
+
+    group.bench_function("warm", |b| b.iter(|| load_or_setup(warm.path())));
+
+    group.bench_function("cold", |b| {
+        b.iter_batched(
+            || tempfile::tempdir().unwrap(),
+            // Return the directory, so it is deleted outside of the measurement
+            |dir| {
+                let key = load_or_setup(dir.path());
+                (dir, key)
+            },
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, cache_setup);
+criterion_main!(benches);
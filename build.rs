@@ -0,0 +1,38 @@
+//! Embeds the resolved sirius version, see `src/build_info.rs`
+
+use std::{env, fs, path::Path};
+
+fn main() {
+    let lock = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("Cargo.lock");
+    println!("cargo:rerun-if-changed={}", lock.display());
+
+    let sirius_version = fs::read_to_string(&lock)
+        .ok()
+        .and_then(|lock| sirius_version(&lock))
+        .unwrap_or_else(|| "unknown".to_owned());
+    println!("cargo:rustc-env=SIRIUS_VERSION={sirius_version}");
+}
+
+/// `version (source)` of the `sirius` package in the lock file
+fn sirius_version(lock: &str) -> Option<String> {
+    let package = lock.split("[[package]]").find(|package| {
+        package
+            .lines()
+            .any(|line| line.trim() == r#"name = "sirius""#)
+    })?;
+    let field = |name: &str| {
+        package.lines().find_map(|line| {
+            line.trim()
+                .strip_prefix(name)?
+                .trim_start()
+                .strip_prefix('=')
+                .map(|value| value.trim().trim_matches('"').to_owned())
+        })
+    };
+
+    let version = field("version")?;
+    Some(match field("source") {
+        Some(source) => format!("{version} ({source})"),
+        None => version,
+    })
+}
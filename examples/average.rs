@@ -0,0 +1,37 @@
+//! A running average of a stream of values, in fixed point
+//!
+//! `cargo run --release --example average`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::{fixed_point_average, AverageCircuit, AVERAGE_SCALE},
+    config::RunConfig,
+    error::ExampleError,
+    field,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    let values = [10, 20, 30];
+    let config = RunConfig {
+        primary_z_0: vec![C1Scalar::from(0), C1Scalar::from(0)],
+        ..RunConfig::default()
+    };
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner = FoldRunner::<2, _>::new(&keys, &config, &AverageCircuit::new(values[0]))?;
+    for value in &values[1..] {
+        runner.step(&AverageCircuit::new(*value))?;
+    }
+    runner.assert_sound()?;
+
+    // The state is the exact `[sum, count]`, the average is proven from it on every step
+    let [sum, count] = runner.z_out().map(|value| field::to_u128(&value).unwrap());
+    let average = fixed_point_average(sum, count).unwrap();
+    println!(
+        "verified average of {values:?} = {}",
+        average as f64 / AVERAGE_SCALE as f64
+    );
+
+    Ok(())
+}
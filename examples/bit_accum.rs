@@ -0,0 +1,33 @@
+//! An 8-bit state XOR-ed with the same input every step, toggling between two values
+//!
+//! `cargo run --release --example bit_accum`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::BitAccumCircuit,
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    let config = RunConfig {
+        primary_z_0: vec![C1Scalar::from(0x0f)],
+        ..RunConfig::default()
+    };
+    let circuit = BitAccumCircuit::new(8, 0xff);
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner = FoldRunner::<1, _>::new(&keys, &config, &circuit)?;
+    runner.steps(2, &circuit)?;
+    runner.assert_sound()?;
+
+    // 0x0f XOR 0xff, three times
+    println!(
+        "verified {} steps, state = {:?}",
+        runner.current_index(),
+        runner.z_out()[0]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+//! The Fibonacci sequence frozen at the first value above a bound, however many steps are folded
+//!
+//! `cargo run --release --example bounded_fibo`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::BoundedFiboCircuit,
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    let config = RunConfig {
+        primary_z_0: [0, 1, 0].map(C1Scalar::from).to_vec(),
+        ..RunConfig::default()
+    };
+    let circuit = BoundedFiboCircuit::new(20);
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner = FoldRunner::<3, _>::new(&keys, &config, &circuit)?;
+    runner.steps(9, &circuit)?;
+    runner.assert_sound()?;
+
+    // Halted on 21 by the 7th step, the 3 later ones keep the state
+    let [a, b, halted] = runner.z_out();
+    println!(
+        "verified {} steps, state = [{a:?}, {b:?}], halted = {halted:?}",
+        runner.current_index()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+//! Claims batched into an accumulator with challenges squeezed from a running transcript, the
+//! way a recursive verifier batches its checks
+//!
+//! `cargo run --release --example challenge_accum`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::ChallengeAccumCircuit,
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    let claims = [2, 7, 1];
+    let config = RunConfig {
+        primary_z_0: vec![C1Scalar::from(0), C1Scalar::from(1)],
+        ..RunConfig::default()
+    };
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner =
+        FoldRunner::<2, _>::new(&keys, &config, &ChallengeAccumCircuit::new(claims[0]))?;
+    for claim in &claims[1..] {
+        runner.step(&ChallengeAccumCircuit::new(*claim))?;
+    }
+    runner.assert_sound()?;
+
+    let [acc, challenge] = runner.z_out();
+    println!("verified accumulation of {claims:?}: acc = {acc:?}, challenge = {challenge:?}");
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+//! A running checksum of data words, compared with the one the verifier expects
+//!
+//! `cargo run --release --example checksum`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::{checksum, ChecksumCircuit},
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    let words = [0x01, 0x02, 0xff].map(C1Scalar::from);
+    let config = RunConfig {
+        primary_z_0: vec![C1Scalar::from(0)],
+        ..RunConfig::default()
+    };
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner = FoldRunner::<1, _>::new(&keys, &config, &ChecksumCircuit::new(words[0]))?;
+    for word in &words[1..] {
+        runner.step(&ChecksumCircuit::new(*word))?;
+    }
+    runner.assert_sound()?;
+
+    let folded = runner.z_out()[0];
+    assert_eq!(folded, checksum(&words), "checksum mismatch");
+    println!("verified checksum of {} words = {folded:?}", words.len());
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+//! `e(n) = c_n * e(n - 1) + e(n - 2)` with the partial quotients of pi as the coefficients,
+//! folding the numerators and the denominators of its convergents
+//!
+//! `cargo run --release --example coeff_recurrence`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::CoeffRecurrenceCircuit,
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+/// Continued fraction of pi, `[3; 7, 15, 1, 292]`
+const PI: [u64; 5] = [3, 7, 15, 1, 292];
+
+/// Last element of the recurrence started from `z_0`
+fn fold(keys: &CommitmentKeys, z_0: [u64; 2]) -> Result<C1Scalar, ExampleError> {
+    let config = RunConfig {
+        primary_z_0: z_0.map(C1Scalar::from).to_vec(),
+        ..RunConfig::default()
+    };
+
+    let mut runner = FoldRunner::<2, _>::new(keys, &config, &CoeffRecurrenceCircuit::new(PI[0]))?;
+    for coefficient in &PI[1..] {
+        runner.step(&CoeffRecurrenceCircuit::new(*coefficient))?;
+    }
+    runner.assert_sound()?;
+
+    Ok(runner.z_out()[1])
+}
+
+fn main() -> Result<(), ExampleError> {
+    let keys = CommitmentKeys::setup(&RunConfig::default())?;
+
+    let numerator = fold(&keys, [0, 1])?;
+    let denominator = fold(&keys, [1, 0])?;
+    // 103993 / 33102
+    println!("verified convergent of pi: {numerator:?} / {denominator:?}");
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+//! A linear layer `z' = W z + b` applied to the state every step
+//!
+//! `cargo run --release --example dense_layer`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::DenseLayerCircuit,
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    let weights = [[2, 0], [0, 3]].map(|row| row.map(C1Scalar::from));
+    let bias = [1, 1].map(C1Scalar::from);
+    let config = RunConfig {
+        primary_z_0: vec![C1Scalar::from(1), C1Scalar::from(0)],
+        ..RunConfig::default()
+    };
+    let circuit = DenseLayerCircuit::new(weights, bias);
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner = FoldRunner::<2, _>::new(&keys, &config, &circuit)?;
+    runner.steps(2, &circuit)?;
+    runner.assert_sound()?;
+
+    // [1, 0] -> [3, 1] -> [7, 4] -> [15, 13]
+    println!(
+        "verified {} applications of the layer, state = {:?}",
+        runner.current_index(),
+        runner.z_out()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+//! The Fibonacci sequence one element per step, with the proven decimal digit count of the last
+//! element
+//!
+//! `cargo run --release --example digit_count_fibo`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::DigitCountFiboCircuit,
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    let config = RunConfig {
+        primary_z_0: [0, 1, 1].map(C1Scalar::from).to_vec(),
+        ..RunConfig::default()
+    };
+    let circuit = DigitCountFiboCircuit::new(6);
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner = FoldRunner::<3, _>::new(&keys, &config, &circuit)?;
+    runner.steps(19, &circuit)?;
+    runner.assert_sound()?;
+
+    // F(21) = 10946, of 5 digits
+    let [_, b, digits] = runner.z_out();
+    println!("verified {b:?} has {digits:?} digits");
+
+    Ok(())
+}
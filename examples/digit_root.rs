@@ -0,0 +1,33 @@
+//! The sum of the decimal digits, folded until it settles on the digital root
+//!
+//! `cargo run --release --example digit_root`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::DigitRootCircuit,
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    let config = RunConfig {
+        primary_z_0: vec![C1Scalar::from(9875)],
+        ..RunConfig::default()
+    };
+    let circuit = DigitRootCircuit::new(10);
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner = FoldRunner::<1, _>::new(&keys, &config, &circuit)?;
+    runner.steps(3, &circuit)?;
+    runner.assert_sound()?;
+
+    // 9875 -> 29 -> 11 -> 2 -> 2
+    println!(
+        "verified {} steps, digital root of 9875 = {:?}",
+        runner.current_index(),
+        runner.z_out()[0]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+//! The Fibonacci sequence modulo 7, folded over a whole Pisano period back to its start
+//!
+//! `cargo run --release --example fibo_mod`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::FiboModCircuit,
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    let config = RunConfig {
+        primary_z_0: vec![C1Scalar::from(0), C1Scalar::from(1)],
+        ..RunConfig::default()
+    };
+    let circuit = FiboModCircuit::new(7);
+    let period = circuit.pisano_period() as usize;
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner = FoldRunner::<2, _>::new(&keys, &config, &circuit)?;
+    runner.steps(period - 1, &circuit)?;
+    runner.assert_sound()?;
+
+    // Back at `z_0 = [0, 1]`
+    println!(
+        "verified {} steps, the Pisano period of 7, state = {:?}",
+        runner.current_index(),
+        runner.z_out()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+//! The Fibonacci circuit with the default parameters: 5 steps of 10 elements each
+//!
+//! `cargo run --release --example fibonacci`
+
+use sirius_fibonacci_example::{
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+    FibonacciCircuit, A1, FOLD_STEP_COUNT,
+};
+
+fn main() -> Result<(), ExampleError> {
+    let config = RunConfig::default();
+    let circuit = FibonacciCircuit::new(config.elements_num);
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner = FoldRunner::<A1, _>::new(&keys, &config, &circuit)?;
+    runner.steps(FOLD_STEP_COUNT - 1, &circuit)?;
+    runner.assert_sound()?;
+
+    let index = runner.current_index() * config.elements_num;
+    let [a, b] = runner.z_out();
+    println!(
+        "verified elements #{index} = {a:?} and #{} = {b:?}",
+        index + 1
+    );
+
+    Ok(())
+}
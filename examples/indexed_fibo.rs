@@ -0,0 +1,29 @@
+//! The Fibonacci sequence one element per step, with the index and its parity in the state
+//!
+//! `cargo run --release --example indexed_fibo`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::IndexedFiboCircuit,
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    let config = RunConfig {
+        primary_z_0: [0, 1, 0, 0].map(C1Scalar::from).to_vec(),
+        ..RunConfig::default()
+    };
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner = FoldRunner::<4, _>::new(&keys, &config, &IndexedFiboCircuit)?;
+    runner.steps(7, &IndexedFiboCircuit)?;
+    runner.assert_sound()?;
+
+    // F(8) = 21, index 8 is even
+    let [a, _, index, parity] = runner.z_out();
+    println!("verified F({index:?}) = {a:?}, index parity = {parity:?}");
+
+    Ok(())
+}
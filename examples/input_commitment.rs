@@ -0,0 +1,34 @@
+//! A running hash of the inputs, binding the whole stream in order
+//!
+//! `cargo run --release --example input_commitment`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::InputCommitmentCircuit,
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    let inputs = [1, 2, 3];
+    let config = RunConfig {
+        primary_z_0: vec![C1Scalar::from(0)],
+        ..RunConfig::default()
+    };
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner =
+        FoldRunner::<1, _>::new(&keys, &config, &InputCommitmentCircuit::new(inputs[0]))?;
+    for input in &inputs[1..] {
+        runner.step(&InputCommitmentCircuit::new(*input))?;
+    }
+    runner.assert_sound()?;
+
+    println!(
+        "verified commitment to {inputs:?} = {:?}",
+        runner.z_out()[0]
+    );
+
+    Ok(())
+}
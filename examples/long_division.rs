@@ -0,0 +1,36 @@
+//! The schoolbook long division of 1234 by 7, one dividend digit brought down per step
+//!
+//! `cargo run --release --example long_division`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::LongDivisionCircuit,
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    let (digits, divisor) = ([1, 2, 3, 4], 7);
+    let config = RunConfig {
+        primary_z_0: vec![C1Scalar::from(0), C1Scalar::from(0)],
+        ..RunConfig::default()
+    };
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner = FoldRunner::<2, _>::new(
+        &keys,
+        &config,
+        &LongDivisionCircuit::new(digits[0], divisor),
+    )?;
+    for digit in &digits[1..] {
+        runner.step(&LongDivisionCircuit::new(*digit, divisor))?;
+    }
+    runner.assert_sound()?;
+
+    // 1234 = 7 * 176 + 2
+    let [quotient, remainder] = runner.z_out();
+    println!("verified 1234 / {divisor}: quotient = {quotient:?}, remainder = {remainder:?}");
+
+    Ok(())
+}
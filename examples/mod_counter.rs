@@ -0,0 +1,33 @@
+//! A counter wrapping back to zero at its modulus, folded past the wraparound
+//!
+//! `cargo run --release --example mod_counter`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::ModCounterCircuit,
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    let config = RunConfig {
+        primary_z_0: vec![C1Scalar::from(0)],
+        ..RunConfig::default()
+    };
+    let circuit = ModCounterCircuit::new(3);
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner = FoldRunner::<1, _>::new(&keys, &config, &circuit)?;
+    runner.steps(4, &circuit)?;
+    runner.assert_sound()?;
+
+    // 5 increments modulo 3
+    println!(
+        "verified {} steps, count = {:?}",
+        runner.current_index(),
+        runner.z_out()[0]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+//! A trace of opcodes, each looked up in a fixed table of the valid ones
+//!
+//! `cargo run --release --example opcode_lookup`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::OpcodeLookupCircuit,
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    // ADD, MUL, LT
+    let opcodes = [0x01, 0x02, 0x10];
+    let config = RunConfig {
+        primary_z_0: vec![C1Scalar::from(0)],
+        ..RunConfig::default()
+    };
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner =
+        FoldRunner::<1, _>::new(&keys, &config, &OpcodeLookupCircuit::new(opcodes[0]))?;
+    for opcode in &opcodes[1..] {
+        runner.step(&OpcodeLookupCircuit::new(*opcode))?;
+    }
+    runner.assert_sound()?;
+
+    // One opcode per byte, 0x010210
+    println!("verified trace of {opcodes:02x?} = {:?}", runner.z_out()[0]);
+
+    Ok(())
+}
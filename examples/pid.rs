@@ -0,0 +1,38 @@
+//! A PID controller updated with a new error every step, with fixed-point gains
+//!
+//! `cargo run --release --example pid`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::{PidCircuit, PidGains, PID_SCALE},
+    config::RunConfig,
+    error::ExampleError,
+    field,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    let gains = PidGains::from_f64(0.5, 0.1, 0.0);
+    let errors = [5, 3, -1];
+    let config = RunConfig {
+        primary_z_0: vec![C1Scalar::from(0); 3],
+        ..RunConfig::default()
+    };
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner = FoldRunner::<3, _>::new(&keys, &config, &PidCircuit::new(errors[0], gains))?;
+    for error in &errors[1..] {
+        runner.step(&PidCircuit::new(*error, gains))?;
+    }
+    runner.assert_sound()?;
+
+    // Negative values wrap around the field modulus
+    let [integral, _, output] = runner.z_out().map(|value| field::to_i128(&value).unwrap());
+    println!(
+        "verified {} updates: integral = {integral}, output = {}",
+        runner.current_index(),
+        output as f64 / PID_SCALE as f64
+    );
+
+    Ok(())
+}
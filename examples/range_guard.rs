@@ -0,0 +1,34 @@
+//! A stream of sensor readings, each proven to be within bounds
+//!
+//! `cargo run --release --example range_guard`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::RangeGuardCircuit,
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    let (readings, lo, hi) = ([5, 50, 95], 0, 100);
+    let config = RunConfig {
+        primary_z_0: vec![C1Scalar::from(0)],
+        ..RunConfig::default()
+    };
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner =
+        FoldRunner::<1, _>::new(&keys, &config, &RangeGuardCircuit::new(readings[0], lo, hi))?;
+    for reading in &readings[1..] {
+        runner.step(&RangeGuardCircuit::new(*reading, lo, hi))?;
+    }
+    runner.assert_sound()?;
+
+    println!(
+        "verified every one of {readings:?} within {lo}..={hi}, last = {:?}",
+        runner.z_out()[0]
+    );
+
+    Ok(())
+}
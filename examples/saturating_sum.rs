@@ -0,0 +1,35 @@
+//! A running sum capped at a maximum, folded past the cap
+//!
+//! `cargo run --release --example saturating_sum`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::SaturatingSumCircuit,
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    let (addends, cap) = ([4, 4, 4, 4], 10);
+    let config = RunConfig {
+        primary_z_0: vec![C1Scalar::from(0)],
+        ..RunConfig::default()
+    };
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner =
+        FoldRunner::<1, _>::new(&keys, &config, &SaturatingSumCircuit::new(addends[0], cap))?;
+    for addend in &addends[1..] {
+        runner.step(&SaturatingSumCircuit::new(*addend, cap))?;
+    }
+    runner.assert_sound()?;
+
+    // 4, 8, then held at the cap
+    println!(
+        "verified sum of {addends:?} capped at {cap} = {:?}",
+        runner.z_out()[0]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+//! An accumulator of a set, the same whatever the order the elements are added in
+//!
+//! `cargo run --release --example set_accumulator`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::{SetAccumulatorCircuit, SetOp},
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+/// Accumulator of `elements`, added in order to the empty set
+fn accumulate(keys: &CommitmentKeys, elements: [u64; 3]) -> Result<C1Scalar, ExampleError> {
+    let config = RunConfig {
+        primary_z_0: vec![C1Scalar::from(1)],
+        ..RunConfig::default()
+    };
+    let add = |element| SetAccumulatorCircuit::new(SetOp::Add(element));
+
+    let mut runner = FoldRunner::<1, _>::new(keys, &config, &add(elements[0]))?;
+    for element in &elements[1..] {
+        runner.step(&add(*element))?;
+    }
+    runner.assert_sound()?;
+
+    Ok(runner.z_out()[0])
+}
+
+fn main() -> Result<(), ExampleError> {
+    let keys = CommitmentKeys::setup(&RunConfig::default())?;
+
+    let acc = accumulate(&keys, [3, 5, 8])?;
+    assert_eq!(
+        accumulate(&keys, [8, 3, 5])?,
+        acc,
+        "the order changed the set"
+    );
+    println!("verified accumulator of {{3, 5, 8}} = {acc:?}");
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+//! A stream of values, each proven not to be below the one before
+//!
+//! `cargo run --release --example sorted_pair`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::SortedPairCircuit,
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    let values = [1, 4, 9, 16];
+    let config = RunConfig {
+        primary_z_0: vec![C1Scalar::from(0), C1Scalar::from(0)],
+        ..RunConfig::default()
+    };
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner = FoldRunner::<2, _>::new(&keys, &config, &SortedPairCircuit::new(values[0]))?;
+    for value in &values[1..] {
+        runner.step(&SortedPairCircuit::new(*value))?;
+    }
+    runner.assert_sound()?;
+
+    let [last, count] = runner.z_out();
+    println!("verified {values:?} is sorted: last = {last:?}, count = {count:?}");
+
+    Ok(())
+}
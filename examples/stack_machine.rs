@@ -0,0 +1,42 @@
+//! A tiny stack machine running one instruction of a program per step
+//!
+//! `cargo run --release --example stack_machine`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::{StackMachineCircuit, StackOp},
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    // (2 + 3) + 4
+    let program = [
+        StackOp::Push(2),
+        StackOp::Push(3),
+        StackOp::Add,
+        StackOp::Push(4),
+        StackOp::Add,
+    ];
+    let config = RunConfig {
+        primary_z_0: vec![C1Scalar::from(0); 4],
+        ..RunConfig::default()
+    };
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner =
+        FoldRunner::<4, _>::new(&keys, &config, &StackMachineCircuit::<4>::new(program[0]))?;
+    for op in &program[1..] {
+        runner.step(&StackMachineCircuit::new(*op))?;
+    }
+    runner.assert_sound()?;
+
+    println!(
+        "verified {} instructions, stack = {:?}",
+        program.len(),
+        runner.z_out()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+//! A running sum with a different addend on every step, each step getting its own circuit
+//!
+//! `cargo run --release --example sum`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::SumCircuit,
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    let addends = [1, 2, 3, 4, 5];
+    let config = RunConfig {
+        primary_z_0: vec![C1Scalar::from(0)],
+        ..RunConfig::default()
+    };
+
+    let keys = CommitmentKeys::setup(&config)?;
+    // The base step adds the first addend
+    let mut runner = FoldRunner::<1, _>::new(&keys, &config, &SumCircuit::new(addends[0]))?;
+    for addend in &addends[1..] {
+        runner.step(&SumCircuit::new(*addend))?;
+    }
+    runner.assert_sound()?;
+
+    println!("verified sum of {addends:?} = {:?}", runner.z_out()[0]);
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+//! The Fibonacci circuit spread across 4 advice columns: the same 5 steps of 10 elements as the
+//! `fibonacci` example, in about 4 times fewer rows
+//!
+//! `cargo run --release --example wide_fibonacci`
+
+use sirius_fibonacci_example::{
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+    WideFibonacciCircuit, A1, FOLD_STEP_COUNT,
+};
+
+fn main() -> Result<(), ExampleError> {
+    let config = RunConfig::default();
+    let circuit = WideFibonacciCircuit::<4>::new(config.elements_num);
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner = FoldRunner::<A1, _>::new(&keys, &config, &circuit)?;
+    runner.steps(FOLD_STEP_COUNT - 1, &circuit)?;
+    runner.assert_sound()?;
+
+    let index = runner.current_index() * config.elements_num;
+    let [a, b] = runner.z_out();
+    println!(
+        "verified elements #{index} = {a:?} and #{} = {b:?}",
+        index + 1
+    );
+
+    Ok(())
+}
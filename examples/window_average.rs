@@ -0,0 +1,37 @@
+//! A moving average over the last 4 values of a stream, weighting the newest ones most
+//!
+//! `cargo run --release --example window_average`
+
+use sirius::prelude::bn256::C1Scalar;
+use sirius_fibonacci_example::{
+    circuits::{WindowAverageCircuit, AVERAGE_SCALE},
+    config::RunConfig,
+    error::ExampleError,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+fn main() -> Result<(), ExampleError> {
+    let (values, weights) = ([1, 2, 3, 4, 5, 6], [1, 2, 3, 4]);
+    let config = RunConfig {
+        primary_z_0: vec![C1Scalar::from(0); 4],
+        ..RunConfig::default()
+    };
+    let circuit = |value| WindowAverageCircuit::new(value, weights);
+
+    let keys = CommitmentKeys::setup(&config)?;
+    let mut runner = FoldRunner::<4, _>::new(&keys, &config, &circuit(values[0]))?;
+    for value in &values[1..] {
+        runner.step(&circuit(*value))?;
+    }
+    runner.assert_sound()?;
+
+    // The window is the state, the average is proven from it on every step
+    let window = runner.z_out();
+    let average = circuit(0).average(window).unwrap();
+    println!(
+        "verified window {window:?}, weighted average = {}",
+        average as f64 / AVERAGE_SCALE as f64
+    );
+
+    Ok(())
+}
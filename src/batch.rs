@@ -0,0 +1,137 @@
+//! Running several configurations in one go, e.g. for parameter sweeps
+
+use std::fmt;
+
+use crate::{config::RunConfig, error::ExampleError};
+
+/// What to do with the rest of the batch when one of its configurations fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BatchPolicy {
+    /// Abort the batch on the first failure, the remaining configurations are skipped
+    FailFast,
+    /// Run every configuration and collect the status of each
+    #[default]
+    Continue,
+}
+
+/// Status of one configuration of the batch
+#[derive(Debug)]
+pub enum Outcome {
+    Success,
+    Failed(ExampleError),
+    /// Not run because an earlier configuration failed under [`BatchPolicy::FailFast`], or went
+    /// over the warning budget of [`crate::warning::set_budget`]
+    Skipped,
+}
+
+/// Every configuration of the batch with its outcome, in order
+pub struct BatchSummary {
+    pub outcomes: Vec<(RunConfig, Outcome)>,
+}
+
+impl BatchSummary {
+    pub fn all_succeeded(&self) -> bool {
+        self.outcomes
+            .iter()
+            .all(|(_, outcome)| matches!(outcome, Outcome::Success))
+    }
+}
+
+impl fmt::Display for BatchSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "batch summary:")?;
+        for (index, (config, outcome)) in self.outcomes.iter().enumerate() {
+            write!(
+                f,
+                "  #{index} (fold steps {}, elements num {}): ",
+                config.fold_step_count, config.elements_num
+            )?;
+            match outcome {
+                Outcome::Success => writeln!(f, "success")?,
+                Outcome::Failed(err) => {
+                    writeln!(f, "failed: {err}")?;
+                    if let Some(undersized) = err.undersized() {
+                        writeln!(f, "    hint: {undersized}")?;
+                    }
+                }
+                Outcome::Skipped => writeln!(f, "skipped")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Run every configuration of `configs` in order with `run`, following `policy` on failures
+///
+/// ```
+/// # use sirius_fibonacci_example::{
+/// #     batch::{run_batch, BatchPolicy, Outcome}, config::RunConfig, error::ExampleError,
+/// #     run_circuit, testing::test_cache_dir, CircuitSpec,
+/// # };
+/// let config = |fold_step_count| RunConfig {
+///     fold_step_count,
+///     cache_dir: test_cache_dir(),
+///     ..RunConfig::default()
+/// };
+/// // The second one has no step to fold
+/// let configs = || vec![config(2), config(0), config(2)];
+///
+/// let mut runs = 0;
+/// let mut run = |config: &RunConfig| {
+///     runs += 1;
+///     run_circuit(&CircuitSpec::Fibonacci, config, None).map(drop)
+/// };
+///
+/// let summary = run_batch(configs(), BatchPolicy::FailFast, &mut run);
+/// assert!(!summary.all_succeeded());
+/// assert!(matches!(
+///     summary.outcomes[..],
+///     [
+///         (_, Outcome::Success),
+///         (_, Outcome::Failed(ExampleError::InvalidConfig(_))),
+///         (_, Outcome::Skipped)
+///     ]
+/// ));
+///
+/// let summary = run_batch(configs(), BatchPolicy::Continue, &mut run);
+/// assert!(!summary.all_succeeded());
+/// assert!(matches!(
+///     summary.outcomes[..],
+///     [
+///         (_, Outcome::Success),
+///         (_, Outcome::Failed(ExampleError::InvalidConfig(_))),
+///         (_, Outcome::Success)
+///     ]
+/// ));
+/// // The skipped configuration was never run
+/// assert_eq!(runs, 2 + 3);
+/// ```
+pub fn run_batch(
+    configs: Vec<RunConfig>,
+    policy: BatchPolicy,
+    mut run: impl FnMut(&RunConfig) -> Result<(), ExampleError>,
+) -> BatchSummary {
+    let mut aborted = false;
+
+    let outcomes = configs
+        .into_iter()
+        .map(|config| {
+            let outcome = if aborted {
+                Outcome::Skipped
+            } else {
+                match run(&config) {
+                    Ok(()) => Outcome::Success,
+                    Err(err) => {
+                        // Past the warning budget, every later configuration would fail too
+                        aborted = policy == BatchPolicy::FailFast
+                            || matches!(err, ExampleError::WarningBudget { .. });
+                        Outcome::Failed(err)
+                    }
+                }
+            };
+            (config, outcome)
+        })
+        .collect();
+
+    BatchSummary { outcomes }
+}
@@ -0,0 +1,201 @@
+//! Comparing the ways to split the same Fibonacci index into steps and blocks
+
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    config::{RunConfig, MIN_CIRCUIT_TABLE_SIZE},
+    error::ExampleError,
+    field,
+    progress::StepEvent,
+    run_circuit, CircuitSpec, A1, MAX_ELEMENTS_PER_STEP,
+};
+
+/// `steps` steps of `elements_num` elements each, proving the pair at `steps * elements_num`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Factoring {
+    pub steps: usize,
+    pub elements_num: usize,
+}
+
+impl Factoring {
+    /// Primary table size (`k`) the block of `elements_num` elements needs, at least the
+    /// [`MIN_CIRCUIT_TABLE_SIZE`] of the sirius part of the step
+    ///
+    /// The block takes `elements_num + A1` rows, see [`crate::FibonacciCircuit::witness`]
+    pub fn table_size(&self) -> usize {
+        let rows = self.elements_num + A1;
+        MIN_CIRCUIT_TABLE_SIZE.max(rows.next_power_of_two().trailing_zeros() as usize)
+    }
+}
+
+/// Every factoring of `index` into whole steps of at most [`MAX_ELEMENTS_PER_STEP`] elements, by
+/// increasing block size
+///
+/// All of them fold the Fibonacci circuit to the same `z_out`:
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     block_sizes::factorings, circuits::ReferenceStep, FibonacciCircuit, FibonacciIter,
+/// # };
+/// let factorings = factorings(60);
+/// assert_eq!(factorings.len(), 12);
+///
+/// let mut expected = FibonacciIter(C1Scalar::from(0), C1Scalar::from(1)).skip(60);
+/// let expected = [expected.next().unwrap(), expected.next().unwrap()];
+/// for factoring in factorings {
+///     let sc = FibonacciCircuit::new(factoring.elements_num);
+///     let z_out = (0..factoring.steps).fold([0, 1].map(C1Scalar::from), |z, _| {
+///         sc.reference_step(&z)
+///     });
+///     assert_eq!(z_out, expected, "{factoring:?}");
+/// }
+/// ```
+pub fn factorings(index: usize) -> Vec<Factoring> {
+    (1..=index.min(MAX_ELEMENTS_PER_STEP))
+        .filter(|elements_num| index % elements_num == 0)
+        .map(|elements_num| Factoring {
+            steps: index / elements_num,
+            elements_num,
+        })
+        .collect()
+}
+
+/// Outcome of the run of one factoring
+#[derive(Debug)]
+pub struct FactoringTrial {
+    pub factoring: Factoring,
+    /// Primary table size the run used
+    pub table_size: usize,
+    /// Time from the start of the setup to the created IVC, `None` if the setup didn't finish
+    pub setup: Option<Duration>,
+    /// Time from the created IVC to the last folded step, `None` if the setup didn't finish
+    pub fold: Option<Duration>,
+    /// Hex of every `z_out` element
+    pub result: Result<Vec<String>, ExampleError>,
+}
+
+/// Every factoring of the index with its trial, by increasing block size
+pub struct BlockSizeReport {
+    pub index: usize,
+    pub trials: Vec<FactoringTrial>,
+}
+
+impl BlockSizeReport {
+    /// Whether every factoring succeeded with the same `z_out`
+    pub fn z_out_agrees(&self) -> bool {
+        let mut outputs = self.trials.iter().map(|trial| trial.result.as_ref().ok());
+        match outputs.next() {
+            Some(Some(first)) => outputs.all(|z_out| z_out == Some(first)),
+            _ => false,
+        }
+    }
+
+    /// Working factoring with the least setup and fold time
+    pub fn fastest(&self) -> Option<Factoring> {
+        self.trials
+            .iter()
+            .filter(|trial| trial.result.is_ok())
+            .filter_map(|trial| Some((trial.setup? + trial.fold?, trial.factoring)))
+            .min_by_key(|(total, _)| *total)
+            .map(|(_, factoring)| factoring)
+    }
+}
+
+impl fmt::Display for BlockSizeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let millis = |duration: Option<Duration>| {
+            duration.map_or_else(
+                || "-".to_owned(),
+                |duration| duration.as_millis().to_string(),
+            )
+        };
+
+        writeln!(
+            f,
+            "{:>5} | {:>12} | {:>2} | {:>5} | {:>10} | {:>10}",
+            "steps", "elements_num", "k", "works", "setup_ms", "fold_ms"
+        )?;
+        for trial in &self.trials {
+            writeln!(
+                f,
+                "{:>5} | {:>12} | {:>2} | {:>5} | {:>10} | {:>10}",
+                trial.factoring.steps,
+                trial.factoring.elements_num,
+                trial.table_size,
+                trial.result.is_ok(),
+                millis(trial.setup),
+                millis(trial.fold),
+            )?;
+        }
+
+        if !self.z_out_agrees() {
+            writeln!(f, "z_out differs across factorings, or a factoring failed")?;
+        }
+        match self.fastest() {
+            Some(Factoring {
+                steps,
+                elements_num,
+            }) => writeln!(
+                f,
+                "fastest to index {}: {steps} steps of {elements_num} elements",
+                self.index
+            ),
+            None => writeln!(f, "none of the factorings works"),
+        }
+    }
+}
+
+/// Fold the Fibonacci circuit to `index` with each of its [`factorings`], from `config`
+///
+/// A factoring whose block needs a larger table than `config` has runs with the table it needs.
+/// Keys already in the cache dir are loaded instead of generated, so factorings with the same
+/// table and key sizes share them and only the first one pays for their generation; run the
+/// comparison twice to compare setup times with every key cached.
+pub fn compare_block_sizes(config: &RunConfig, index: usize) -> BlockSizeReport {
+    let trials = factorings(index)
+        .into_iter()
+        .map(|factoring| {
+            let table_size = config
+                .primary_circuit_table_size
+                .max(factoring.table_size());
+            let config = RunConfig {
+                fold_step_count: factoring.steps,
+                elements_num: factoring.elements_num,
+                primary_circuit_table_size: table_size,
+                ..config.clone()
+            };
+
+            let mut setup_start = None;
+            let mut setup = None;
+            let mut fold_start = None;
+            let mut fold = None;
+            let mut on_event = |event: StepEvent| match event {
+                StepEvent::SetupStarted => setup_start = Some(Instant::now()),
+                StepEvent::SetupFinished => {
+                    setup = setup_start.map(|start| start.elapsed());
+                    fold_start = Some(Instant::now());
+                }
+                StepEvent::StepFolded { .. } => fold = fold_start.map(|start| start.elapsed()),
+                StepEvent::FoldingFinished { .. } => {}
+            };
+
+            let result = run_circuit(&CircuitSpec::Fibonacci, &config, Some(&mut on_event))
+                .map(|summary| summary.z_out.iter().map(field::to_hex).collect());
+
+            FactoringTrial {
+                factoring,
+                table_size,
+                setup,
+                // Only the base step, folded during the setup
+                fold: fold.or(setup.map(|_| Duration::ZERO)),
+                result,
+            }
+        })
+        .collect();
+
+    BlockSizeReport { index, trials }
+}
@@ -0,0 +1,59 @@
+//! Versions of the code a binary was built from, embedded at compile time by `build.rs`
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+pub const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Version of the resolved `sirius` dependency with its source, e.g. the git tag & commit, or
+/// `unknown` if `Cargo.lock` couldn't be read
+pub const SIRIUS_VERSION: &str = env!("SIRIUS_VERSION");
+
+/// Shown by `--version`
+pub const LONG_VERSION: &str = concat!(
+    env!("CARGO_PKG_VERSION"),
+    "\nsirius ",
+    env!("SIRIUS_VERSION")
+);
+
+/// Versions recorded in an emitted artifact
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildInfo {
+    pub crate_version: String,
+    pub sirius_version: String,
+}
+
+impl BuildInfo {
+    /// Versions of this build
+    ///
+    /// ```
+    /// # use sirius_fibonacci_example::build_info::{BuildInfo, LONG_VERSION};
+    /// let build = BuildInfo::current();
+    /// assert!(!build.crate_version.is_empty());
+    /// assert_eq!(build.crate_version, env!("CARGO_PKG_VERSION"));
+    /// // Resolved from the lock file written by the build
+    /// assert!(build.sirius_version.starts_with("0.1.1"), "{build}");
+    ///
+    /// assert_eq!(
+    ///     LONG_VERSION,
+    ///     format!("{}\nsirius {}", build.crate_version, build.sirius_version)
+    /// );
+    /// ```
+    pub fn current() -> Self {
+        Self {
+            crate_version: CRATE_VERSION.to_owned(),
+            sirius_version: SIRIUS_VERSION.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for BuildInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "sirius-fibonacci-example {}, sirius {}",
+            self.crate_version, self.sirius_version
+        )
+    }
+}
@@ -0,0 +1,304 @@
+//! Self-describing JSON bundle of a proven run, for a standalone verifier
+//!
+//! A bundle is a re-execution recipe, not a portable proof. `sirius` v0.1.1 has no
+//! (de)serialization of the folded `IVC`, so the bundle can't carry proof bytes. Instead it
+//! carries everything needed to rebuild the public parameters and the IVC deterministically: the
+//! verifier re-folds the whole run from `z_0`, which takes as long as proving it did, verifies,
+//! and checks the claimed `z_out` against the one it got. It is no cheaper than the run, and
+//! needs the per-step data of the circuit, which a succinct proof would keep private.
+
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use sirius::prelude::bn256::C1Scalar;
+
+use crate::{
+    build_info::BuildInfo, config::RunConfig, env_info::EnvInfo, error::ExampleError, field,
+    pp_digest::pp_digest, progress::StepEvent, run_circuit, warning, CircuitSpec, RunSummary,
+};
+
+/// Bumped on every incompatible change of [`ProofBundle`]
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Recipe to re-run and verify a run, see the module docs
+///
+/// A proven run, written and read back, verifies as a bundle:
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     bundle::ProofBundle, config::RunConfig, run_circuit, testing::test_cache_dir,
+/// #     CircuitSpec,
+/// # };
+/// let dir = tempfile::tempdir().unwrap();
+/// let path = dir.path().join("bundle.json");
+///
+/// let circuit = CircuitSpec::Sum { addends: vec![3, 4] };
+/// let config = RunConfig {
+///     fold_step_count: 3,
+///     primary_z_0: vec![C1Scalar::from(0)],
+///     cache_dir: test_cache_dir(),
+///     ..RunConfig::default()
+/// };
+/// let summary = run_circuit(&circuit, &config, None).unwrap();
+/// assert_eq!(summary.z_out, [C1Scalar::from(3 + 4 + 3)]);
+///
+/// ProofBundle::new(circuit, config, summary).write(&path).unwrap();
+///
+/// let bundle = ProofBundle::read(&path).unwrap();
+/// assert_eq!(bundle.steps, 3);
+/// bundle.verify(&test_cache_dir(), 0, None).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProofBundle {
+    pub format_version: u32,
+    /// Versions of the code that produced the bundle, absent in bundles written before they were
+    /// recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub built_with: Option<BuildInfo>,
+    /// [`pp_digest`] of the producer's setup, absent in bundles written before it was recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pp_digest: Option<String>,
+    /// Host the bundle was proven on, only recorded with `--env-dump`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<EnvInfo>,
+    /// Primary step circuit with its parameters
+    pub circuit: CircuitSpec,
+    /// Run parameters: step count, table & key sizes and `z_0` of both circuits
+    pub config: RunConfig,
+    /// Claimed number of folded steps, including the base step
+    pub steps: usize,
+    /// Claimed output of the last step
+    #[serde(with = "field::serde_vec")]
+    pub z_out: Vec<C1Scalar>,
+}
+
+impl ProofBundle {
+    pub fn new(circuit: CircuitSpec, config: RunConfig, summary: RunSummary) -> Self {
+        Self {
+            format_version: FORMAT_VERSION,
+            built_with: Some(BuildInfo::current()),
+            pp_digest: Some(pp_digest(&circuit, &config)),
+            environment: None,
+            circuit,
+            config,
+            steps: summary.steps,
+            z_out: summary.z_out,
+        }
+    }
+
+    /// Write the bundle as JSON, [`ProofBundle::read`] gets the same bundle back
+    ///
+    /// ```
+    /// # use sirius::prelude::bn256::C1Scalar;
+    /// # use sirius_fibonacci_example::{
+    /// #     bundle::ProofBundle, config::RunConfig, verification::VerificationReport, CircuitSpec,
+    /// #     RunSummary,
+    /// # };
+    /// let dir = tempfile::tempdir().unwrap();
+    /// let path = dir.path().join("bundle.json");
+    ///
+    /// let summary = RunSummary {
+    ///     steps: 5,
+    ///     z_out: vec![C1Scalar::from(34), C1Scalar::from(55)],
+    ///     verification: Some(VerificationReport::default()),
+    ///     recorded_steps: None,
+    /// };
+    /// // A run without a single step, with a circuit that has per-step data and a bundle of a
+    /// // version that didn't record its build, round-trip as well
+    /// let empty = RunSummary {
+    ///     steps: 0,
+    ///     z_out: vec![],
+    ///     verification: Some(VerificationReport::default()),
+    ///     recorded_steps: None,
+    /// };
+    /// let sum = CircuitSpec::Sum { addends: vec![3, 4] };
+    /// let mut legacy = ProofBundle::new(CircuitSpec::Fibonacci, RunConfig::default(), summary);
+    /// legacy.built_with = None;
+    /// legacy.pp_digest = None;
+    ///
+    /// for bundle in [ProofBundle::new(sum, RunConfig::default(), empty), legacy] {
+    ///     bundle.write(&path).unwrap();
+    ///     assert_eq!(ProofBundle::read(&path).unwrap(), bundle);
+    /// }
+    /// ```
+    pub fn write(&self, path: &Path) -> Result<(), ExampleError> {
+        let json = serde_json::to_string_pretty(self).expect("bundle is always representable");
+        fs::write(path, json).map_err(|err| bundle_err(path, err.to_string()))
+    }
+
+    pub fn read(path: &Path) -> Result<Self, ExampleError> {
+        let json = fs::read_to_string(path).map_err(|err| bundle_err(path, err.to_string()))?;
+        let bundle: Self =
+            serde_json::from_str(&json).map_err(|err| bundle_err(path, err.to_string()))?;
+
+        if bundle.format_version != FORMAT_VERSION {
+            return Err(bundle_err(
+                path,
+                format!(
+                    "format version {} is not supported, expected {FORMAT_VERSION}",
+                    bundle.format_version
+                ),
+            ));
+        }
+
+        Ok(bundle)
+    }
+
+    /// Config to re-run the bundle with, using only what the bundle records about the proof
+    ///
+    /// The public parameters are rebuilt from the recorded table & key sizes, `z_0` and circuit.
+    /// Exactly the claimed steps are folded: what made the producer stop (its step count, a time
+    /// budget, a fixpoint) is not part of the proof. The IVC is always verified, whether or not
+    /// the producer did. The key cache is a local matter, so it is
+    /// the verifier's own `cache_dir`.
+    pub fn verify_config(&self, cache_dir: PathBuf, cache_retries: u32) -> RunConfig {
+        RunConfig {
+            fold_step_count: self.steps,
+            max_time: None,
+            halt_on_fixpoint: false,
+            describe_ivc: false,
+            no_verify: false,
+            cache_dir,
+            cache_retries,
+            ..self.config.clone()
+        }
+    }
+
+    /// Re-run the bundle from `z_0` with its [`ProofBundle::verify_config`], verify the IVC and
+    /// [`ProofBundle::check`] the result
+    pub fn verify(
+        &self,
+        cache_dir: &Path,
+        cache_retries: u32,
+        progress: Option<&mut dyn FnMut(StepEvent)>,
+    ) -> Result<(), ExampleError> {
+        let config = self.verify_config(cache_dir.to_path_buf(), cache_retries);
+
+        let digest = pp_digest(&self.circuit, &config);
+        if let Some(recorded) = self
+            .pp_digest
+            .as_ref()
+            .filter(|recorded| **recorded != digest)
+        {
+            warning::warn(format_args!(
+                "public parameters digest {digest} differs from the recorded {recorded}"
+            ))?;
+        }
+
+        let summary = run_circuit(&self.circuit, &config, progress)?;
+        self.check(&summary)
+    }
+
+    /// Size of the bundle serialized into a scratch buffer, by component
+    ///
+    /// There are no proof bytes to measure (see the module docs), so this is the size of what a
+    /// verifier needs: it grows with the per-step data of the circuit, not with the steps folded
+    pub fn size(&self) -> BundleSize {
+        let serde_json::Value::Object(fields) =
+            serde_json::to_value(self).expect("bundle is always representable")
+        else {
+            unreachable!("a struct is serialized as an object")
+        };
+
+        BundleSize {
+            total: json_len(self),
+            components: fields
+                .iter()
+                .map(|(name, value)| (name.clone(), json_len(value)))
+                .collect(),
+        }
+    }
+
+    /// Check the result of re-running the bundle against its claims
+    ///
+    /// [`ProofBundle::verify`] re-folds and verifies the IVC, and the `z_out` of the re-run is
+    /// compared here, so a tampered claim fails even though the IVC itself verifies. A bundle
+    /// written by a run and tampered with on disk fails `--verify`:
+    ///
+    /// ```
+    /// # use sirius::prelude::bn256::C1Scalar;
+    /// # use sirius_fibonacci_example::{
+    /// #     bundle::ProofBundle, config::RunConfig, error::ExampleError, run_circuit,
+    /// #     testing::test_cache_dir, verify_cache::VerifyCache, CircuitSpec,
+    /// # };
+    /// let dir = tempfile::tempdir().unwrap();
+    /// let path = dir.path().join("bundle.json");
+    ///
+    /// let circuit = CircuitSpec::Sum { addends: vec![3, 4] };
+    /// let config = RunConfig {
+    ///     fold_step_count: 2,
+    ///     primary_z_0: vec![C1Scalar::from(0)],
+    ///     cache_dir: test_cache_dir(),
+    ///     ..RunConfig::default()
+    /// };
+    /// let summary = run_circuit(&circuit, &config, None).unwrap();
+    /// ProofBundle::new(circuit, config, summary).write(&path).unwrap();
+    ///
+    /// assert_eq!(ProofBundle::read(&path).unwrap().z_out, [C1Scalar::from(3 + 4)]);
+    ///
+    /// let mut json: serde_json::Value =
+    ///     serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    /// json["z_out"][0] = "8".into();
+    /// std::fs::write(&path, json.to_string()).unwrap();
+    ///
+    /// let bundle = ProofBundle::read(&path).unwrap();
+    /// assert_eq!(bundle.z_out, [C1Scalar::from(8)]);
+    /// let err = VerifyCache::new(16)
+    ///     .verify(&bundle, &test_cache_dir(), 0, None)
+    ///     .unwrap_err();
+    /// assert!(matches!(err, ExampleError::BundleMismatch(_)), "{err}");
+    /// ```
+    pub fn check(&self, summary: &RunSummary) -> Result<(), ExampleError> {
+        if summary.steps != self.steps {
+            return Err(ExampleError::BundleMismatch(format!(
+                "claimed {} steps, verified {}",
+                self.steps, summary.steps
+            )));
+        }
+        if summary.z_out != self.z_out {
+            return Err(ExampleError::BundleMismatch(format!(
+                "claimed z_out {}, verified {}",
+                self.config.field_format.display_all(&self.z_out),
+                self.config.field_format.display_all(&summary.z_out)
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Serialized size of a [`ProofBundle`], the closest thing to a proof size sirius v0.1.1 allows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleSize {
+    /// Bytes of the whole bundle as compact JSON
+    pub total: usize,
+    /// Bytes of each top-level field as compact JSON, the rest of `total` is the JSON framing
+    pub components: Vec<(String, usize)>,
+}
+
+impl fmt::Display for BundleSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "proof bundle size: {} bytes", self.total)?;
+        for (name, bytes) in &self.components {
+            writeln!(f, "  {name:<14} {bytes:>8}")?;
+        }
+        Ok(())
+    }
+}
+
+fn json_len(value: &impl Serialize) -> usize {
+    serde_json::to_vec(value)
+        .expect("bundle is always representable")
+        .len()
+}
+
+fn bundle_err(path: &Path, err: String) -> ExampleError {
+    ExampleError::Bundle {
+        path: path.to_path_buf(),
+        err,
+    }
+}
@@ -0,0 +1,219 @@
+//! Field-by-field diff of two proof bundles, printed by `--compare-against`
+//!
+//! Meant for regression analysis across versions: a bundle proven with this build compared to
+//! one a previous build wrote from the same run. There are no proof bytes to compare (see
+//! [`crate::bundle`]); what a sirius upgrade changes about the encoding of a proof shows in the
+//! [`crate::pp_digest`] of the public parameters, while the same public inputs, config and
+//! outputs mean its semantics are preserved.
+
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::bundle::ProofBundle;
+
+/// Group of bundle fields reported together
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    /// Step circuit with its data, and `z_0` of both circuits
+    PublicInputs,
+    /// Claimed steps and `z_out`
+    Outputs,
+    /// The rest of the run parameters
+    Config,
+    /// Digest of the public parameters, what changes with the proof encoding
+    PublicParameters,
+    /// Bundle format, build and host, which don't bear on the proof
+    Provenance,
+}
+
+impl Section {
+    pub const ALL: [Self; 5] = [
+        Self::PublicInputs,
+        Self::Outputs,
+        Self::Config,
+        Self::PublicParameters,
+        Self::Provenance,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::PublicInputs => "public inputs",
+            Self::Outputs => "outputs",
+            Self::Config => "config",
+            Self::PublicParameters => "public parameters",
+            Self::Provenance => "provenance",
+        }
+    }
+
+    /// Section of the field at the dotted `path`, `None` for the fields that are not compared
+    fn of(path: &str) -> Option<Self> {
+        let mut segments = path.split(['.', '[']);
+        Some(match segments.next().unwrap_or_default() {
+            "circuit" => Self::PublicInputs,
+            "steps" | "z_out" => Self::Outputs,
+            "pp_digest" => Self::PublicParameters,
+            "format_version" | "built_with" | "environment" => Self::Provenance,
+            "config" => match segments.next().unwrap_or_default() {
+                "primary_z_0" | "secondary_z_0" => Self::PublicInputs,
+                // The key cache is a local matter of whoever proved the bundle
+                "cache_dir" | "cache_retries" => return None,
+                _ => Self::Config,
+            },
+            _ => Self::Provenance,
+        })
+    }
+}
+
+/// One field that differs, with its value in both bundles as compact JSON
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDiff {
+    pub section: Section,
+    /// Dotted path of the field, e.g. `config.fold_step_count` or `z_out[1]`
+    pub path: String,
+    /// `None` if the field is absent from that bundle
+    pub baseline: Option<String>,
+    pub bundle: Option<String>,
+}
+
+/// Every differing field of two bundles, ordered by path
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleDiff(pub Vec<FieldDiff>);
+
+impl BundleDiff {
+    /// Diff of `bundle` against `baseline`
+    ///
+    /// ```
+    /// # use sirius::prelude::bn256::C1Scalar;
+    /// # use sirius_fibonacci_example::{
+    /// #     bundle::ProofBundle,
+    /// #     bundle_diff::{BundleDiff, Section},
+    /// #     config::RunConfig,
+    /// #     CircuitSpec, RunSummary,
+    /// # };
+    /// let summary = RunSummary {
+    ///     steps: 5,
+    ///     z_out: vec![C1Scalar::from(34), C1Scalar::from(55)],
+    ///     verification: None,
+    ///     recorded_steps: None,
+    /// };
+    /// let baseline = ProofBundle::new(CircuitSpec::Fibonacci, RunConfig::default(), summary);
+    /// assert!(BundleDiff::of(&baseline, &baseline).is_empty());
+    ///
+    /// let mut modified = baseline.clone();
+    /// modified.z_out[1] += C1Scalar::from(1);
+    /// modified.config.primary_circuit_table_size += 1;
+    /// modified.config.primary_z_0[0] += C1Scalar::from(1);
+    /// // Not compared, it is only where the producer kept its keys
+    /// modified.config.cache_dir = "elsewhere".into();
+    ///
+    /// let diff = BundleDiff::of(&baseline, &modified);
+    /// let paths = diff.0.iter().map(|field| field.path.as_str()).collect::<Vec<_>>();
+    /// assert_eq!(
+    ///     paths,
+    ///     ["config.primary_circuit_table_size", "config.primary_z_0[0]", "z_out[1]"]
+    /// );
+    /// assert!(!diff.matches(Section::PublicInputs));
+    /// assert!(!diff.matches(Section::Outputs));
+    /// assert!(diff.matches(Section::PublicParameters));
+    /// ```
+    pub fn of(baseline: &ProofBundle, bundle: &ProofBundle) -> Self {
+        let mut fields = vec![];
+        diff_values(
+            "",
+            Some(&to_json(baseline)),
+            Some(&to_json(bundle)),
+            &mut fields,
+        );
+        Self(fields)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether no field of `section` differs
+    pub fn matches(&self, section: Section) -> bool {
+        self.0.iter().all(|field| field.section != section)
+    }
+}
+
+impl fmt::Display for BundleDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for section in Section::ALL {
+            if self.matches(section) {
+                writeln!(f, "{}: match", section.name())?;
+                continue;
+            }
+
+            writeln!(f, "{}: differ", section.name())?;
+            for field in self.0.iter().filter(|field| field.section == section) {
+                let absent = "absent".to_owned();
+                writeln!(
+                    f,
+                    "  - {}: {}",
+                    field.path,
+                    field.baseline.as_ref().unwrap_or(&absent)
+                )?;
+                writeln!(
+                    f,
+                    "  + {}: {}",
+                    field.path,
+                    field.bundle.as_ref().unwrap_or(&absent)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn to_json(bundle: &ProofBundle) -> Value {
+    serde_json::to_value(bundle).expect("bundle is always representable")
+}
+
+/// Recurse into the objects, and the arrays of the same length, to report the differing leaves
+fn diff_values(
+    path: &str,
+    baseline: Option<&Value>,
+    bundle: Option<&Value>,
+    fields: &mut Vec<FieldDiff>,
+) {
+    match (baseline, bundle) {
+        (Some(Value::Object(baseline)), Some(Value::Object(bundle))) => {
+            let mut keys = baseline.keys().collect::<Vec<_>>();
+            keys.extend(bundle.keys().filter(|key| !baseline.contains_key(*key)));
+            keys.sort();
+            for key in keys {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                diff_values(&path, baseline.get(key), bundle.get(key), fields);
+            }
+        }
+        (Some(Value::Array(baseline)), Some(Value::Array(bundle)))
+            if baseline.len() == bundle.len() =>
+        {
+            for (index, (baseline, bundle)) in baseline.iter().zip(bundle).enumerate() {
+                diff_values(
+                    &format!("{path}[{index}]"),
+                    Some(baseline),
+                    Some(bundle),
+                    fields,
+                );
+            }
+        }
+        (baseline, bundle) if baseline != bundle => {
+            if let Some(section) = Section::of(path) {
+                fields.push(FieldDiff {
+                    section,
+                    path: path.to_owned(),
+                    baseline: baseline.map(Value::to_string),
+                    bundle: bundle.map(Value::to_string),
+                });
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,233 @@
+//! Commitment-key cache helpers
+//!
+//! On networked or shared filesystems writing (or reading) the cache can fail intermittently, so
+//! every cache access goes through [`with_retry`].
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io,
+    path::Path,
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+use fs2::FileExt;
+
+use sirius::{halo2_proofs::arithmetic::CurveAffine, prelude::CommitmentKey};
+
+use crate::output::{self, Phase};
+
+/// Delay before the first retry, doubled after every failed attempt
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Errors that are worth retrying: the ones a flaky filesystem produces intermittently
+fn is_transient(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+    )
+}
+
+/// Runs `op`, retrying it up to `retries` times with exponential backoff on transient IO errors
+///
+/// `NotFound` is not transient: it means there is no cache yet (or the directory was removed), so
+/// the directory is recreated once and `op` falls through to a fresh setup without waiting.
+pub fn with_retry<T>(
+    dir: &Path,
+    retries: u32,
+    mut op: impl FnMut() -> io::Result<T>,
+) -> io::Result<T> {
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempt = 0;
+    let mut recreated = false;
+
+    loop {
+        match op() {
+            Ok(value) => return Ok(value),
+            Err(err) if err.kind() == io::ErrorKind::NotFound && !recreated => {
+                output::info(
+                    Phase::Setup,
+                    format_args!("cache not found in {dir:?}, falling through to setup"),
+                );
+                fs::create_dir_all(dir)?;
+                recreated = true;
+            }
+            Err(err) if is_transient(&err) && attempt < retries => {
+                attempt += 1;
+                output::info(
+                    Phase::Setup,
+                    format_args!(
+                        "cache io failed: {err}, retry {attempt}/{retries} in {backoff:?}"
+                    ),
+                );
+                thread::sleep(backoff);
+                backoff *= 2;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Check from its metadata, without touching the filesystem, that `dir` looks able to hold the
+/// cache
+///
+/// A missing `dir` is checked through its nearest existing ancestor, in which
+/// [`load_or_setup`] would create it. Only the read-only permission is checked, so e.g. a
+/// directory owned by another user still passes, see [`probe_writable`] for a check that doesn't.
+pub fn check_writable(dir: &Path) -> io::Result<()> {
+    let (existing, metadata) = nearest_existing_dir(dir)?;
+
+    if metadata.permissions().readonly() {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("{existing:?} is read-only"),
+        ));
+    }
+    Ok(())
+}
+
+/// Check that `dir` can hold the cache by creating a file in it, and removing it again
+///
+/// A missing `dir` is probed through its nearest existing ancestor, in which [`load_or_setup`]
+/// would create it, so nothing is left behind either way. Unlike [`check_writable`] this catches
+/// every reason a write fails, e.g. the permissions of another owner.
+pub fn probe_writable(dir: &Path) -> io::Result<()> {
+    static PROBES: AtomicUsize = AtomicUsize::new(0);
+
+    let (existing, _) = nearest_existing_dir(dir)?;
+    // Unique among concurrent probes of the same dir, in this process or another one
+    let probe = existing.join(format!(
+        ".write-probe-{}-{}",
+        process::id(),
+        PROBES.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)?;
+    fs::remove_file(&probe)
+}
+
+/// `dir`, or else its nearest ancestor, that exists, with its metadata; an error if it is not a
+/// directory
+fn nearest_existing_dir(dir: &Path) -> io::Result<(&Path, fs::Metadata)> {
+    let (existing, metadata) = dir
+        .ancestors()
+        // `""` is the parent of a relative path with a single component
+        .map(|ancestor| {
+            if ancestor.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                ancestor
+            }
+        })
+        .find_map(|ancestor| Some((ancestor, fs::metadata(ancestor).ok()?)))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no existing ancestor"))?;
+
+    if !metadata.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            format!("{existing:?} is not a directory"),
+        ));
+    }
+    Ok((existing, metadata))
+}
+
+/// Load the commitment key `name` from `dir`, or generate it with size `k` and store it there
+///
+/// Holds an exclusive advisory lock on `dir/<name>.lock` meanwhile, so that concurrent runs
+/// sharing `dir` (e.g. parallel CI jobs) wait for the one generating the key instead of reading
+/// a half-written file. Two setups racing on a cold cache both get keys that fold and verify:
+///
+/// ```
+/// # use sirius_fibonacci_example::{
+/// #     config::RunConfig,
+/// #     runner::{CommitmentKeys, FoldRunner},
+/// #     FibonacciCircuit,
+/// # };
+/// let cache = tempfile::tempdir().unwrap();
+/// let config = RunConfig {
+///     cache_dir: cache.path().to_path_buf(),
+///     ..RunConfig::default()
+/// };
+///
+/// let keys = std::thread::scope(|scope| {
+///     // Both spawned before either is joined
+///     [(); 2]
+///         .map(|_| scope.spawn(|| CommitmentKeys::setup(&config)))
+///         .map(|setup| setup.join().unwrap().unwrap())
+/// });
+///
+/// let circuit = FibonacciCircuit::new(config.elements_num);
+/// for keys in &keys {
+///     let mut runner = FoldRunner::new(keys, &config, &circuit).unwrap();
+///     runner.step(&circuit).unwrap();
+///     runner.assert_sound().unwrap();
+/// }
+/// ```
+///
+/// # Safety
+/// The key files are reflected directly from memory, so a file in `dir` must be a correct cache
+/// produced by this function, see [`CommitmentKey::load_or_setup_cache`]
+pub unsafe fn load_or_setup<C: CurveAffine>(
+    dir: &Path,
+    name: &str,
+    k: usize,
+    retries: u32,
+) -> io::Result<CommitmentKey<C>> {
+    fs::create_dir_all(dir)?;
+    let lock = File::create(dir.join(format!("{name}.lock")))?;
+    lock.lock_exclusive()?;
+
+    // Unlocked when `lock` is dropped
+    with_retry(dir, retries, || unsafe {
+        CommitmentKey::<C>::load_or_setup_cache(dir, name, k)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    /// An op failing with `kind` on its first call and returning its call count after
+    fn fails_once(calls: &Cell<u32>, kind: io::ErrorKind) -> impl FnMut() -> io::Result<u32> + '_ {
+        move || {
+            calls.set(calls.get() + 1);
+            match calls.get() {
+                1 => Err(io::Error::from(kind)),
+                calls => Ok(calls),
+            }
+        }
+    }
+
+    #[test]
+    fn transient_failure_is_retried() {
+        let dir = tempfile::tempdir().unwrap();
+        let calls = Cell::new(0);
+
+        let op = fails_once(&calls, io::ErrorKind::Interrupted);
+        assert_eq!(with_retry(dir.path(), 1, op).unwrap(), 2);
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn persistent_failure_is_not_retried() {
+        let dir = tempfile::tempdir().unwrap();
+        for kind in [
+            io::ErrorKind::UnexpectedEof,
+            io::ErrorKind::WriteZero,
+            io::ErrorKind::Other,
+        ] {
+            let calls = Cell::new(0);
+
+            let op = fails_once(&calls, kind);
+            assert_eq!(with_retry(dir.path(), 3, op).unwrap_err().kind(), kind);
+            assert_eq!(calls.get(), 1);
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Expression, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::{
+    gadgets::{self, RangeCheckConfig},
+    ReferenceStep,
+};
+
+/// Fractional bits of the fixed-point average
+pub const AVERAGE_SCALE_BITS: u32 = 16;
+
+/// The fixed-point average `a` stands for `a / AVERAGE_SCALE`
+pub const AVERAGE_SCALE: u64 = 1 << AVERAGE_SCALE_BITS;
+
+/// Values are `u64`, so their scaled average fits into this many bits
+const AVERAGE_BITS: usize = 64 + AVERAGE_SCALE_BITS as usize;
+
+/// The count is a `u64`, so is the gap between the remainder and it
+const GAP_BITS: usize = 64;
+
+/// Fixed-point average of `count` values summing to `sum`: `floor(sum * AVERAGE_SCALE / count)`,
+/// the one [`AverageCircuit`] proves for its state
+///
+/// `None` for no values, or if the scaled sum overflows
+///
+/// ```
+/// # use sirius_fibonacci_example::circuits::{fixed_point_average, AVERAGE_SCALE};
+///
+/// // 1, 2 and 4 average to 2.333.., truncated to the scale
+/// let average = fixed_point_average(1 + 2 + 4, 3).unwrap();
+/// assert_eq!(average, 7 * AVERAGE_SCALE as u128 / 3);
+/// assert!((average as f64 / AVERAGE_SCALE as f64 - 7.0 / 3.0).abs() < 1.0 / AVERAGE_SCALE as f64);
+/// ```
+pub fn fixed_point_average(sum: u128, count: u128) -> Option<u128> {
+    sum.checked_mul(AVERAGE_SCALE as u128)?.checked_div(count)
+}
+
+/// Configuration for the [`AverageCircuit`], the step takes its first row, the rest are the range
+/// checks
+#[derive(Debug, Clone)]
+pub struct AverageConfig {
+    s: Selector,
+    /// Sum of the values so far
+    sum: Column<Advice>,
+    /// Number of values so far
+    count: Column<Advice>,
+    /// Per-step value, a private witness of the step
+    value: Column<Advice>,
+    /// `sum + value`
+    sum_next: Column<Advice>,
+    /// `count + 1`
+    count_next: Column<Advice>,
+    /// Fixed-point average of the new state, range-checked into [`AVERAGE_BITS`]
+    average: Column<Advice>,
+    /// Remainder of the scaled division
+    rem: Column<Advice>,
+    /// `count_next - 1 - rem`, range-checked to prove `rem < count_next`
+    gap: Column<Advice>,
+    range: RangeCheckConfig,
+}
+
+/// Circuit that takes a new value every step and keeps a running average:
+/// `[sum, count] -> [sum + value, count + 1]`
+///
+/// Division is not native to the field, so the step proves the fixed-point average of the new
+/// state by multiplication instead: it witnesses `average` and `rem` with
+/// `sum * AVERAGE_SCALE = average * count + rem`, range-checking `average` and `count - 1 - rem`
+/// so that neither can wrap around the field. That makes `average` the truncated quotient, see
+/// [`fixed_point_average`]. It is not part of the state: the state stays exact and any step can
+/// compute the average of it on demand. `z_0` is `[0, 0]` for a fresh average. Like the
+/// [`super::SumCircuit`], each step gets its own instance.
+#[derive(Clone)]
+pub struct AverageCircuit {
+    value: u64,
+}
+
+impl AverageCircuit {
+    pub fn new(value: u64) -> Self {
+        Self { value }
+    }
+}
+
+impl<F: PrimeField> StepCircuit<2, F> for AverageCircuit {
+    type Config = AverageConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.selector(),
+            sum: cs.advice_column(),
+            count: cs.advice_column(),
+            value: cs.advice_column(),
+            sum_next: cs.advice_column(),
+            count_next: cs.advice_column(),
+            average: cs.advice_column(),
+            rem: cs.advice_column(),
+            gap: cs.advice_column(),
+            range: RangeCheckConfig::configure(cs),
+        };
+
+        cs.enable_equality(config.sum);
+        cs.enable_equality(config.count);
+        cs.enable_equality(config.sum_next);
+        cs.enable_equality(config.count_next);
+        cs.enable_equality(config.average);
+        cs.enable_equality(config.gap);
+
+        cs.create_gate("average", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let sum = meta.query_advice(config.sum, Rotation::cur());
+            let count = meta.query_advice(config.count, Rotation::cur());
+            let value = meta.query_advice(config.value, Rotation::cur());
+            let sum_next = meta.query_advice(config.sum_next, Rotation::cur());
+            let count_next = meta.query_advice(config.count_next, Rotation::cur());
+            let average = meta.query_advice(config.average, Rotation::cur());
+            let rem = meta.query_advice(config.rem, Rotation::cur());
+            let gap = meta.query_advice(config.gap, Rotation::cur());
+
+            let one = Expression::Constant(F::ONE);
+            vec![
+                s.clone() * (sum_next.clone() - (sum + value)),
+                s.clone() * (count_next.clone() - (count + one.clone())),
+                s.clone()
+                    * (sum_next * F::from(AVERAGE_SCALE)
+                        - (average * count_next.clone() + rem.clone())),
+                s * (gap - (count_next - one - rem)),
+            ]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; 2],
+    ) -> Result<[AssignedCell<F, F>; 2], SynthesisError> {
+        layouter.assign_region(
+            || "average",
+            |mut region| {
+                let [sum, count] = z_i;
+
+                config.s.enable(&mut region, 0)?;
+
+                let sum = sum.copy_advice(|| "sum", &mut region, config.sum, 0)?;
+                let count = count.copy_advice(|| "count", &mut region, config.count, 0)?;
+                let value = Value::known(F::from(self.value));
+                region.assign_advice(|| "value", config.value, 0, || value)?;
+
+                let sum_next = sum.value().copied() + value;
+                let count_next = count.value().map(|count| *count + F::ONE);
+                let quotient = sum_next.zip(count_next).map(|(sum, count)| {
+                    let sum = gadgets::to_u128(&sum).unwrap_or_default();
+                    let count = gadgets::to_u128(&count).unwrap_or_default();
+                    let average = fixed_point_average(sum, count).unwrap_or_default();
+                    let rem = (sum.wrapping_mul(AVERAGE_SCALE as u128))
+                        .wrapping_sub(average.wrapping_mul(count));
+                    (average, rem, count.wrapping_sub(1).wrapping_sub(rem))
+                });
+
+                let sum_next =
+                    region.assign_advice(|| "sum_next", config.sum_next, 0, || sum_next)?;
+                let count_next =
+                    region.assign_advice(|| "count_next", config.count_next, 0, || count_next)?;
+                let average = region.assign_advice(
+                    || "average",
+                    config.average,
+                    0,
+                    || quotient.map(|(average, _, _)| F::from_u128(average)),
+                )?;
+                region.assign_advice(
+                    || "rem",
+                    config.rem,
+                    0,
+                    || quotient.map(|(_, rem, _)| F::from_u128(rem)),
+                )?;
+                let gap = region.assign_advice(
+                    || "gap",
+                    config.gap,
+                    0,
+                    || quotient.map(|(_, _, gap)| F::from_u128(gap)),
+                )?;
+
+                config
+                    .range
+                    .assign(&mut region, 1, &average, AVERAGE_BITS)?;
+                config.range.assign(
+                    &mut region,
+                    1 + RangeCheckConfig::rows(AVERAGE_BITS),
+                    &gap,
+                    GAP_BITS,
+                )?;
+
+                Ok([sum_next, count_next])
+            },
+        )
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<2, F> for AverageCircuit {
+    fn reference_step(&self, [sum, count]: &[F; 2]) -> [F; 2] {
+        [*sum + F::from(self.value), *count + F::ONE]
+    }
+}
@@ -0,0 +1,196 @@
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Expression, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::{gadgets, ReferenceStep};
+
+/// Configuration for the [`BitAccumCircuit`], one row per bit
+#[derive(Debug, Clone)]
+pub struct BitAccumConfig {
+    /// Enforces zero accumulators on the first row
+    s_init: Selector,
+    /// Enforces the per-bit relations
+    s_bit: Selector,
+    /// Bit of the state
+    a: Column<Advice>,
+    /// Bit of the step input
+    b: Column<Advice>,
+    /// `a XOR b`
+    c: Column<Advice>,
+    /// Running recomposition of the state bits, equal to `z_in` on the last row
+    acc_in: Column<Advice>,
+    /// Running recomposition of the XOR bits, the output on the last row
+    acc_out: Column<Advice>,
+}
+
+/// Circuit that keeps a `bit_width`-bit accumulator and XORs the step input into it every step.
+///
+/// Arithmetic circuits have no native bitwise operations, so the state and the input are
+/// decomposed into boolean witnesses, each pair of bits is XOR-ed with `c = a + b - 2ab` and the
+/// result is recomposed. This also proves the state fits into `bit_width` bits.
+///
+/// The cost is `bit_width + 1` rows and five advice columns per step, i.e. linear in the width,
+/// compared to a single row for an addition: keep the width small.
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     circuits::{BitAccumCircuit, ReferenceStep},
+/// #     mock,
+/// #     testing::prove_and_verify,
+/// # };
+/// let sc = BitAccumCircuit::new(8, 0b1010_1010);
+/// let z = [C1Scalar::from(0b1111_0000)];
+/// mock::check_step(5, &sc, z).unwrap();
+/// let z = sc.reference_step(&z);
+/// assert_eq!(z, [C1Scalar::from(0b0101_1010)]);
+///
+/// // XOR-ing the same input twice is the identity
+/// mock::check_step(5, &sc, z).unwrap();
+/// assert_eq!(sc.reference_step(&z), [C1Scalar::from(0b1111_0000)]);
+///
+/// // A state over 8 bits doesn't decompose
+/// mock::check_step(5, &sc, [C1Scalar::from(1 << 8)]).unwrap_err();
+///
+/// let z_out = prove_and_verify(&BitAccumCircuit::new(8, 0xff), [C1Scalar::from(0x0f)], 3);
+/// assert_eq!(z_out, [C1Scalar::from(0xf0)]);
+/// ```
+#[derive(Clone)]
+pub struct BitAccumCircuit {
+    bit_width: usize,
+    input: u64,
+}
+
+impl BitAccumCircuit {
+    /// # Panics
+    /// If `bit_width` is not in `1..=64`
+    pub fn new(bit_width: usize, input: u64) -> Self {
+        assert!((1..=64).contains(&bit_width), "bit width must be in 1..=64");
+
+        Self {
+            bit_width,
+            input: input & Self::mask(bit_width),
+        }
+    }
+
+    fn mask(bit_width: usize) -> u64 {
+        u64::MAX >> (64 - bit_width)
+    }
+}
+
+impl<F: PrimeField> StepCircuit<1, F> for BitAccumCircuit {
+    type Config = BitAccumConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s_init: cs.selector(),
+            s_bit: cs.selector(),
+            a: cs.advice_column(),
+            b: cs.advice_column(),
+            c: cs.advice_column(),
+            acc_in: cs.advice_column(),
+            acc_out: cs.advice_column(),
+        };
+
+        cs.enable_equality(config.acc_in);
+        cs.enable_equality(config.acc_out);
+
+        cs.create_gate("bit-accum-init", |meta| {
+            let s = meta.query_selector(config.s_init);
+
+            let acc_in = meta.query_advice(config.acc_in, Rotation::cur());
+            let acc_out = meta.query_advice(config.acc_out, Rotation::cur());
+
+            vec![s.clone() * acc_in, s * acc_out]
+        });
+
+        cs.create_gate("bit-accum-xor", |meta| {
+            let s = meta.query_selector(config.s_bit);
+
+            let a = meta.query_advice(config.a, Rotation::cur());
+            let b = meta.query_advice(config.b, Rotation::cur());
+            let c = meta.query_advice(config.c, Rotation::cur());
+            let acc_in = meta.query_advice(config.acc_in, Rotation::cur());
+            let acc_in_prev = meta.query_advice(config.acc_in, Rotation::prev());
+            let acc_out = meta.query_advice(config.acc_out, Rotation::cur());
+            let acc_out_prev = meta.query_advice(config.acc_out, Rotation::prev());
+
+            let one = Expression::Constant(F::ONE);
+            let two = F::from(2u64);
+
+            vec![
+                s.clone() * a.clone() * (one.clone() - a.clone()),
+                s.clone() * b.clone() * (one - b.clone()),
+                s.clone() * (c.clone() - (a.clone() + b.clone() - a.clone() * b * two)),
+                s.clone() * (acc_in - (acc_in_prev * two + a)),
+                s * (acc_out - (acc_out_prev * two + c)),
+            ]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; 1],
+    ) -> Result<[AssignedCell<F, F>; 1], SynthesisError> {
+        let bit_value = |bit: bool| if bit { F::ONE } else { F::ZERO };
+        let two = F::from(2u64);
+
+        let z_out = layouter.assign_region(
+            || "bit-accum",
+            |mut region| {
+                let [z] = z_i;
+
+                config.s_init.enable(&mut region, 0)?;
+                let zero = || Value::known(F::ZERO);
+                let mut acc_in = region.assign_advice(|| "acc_in", config.acc_in, 0, zero)?;
+                let mut acc_out = region.assign_advice(|| "acc_out", config.acc_out, 0, zero)?;
+
+                // Most significant bit first
+                for (row, index) in (1..).zip((0..self.bit_width).rev()) {
+                    config.s_bit.enable(&mut region, row)?;
+
+                    let a = z.value().map(|z| bit_value(gadgets::nth_bit(z, index)));
+                    let b = Value::known(bit_value((self.input >> index) & 1 == 1));
+                    let c = a.zip(b).map(|(a, b)| a + b - a * b * two);
+
+                    region.assign_advice(|| "a", config.a, row, || a)?;
+                    region.assign_advice(|| "b", config.b, row, || b)?;
+                    region.assign_advice(|| "c", config.c, row, || c)?;
+
+                    let next = acc_in.value().zip(a).map(|(acc, a)| *acc * two + a);
+                    acc_in = region.assign_advice(|| "acc_in", config.acc_in, row, || next)?;
+
+                    let next = acc_out.value().zip(c).map(|(acc, c)| *acc * two + c);
+                    acc_out = region.assign_advice(|| "acc_out", config.acc_out, row, || next)?;
+                }
+
+                // The state bits must add up to the input
+                region.constrain_equal(acc_in.cell(), z.cell())?;
+
+                Ok(acc_out)
+            },
+        )?;
+
+        Ok([z_out])
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<1, F> for BitAccumCircuit {
+    fn reference_step(&self, [z]: &[F; 1]) -> [F; 1] {
+        let z = gadgets::to_u128(z).unwrap_or_default() as u64 & Self::mask(self.bit_width);
+        [F::from(z ^ self.input)]
+    }
+}
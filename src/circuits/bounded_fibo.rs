@@ -0,0 +1,253 @@
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Expression, Fixed, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::{
+    gadgets::{self, RangeCheckConfig},
+    ReferenceStep,
+};
+
+/// Bits of the range-checked distance to the bound: while running the pair stays within the
+/// `u64` bound, and the frozen pair within twice it, so their sum fits into 66 bits
+const DIFF_BITS: usize = 66;
+
+/// Configuration for the [`BoundedFiboCircuit`], the step takes its first row, the rest is the
+/// range check
+#[derive(Debug, Clone)]
+pub struct BoundedFiboConfig {
+    s: Selector,
+    a: Column<Advice>,
+    b: Column<Advice>,
+    /// Sticky flag, `1` once a value exceeded the bound
+    halted: Column<Advice>,
+    /// Hint: `1` when `a + b` is above the bound, `0` otherwise
+    over: Column<Advice>,
+    /// Distance between `a + b` and the bound, range-checked to prove the comparison
+    diff: Column<Advice>,
+    a_next: Column<Advice>,
+    b_next: Column<Advice>,
+    halted_next: Column<Advice>,
+    bound: Column<Fixed>,
+    range: RangeCheckConfig,
+}
+
+/// The Fibonacci recurrence, one element per step, frozen once a value exceeds `bound`:
+/// `[a, b, halted]`
+///
+/// While `halted` is `0`, the pair advances to `[b, a + b]` and `halted` is set if `a + b` is
+/// above the bound. Once set, the flag sticks (`halted' = halted + (1 - halted) * over`) and
+/// every later step is a no-op that keeps the whole state, so the IVC ends at the first pair
+/// past the bound however many steps are folded, the pattern of a bounded computation that
+/// terminates early. The comparison is proven like the cap of the [`super::SaturatingSumCircuit`],
+/// with a boolean hint and the range-checked distance to the bound, as long as `z_0` fits into
+/// `u64`. `z_0` is `[0, 1, 0]` for the sequence from its start.
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     circuits::{BoundedFiboCircuit, ReferenceStep},
+/// #     mock,
+/// #     testing::prove_and_verify,
+/// # };
+/// let sc = BoundedFiboCircuit::new(100);
+///
+/// let mut z = [0, 1, 0].map(C1Scalar::from);
+/// for _ in 0..20 {
+///     // Every step is satisfiable, the frozen ones included
+///     mock::check_step(8, &sc, z).unwrap();
+///     z = sc.reference_step(&z);
+/// }
+/// // 144 is the first value above 100, reached by the 11th step and kept by the 9 later ones
+/// assert_eq!(z, [89, 144, 1].map(C1Scalar::from));
+///
+/// // Folded, with a bound of 5 the 5th step halts on 8
+/// let z_out = prove_and_verify(&BoundedFiboCircuit::new(5), [0, 1, 0].map(C1Scalar::from), 8);
+/// assert_eq!(z_out, [5, 8, 1].map(C1Scalar::from));
+/// ```
+#[derive(Clone)]
+pub struct BoundedFiboCircuit {
+    bound: u64,
+}
+
+impl BoundedFiboCircuit {
+    pub fn new(bound: u64) -> Self {
+        Self { bound }
+    }
+
+    /// Whether `a + b` is above the bound, `a` & `b` must fit into `u64`
+    fn is_over<F: PrimeField>(&self, a: &F, b: &F) -> bool {
+        let to_u128 = |value| gadgets::to_u128(value).unwrap_or(u128::MAX);
+        to_u128(a).saturating_add(to_u128(b)) > self.bound as u128
+    }
+}
+
+impl<F: PrimeField> StepCircuit<3, F> for BoundedFiboCircuit {
+    type Config = BoundedFiboConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.selector(),
+            a: cs.advice_column(),
+            b: cs.advice_column(),
+            halted: cs.advice_column(),
+            over: cs.advice_column(),
+            diff: cs.advice_column(),
+            a_next: cs.advice_column(),
+            b_next: cs.advice_column(),
+            halted_next: cs.advice_column(),
+            bound: cs.fixed_column(),
+            range: RangeCheckConfig::configure(cs),
+        };
+
+        cs.enable_equality(config.a);
+        cs.enable_equality(config.b);
+        cs.enable_equality(config.halted);
+        cs.enable_equality(config.diff);
+        cs.enable_equality(config.a_next);
+        cs.enable_equality(config.b_next);
+        cs.enable_equality(config.halted_next);
+
+        cs.create_gate("bounded-fibo", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let a = meta.query_advice(config.a, Rotation::cur());
+            let b = meta.query_advice(config.b, Rotation::cur());
+            let halted = meta.query_advice(config.halted, Rotation::cur());
+            let over = meta.query_advice(config.over, Rotation::cur());
+            let diff = meta.query_advice(config.diff, Rotation::cur());
+            let a_next = meta.query_advice(config.a_next, Rotation::cur());
+            let b_next = meta.query_advice(config.b_next, Rotation::cur());
+            let halted_next = meta.query_advice(config.halted_next, Rotation::cur());
+            let bound = meta.query_fixed(config.bound, Rotation::cur());
+
+            let one = Expression::Constant(F::ONE);
+            let sum = a.clone() + b.clone();
+            let under = one.clone() - over.clone();
+            let running = one.clone() - halted.clone();
+
+            vec![
+                s.clone() * (halted.clone() * running.clone()),
+                s.clone() * (over.clone() * under.clone()),
+                s.clone()
+                    * (diff
+                        - (over.clone() * (sum.clone() - bound.clone() - one)
+                            + under * (bound - sum))),
+                // A halted step keeps the pair, a running one advances it
+                s.clone() * (a_next - (a.clone() + running.clone() * (b.clone() - a.clone()))),
+                s.clone() * (b_next - (b + running.clone() * a)),
+                s * (halted_next - (halted + running * over)),
+            ]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; 3],
+    ) -> Result<[AssignedCell<F, F>; 3], SynthesisError> {
+        let bound = F::from(self.bound);
+
+        let z_out = layouter.assign_region(
+            || "bounded-fibo",
+            |mut region| {
+                let [a, b, halted] = z_i;
+
+                config.s.enable(&mut region, 0)?;
+                region.assign_fixed(|| "bound", config.bound, 0, || Value::known(bound))?;
+
+                let a = a.copy_advice(|| "a", &mut region, config.a, 0)?;
+                let b = b.copy_advice(|| "b", &mut region, config.b, 0)?;
+                let halted = halted.copy_advice(|| "halted", &mut region, config.halted, 0)?;
+
+                let (a, b, halted) = (
+                    a.value().copied(),
+                    b.value().copied(),
+                    halted.value().copied(),
+                );
+                let sum = a + b;
+                let over = a.zip(b).map(|(a, b)| self.is_over(&a, &b));
+                region.assign_advice(
+                    || "over",
+                    config.over,
+                    0,
+                    || over.map(|over| F::from(over as u64)),
+                )?;
+
+                let diff = region.assign_advice(
+                    || "diff",
+                    config.diff,
+                    0,
+                    || {
+                        sum.zip(over).map(|(sum, over)| {
+                            if over {
+                                sum - bound - F::ONE
+                            } else {
+                                bound - sum
+                            }
+                        })
+                    },
+                )?;
+                config.range.assign(&mut region, 1, &diff, DIFF_BITS)?;
+
+                let running = halted.map(|halted| halted == F::ZERO);
+                let a_next = region.assign_advice(
+                    || "a_next",
+                    config.a_next,
+                    0,
+                    || {
+                        a.zip(b)
+                            .zip(running)
+                            .map(|((a, b), running)| if running { b } else { a })
+                    },
+                )?;
+                let b_next = region.assign_advice(
+                    || "b_next",
+                    config.b_next,
+                    0,
+                    || {
+                        b.zip(sum)
+                            .zip(running)
+                            .map(|((b, sum), running)| if running { sum } else { b })
+                    },
+                )?;
+                let halted_next = region.assign_advice(
+                    || "halted_next",
+                    config.halted_next,
+                    0,
+                    || {
+                        halted
+                            .zip(over)
+                            .map(|(halted, over)| F::from((halted != F::ZERO || over) as u64))
+                    },
+                )?;
+
+                Ok([a_next, b_next, halted_next])
+            },
+        )?;
+
+        Ok(z_out)
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<3, F> for BoundedFiboCircuit {
+    fn reference_step(&self, z_i: &[F; 3]) -> [F; 3] {
+        let [a, b, halted] = *z_i;
+        if halted != F::ZERO {
+            return *z_i;
+        }
+
+        [b, a + b, F::from(self.is_over(&a, &b) as u64)]
+    }
+}
@@ -0,0 +1,188 @@
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::ReferenceStep;
+
+/// Configuration for the [`ChallengeAccumCircuit`], a single row holds the whole step
+#[derive(Debug, Clone)]
+pub struct ChallengeAccumConfig {
+    s: Selector,
+    acc_in: Column<Advice>,
+    challenge_in: Column<Advice>,
+    /// Per-step claim, a private witness of the step
+    claim: Column<Advice>,
+    /// Hint: `(challenge_in + claim)^2`
+    sq: Column<Advice>,
+    /// Hint: `(challenge_in + claim)^4`
+    quad: Column<Advice>,
+    acc_out: Column<Advice>,
+    challenge_out: Column<Advice>,
+}
+
+/// Circuit that folds one claim per step into an accumulator, the way a recursive verifier
+/// batches the checks of the proofs it verifies
+///
+/// `sirius` v0.1.1 exposes no in-circuit verifier gadget to a step circuit, so instead of
+/// verifying an inner proof each step absorbs its claim into a transcript and accumulates it with
+/// the challenge squeezed from there, the random linear combination a verifier would check once
+/// at the end. The state is `[acc, challenge]`:
+///
+/// - `challenge' = (challenge + claim)^5`, an `x^5` S-box as the transcript permutation
+/// - `acc' = acc + challenge' * claim`
+///
+/// Like the [`super::SumCircuit`], each step needs its own claim, so each step gets its own
+/// instance
+///
+/// ```
+/// # use sirius::{ff::Field, prelude::bn256::C1Scalar};
+/// # use sirius_fibonacci_example::{
+/// #     circuits::{ChallengeAccumCircuit, ReferenceStep},
+/// #     mock,
+/// #     testing::prove_and_verify,
+/// # };
+/// // The accumulation on the CPU
+/// let absorb = |[acc, challenge]: [C1Scalar; 2], claim: u64| {
+///     let claim = C1Scalar::from(claim);
+///     let challenge = (challenge + claim).pow_vartime([5]);
+///     [acc + challenge * claim, challenge]
+/// };
+///
+/// let claims = [3, 1, 4];
+/// let mut z = [C1Scalar::from(0); 2];
+/// for claim in claims {
+///     let sc = ChallengeAccumCircuit::new(claim);
+///     mock::check_step(5, &sc, z).unwrap();
+///     z = sc.reference_step(&z);
+/// }
+/// assert_eq!(z, claims.into_iter().fold([C1Scalar::from(0); 2], absorb));
+///
+/// let z_out = prove_and_verify(&ChallengeAccumCircuit::new(3), [C1Scalar::from(0); 2], 2);
+/// assert_eq!(z_out, absorb(absorb([C1Scalar::from(0); 2], 3), 3));
+/// ```
+#[derive(Clone)]
+pub struct ChallengeAccumCircuit {
+    claim: u64,
+}
+
+impl ChallengeAccumCircuit {
+    pub fn new(claim: u64) -> Self {
+        Self { claim }
+    }
+}
+
+impl<F: PrimeField> StepCircuit<2, F> for ChallengeAccumCircuit {
+    type Config = ChallengeAccumConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.selector(),
+            acc_in: cs.advice_column(),
+            challenge_in: cs.advice_column(),
+            claim: cs.advice_column(),
+            sq: cs.advice_column(),
+            quad: cs.advice_column(),
+            acc_out: cs.advice_column(),
+            challenge_out: cs.advice_column(),
+        };
+
+        cs.enable_equality(config.acc_in);
+        cs.enable_equality(config.challenge_in);
+        cs.enable_equality(config.acc_out);
+        cs.enable_equality(config.challenge_out);
+
+        cs.create_gate("challenge-accum", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let acc_in = meta.query_advice(config.acc_in, Rotation::cur());
+            let challenge_in = meta.query_advice(config.challenge_in, Rotation::cur());
+            let claim = meta.query_advice(config.claim, Rotation::cur());
+            let sq = meta.query_advice(config.sq, Rotation::cur());
+            let quad = meta.query_advice(config.quad, Rotation::cur());
+            let acc_out = meta.query_advice(config.acc_out, Rotation::cur());
+            let challenge_out = meta.query_advice(config.challenge_out, Rotation::cur());
+
+            let absorbed = challenge_in + claim.clone();
+
+            // The fifth power is split over the hints to keep the gate degree at three
+            vec![
+                s.clone() * (sq.clone() - absorbed.clone() * absorbed.clone()),
+                s.clone() * (quad.clone() - sq.clone() * sq),
+                s.clone() * (challenge_out.clone() - quad * absorbed),
+                s * (acc_out - acc_in - challenge_out * claim),
+            ]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; 2],
+    ) -> Result<[AssignedCell<F, F>; 2], SynthesisError> {
+        let claim = Value::known(F::from(self.claim));
+
+        let z_out = layouter.assign_region(
+            || "challenge-accum",
+            |mut region| {
+                let [acc, challenge] = z_i;
+
+                config.s.enable(&mut region, 0)?;
+
+                let acc_in = acc.copy_advice(|| "acc_in", &mut region, config.acc_in, 0)?;
+                let challenge_in = challenge.copy_advice(
+                    || "challenge_in",
+                    &mut region,
+                    config.challenge_in,
+                    0,
+                )?;
+                region.assign_advice(|| "claim", config.claim, 0, || claim)?;
+
+                let absorbed = challenge_in.value().copied() + claim;
+                let sq = absorbed * absorbed;
+                let quad = sq * sq;
+                let challenge_out = quad * absorbed;
+
+                region.assign_advice(|| "sq", config.sq, 0, || sq)?;
+                region.assign_advice(|| "quad", config.quad, 0, || quad)?;
+
+                let acc_out = region.assign_advice(
+                    || "acc_out",
+                    config.acc_out,
+                    0,
+                    || acc_in.value().copied() + challenge_out * claim,
+                )?;
+                let challenge_out = region.assign_advice(
+                    || "challenge_out",
+                    config.challenge_out,
+                    0,
+                    || challenge_out,
+                )?;
+
+                Ok([acc_out, challenge_out])
+            },
+        )?;
+
+        Ok(z_out)
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<2, F> for ChallengeAccumCircuit {
+    fn reference_step(&self, [acc, challenge]: &[F; 2]) -> [F; 2] {
+        let claim = F::from(self.claim);
+        let challenge = (*challenge + claim).pow_vartime([5]);
+
+        [*acc + challenge * claim, challenge]
+    }
+}
@@ -0,0 +1,121 @@
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::ReferenceStep;
+
+/// Checksum of `words` computed out of the circuit, what folding a [`ChecksumCircuit`] per word
+/// from `z_0 = [0]` ends at
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::circuits::checksum;
+/// let words = [-C1Scalar::from(1), C1Scalar::from(7), C1Scalar::from(3)];
+/// // The sum wraps around the modulus: -1 + 7 + 3 = 9
+/// assert_eq!(checksum(&words), C1Scalar::from(9));
+/// ```
+pub fn checksum<F: PrimeField>(words: &[F]) -> F {
+    words.iter().sum()
+}
+
+/// Configuration for the [`ChecksumCircuit`], a single row holds the whole step
+#[derive(Debug, Clone)]
+pub struct ChecksumConfig {
+    s: Selector,
+    checksum: Column<Advice>,
+    /// Per-step data word, a private witness of the step
+    word: Column<Advice>,
+    checksum_next: Column<Advice>,
+}
+
+/// Circuit that adds a word of data to a running checksum every step:
+/// `checksum' = checksum + word`, in the field
+///
+/// A word is any field element, e.g. 31 bytes of a file, so the data is consumed much faster
+/// than with the `u64` addends of the [`super::SumCircuit`]. A single addition per step makes
+/// this far cheaper than hashing, at the price of a weaker binding: any reordering of the words,
+/// or changes that cancel out, keep the checksum. The final `z_out` is the checksum of all the
+/// folded data, which the verifier compares with the one it expects. Each step gets its own
+/// instance.
+#[derive(Clone)]
+pub struct ChecksumCircuit<F> {
+    word: F,
+}
+
+impl<F: PrimeField> ChecksumCircuit<F> {
+    pub fn new(word: F) -> Self {
+        Self { word }
+    }
+}
+
+impl<F: PrimeField> StepCircuit<1, F> for ChecksumCircuit<F> {
+    type Config = ChecksumConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.selector(),
+            checksum: cs.advice_column(),
+            word: cs.advice_column(),
+            checksum_next: cs.advice_column(),
+        };
+
+        cs.enable_equality(config.checksum);
+        cs.enable_equality(config.checksum_next);
+
+        cs.create_gate("checksum", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let checksum = meta.query_advice(config.checksum, Rotation::cur());
+            let word = meta.query_advice(config.word, Rotation::cur());
+            let checksum_next = meta.query_advice(config.checksum_next, Rotation::cur());
+
+            vec![s * (checksum + word - checksum_next)]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; 1],
+    ) -> Result<[AssignedCell<F, F>; 1], SynthesisError> {
+        let word = Value::known(self.word);
+
+        let checksum_next = layouter.assign_region(
+            || "checksum",
+            |mut region| {
+                let [z] = z_i;
+
+                config.s.enable(&mut region, 0)?;
+
+                let checksum = z.copy_advice(|| "checksum", &mut region, config.checksum, 0)?;
+                region.assign_advice(|| "word", config.word, 0, || word)?;
+                region.assign_advice(
+                    || "checksum_next",
+                    config.checksum_next,
+                    0,
+                    || checksum.value().copied() + word,
+                )
+            },
+        )?;
+
+        Ok([checksum_next])
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<1, F> for ChecksumCircuit<F> {
+    fn reference_step(&self, [checksum]: &[F; 1]) -> [F; 1] {
+        [*checksum + self.word]
+    }
+}
@@ -0,0 +1,143 @@
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::ReferenceStep;
+
+/// Configuration for the [`CoeffRecurrenceCircuit`], the step takes a single row
+#[derive(Debug, Clone)]
+pub struct CoeffRecurrenceConfig {
+    s: Selector,
+    /// `e(n - 2)`
+    a: Column<Advice>,
+    /// `e(n - 1)`
+    b: Column<Advice>,
+    /// Coefficient of the step, a private witness
+    c: Column<Advice>,
+    /// `e(n)`
+    e: Column<Advice>,
+}
+
+/// Circuit that advances the pair `[e(n - 2), e(n - 1)]` by `e(n) = c_n * e(n - 1) + e(n - 2)`,
+/// with a coefficient `c_n` of its own on every step
+///
+/// With every coefficient `1` this is the Fibonacci recurrence. With the partial quotients of a
+/// continued fraction `[c_0; c_1, c_2, ...]` it computes the numerators of its convergents from
+/// `z_0 = [0, 1]` and the denominators from `z_0 = [1, 0]`. Like the [`super::SumCircuit`], each
+/// step gets its own instance.
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     circuits::{CoeffRecurrenceCircuit, ReferenceStep},
+/// #     mock,
+/// #     testing::prove_and_verify,
+/// # };
+/// // The continued fraction of pi, [3; 7, 15, 1, 292]
+/// let coefficients = [3, 7, 15, 1, 292];
+///
+/// let mut z = [C1Scalar::from(0), C1Scalar::from(1)];
+/// for coefficient in coefficients {
+///     let sc = CoeffRecurrenceCircuit::new(coefficient);
+///     mock::check_step(5, &sc, z).unwrap();
+///     z = sc.reference_step(&z);
+/// }
+///
+/// // On the CPU
+/// let (mut a, mut b) = (0u64, 1u64);
+/// for coefficient in coefficients {
+///     (a, b) = (b, coefficient * b + a);
+/// }
+/// assert_eq!((a, b), (355, 103993));
+/// assert_eq!(z, [a, b].map(C1Scalar::from));
+///
+/// // With every coefficient 1 it is the Fibonacci recurrence
+/// let z_out = prove_and_verify(&CoeffRecurrenceCircuit::new(1), [0, 1].map(C1Scalar::from), 5);
+/// assert_eq!(z_out, [5, 8].map(C1Scalar::from));
+/// ```
+#[derive(Clone)]
+pub struct CoeffRecurrenceCircuit {
+    coefficient: u64,
+}
+
+impl CoeffRecurrenceCircuit {
+    pub fn new(coefficient: u64) -> Self {
+        Self { coefficient }
+    }
+}
+
+impl<F: PrimeField> StepCircuit<2, F> for CoeffRecurrenceCircuit {
+    type Config = CoeffRecurrenceConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.selector(),
+            a: cs.advice_column(),
+            b: cs.advice_column(),
+            c: cs.advice_column(),
+            e: cs.advice_column(),
+        };
+
+        cs.enable_equality(config.a);
+        cs.enable_equality(config.b);
+        cs.enable_equality(config.e);
+
+        cs.create_gate("coeff-recurrence", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let a = meta.query_advice(config.a, Rotation::cur());
+            let b = meta.query_advice(config.b, Rotation::cur());
+            let c = meta.query_advice(config.c, Rotation::cur());
+            let e = meta.query_advice(config.e, Rotation::cur());
+
+            vec![s * (e - (c * b + a))]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; 2],
+    ) -> Result<[AssignedCell<F, F>; 2], SynthesisError> {
+        layouter.assign_region(
+            || "coeff-recurrence",
+            |mut region| {
+                let [a, b] = z_i;
+
+                config.s.enable(&mut region, 0)?;
+
+                let a = a.copy_advice(|| "a", &mut region, config.a, 0)?;
+                let b = b.copy_advice(|| "b", &mut region, config.b, 0)?;
+                let c = Value::known(F::from(self.coefficient));
+                region.assign_advice(|| "c", config.c, 0, || c)?;
+
+                let e = region.assign_advice(
+                    || "e",
+                    config.e,
+                    0,
+                    || c * b.value().copied() + a.value().copied(),
+                )?;
+
+                Ok([b, e])
+            },
+        )
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<2, F> for CoeffRecurrenceCircuit {
+    fn reference_step(&self, [a, b]: &[F; 2]) -> [F; 2] {
+        [*b, F::from(self.coefficient) * b + a]
+    }
+}
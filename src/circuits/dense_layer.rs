@@ -0,0 +1,154 @@
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Expression, Fixed, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::ReferenceStep;
+
+/// Configuration for the [`DenseLayerCircuit`], the step takes one row per output
+#[derive(Debug, Clone)]
+pub struct DenseLayerConfig<const N: usize> {
+    s: Selector,
+    /// The whole input, copied into every row
+    x: [Column<Advice>; N],
+    /// Weight row of the output, fixed
+    w: [Column<Fixed>; N],
+    /// Bias of the output, fixed
+    bias: Column<Fixed>,
+    /// `w · x + bias`
+    y: Column<Advice>,
+}
+
+/// Circuit that applies a linear layer to its `N`-element state: `z' = W z + b`
+///
+/// The weight matrix `W` (one row per output) and the bias `b` are given once, at construction,
+/// and are the same on every step, so they are fixed columns: they are part of the circuit the
+/// public parameters are built for, not a witness. Each output row constrains `y_j = W_j · x + b_j`
+/// with the whole input `x` copied next to the weight row. Folding `n` steps proves `n`
+/// applications of the layer, e.g. an unrolled recurrent layer without activation. The arithmetic
+/// is in the field, so values wrap around its modulus instead of overflowing.
+#[derive(Clone)]
+pub struct DenseLayerCircuit<const N: usize, F> {
+    weights: [[F; N]; N],
+    bias: [F; N],
+}
+
+impl<const N: usize, F: PrimeField> DenseLayerCircuit<N, F> {
+    pub fn new(weights: [[F; N]; N], bias: [F; N]) -> Self {
+        Self { weights, bias }
+    }
+
+    /// The layer computed out of the circuit, a plain matrix-vector product plus bias
+    ///
+    /// ```
+    /// # use sirius::prelude::bn256::C1Scalar;
+    /// # use sirius_fibonacci_example::circuits::DenseLayerCircuit;
+    /// let f = |value: u64| C1Scalar::from(value);
+    /// let layer = DenseLayerCircuit::new([[f(1), f(2)], [f(3), f(4)]], [f(5), f(6)]);
+    ///
+    /// // [1 2] [7]   [5]   [1 * 7 + 2 * 8 + 5]   [28]
+    /// // [3 4] [8] + [6] = [3 * 7 + 4 * 8 + 6] = [59]
+    /// assert_eq!(layer.apply(&[f(7), f(8)]), [f(28), f(59)]);
+    /// ```
+    pub fn apply(&self, x: &[F; N]) -> [F; N] {
+        let mut y = self.bias;
+        for (y, row) in y.iter_mut().zip(&self.weights) {
+            *y += row.iter().zip(x).map(|(w, x)| *w * x).sum::<F>();
+        }
+        y
+    }
+}
+
+impl<const N: usize, F: PrimeField> StepCircuit<N, F> for DenseLayerCircuit<N, F> {
+    type Config = DenseLayerConfig<N>;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.selector(),
+            x: [(); N].map(|_| cs.advice_column()),
+            w: [(); N].map(|_| cs.fixed_column()),
+            bias: cs.fixed_column(),
+            y: cs.advice_column(),
+        };
+
+        config.x.iter().for_each(|x| cs.enable_equality(*x));
+        cs.enable_equality(config.y);
+
+        cs.create_gate("dense-layer", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let dot = config
+                .x
+                .iter()
+                .zip(&config.w)
+                .map(|(x, w)| {
+                    meta.query_advice(*x, Rotation::cur()) * meta.query_fixed(*w, Rotation::cur())
+                })
+                .fold(Expression::Constant(F::ZERO), |acc, term| acc + term);
+            let bias = meta.query_fixed(config.bias, Rotation::cur());
+            let y = meta.query_advice(config.y, Rotation::cur());
+
+            vec![s * (y - (dot + bias))]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; N],
+    ) -> Result<[AssignedCell<F, F>; N], SynthesisError> {
+        let z_out = layouter.assign_region(
+            || "dense-layer",
+            |mut region| {
+                (0..N)
+                    .map(|row| {
+                        config.s.enable(&mut region, row)?;
+
+                        for (k, (input, x)) in z_i.iter().zip(&config.x).enumerate() {
+                            input.copy_advice(|| "x", &mut region, *x, row)?;
+                            region.assign_fixed(
+                                || "w",
+                                config.w[k],
+                                row,
+                                || Value::known(self.weights[row][k]),
+                            )?;
+                        }
+                        region.assign_fixed(
+                            || "bias",
+                            config.bias,
+                            row,
+                            || Value::known(self.bias[row]),
+                        )?;
+
+                        let dot = z_i
+                            .iter()
+                            .zip(&self.weights[row])
+                            .fold(Value::known(self.bias[row]), |acc, (x, w)| {
+                                acc + x.value().map(|x| *x * w)
+                            });
+                        region.assign_advice(|| "y", config.y, row, || dot)
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            },
+        )?;
+
+        Ok(z_out.try_into().unwrap())
+    }
+}
+
+impl<const N: usize, F: PrimeField> ReferenceStep<N, F> for DenseLayerCircuit<N, F> {
+    fn reference_step(&self, z_i: &[F; N]) -> [F; N] {
+        self.apply(z_i)
+    }
+}
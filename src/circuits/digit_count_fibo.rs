@@ -0,0 +1,285 @@
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Expression, Selector, TableColumn},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::{
+    gadgets::{self, RangeCheckConfig},
+    ReferenceStep,
+};
+
+/// Most decimal digits a bracket can have: `10^19` is the largest power of ten in `u64`
+pub const MAX_DECIMAL_DIGITS: u32 = 19;
+
+/// Bits of the range-checked distances to the bracket bounds, which are all below `10^19`
+const DIFF_BITS: usize = 64;
+
+/// Configuration for the [`DigitCountFiboCircuit`], the step takes its first row, the rest are
+/// the two range checks
+#[derive(Debug, Clone)]
+pub struct DigitCountFiboConfig {
+    s: Selector,
+    a: Column<Advice>,
+    b: Column<Advice>,
+    a_next: Column<Advice>,
+    b_next: Column<Advice>,
+    digits_next: Column<Advice>,
+    /// Bracket `[lo, hi)` of the numbers with `digits_next` digits
+    lo: Column<Advice>,
+    hi: Column<Advice>,
+    /// `b_next - lo`, range-checked to prove `lo <= b_next`
+    lo_diff: Column<Advice>,
+    /// `hi - 1 - b_next`, range-checked to prove `b_next < hi`
+    hi_diff: Column<Advice>,
+    /// Fixed table of the `(digits, lo, hi)` brackets
+    table_digits: TableColumn,
+    table_lo: TableColumn,
+    table_hi: TableColumn,
+    range: RangeCheckConfig,
+}
+
+/// The Fibonacci recurrence, one element per step, proving the number of decimal digits of the
+/// last element alongside it: `[a, b, digits]`
+///
+/// The pair advances to `[b, a + b]` as in the [`crate::FibonacciCircuit`] with a single element
+/// per step, and `digits` is set to the digit count `d` of `a + b`, carried through the folds as
+/// a derived metric of the state. The count is proven by bracketing: `(d, lo, hi)` is looked up in
+/// a fixed table of the brackets `[10^(d - 1), 10^d)` given at construction, `[0, 10)` for a
+/// single digit, and the distances `a + b - lo` and `hi - 1 - (a + b)` are range-checked. A value
+/// with more digits than the widest bracket has none, so its step is unsatisfiable: the sequence
+/// must stay below `10^max_digits`. `z_0` is `[0, 1, 1]` for the sequence from its start.
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     circuits::{DigitCountFiboCircuit, ReferenceStep},
+/// #     error::ExampleError,
+/// #     field, mock,
+/// #     testing::prove_and_verify,
+/// # };
+/// let sc = DigitCountFiboCircuit::new(6);
+///
+/// let mut z = [0, 1, 1].map(C1Scalar::from);
+/// for _ in 0..25 {
+///     mock::check_step(8, &sc, z).unwrap();
+///     z = sc.reference_step(&z);
+/// }
+/// // The 26th element, 121393, has 6 digits
+/// let last = field::to_u128(&z[1]).unwrap();
+/// assert_eq!(last, 121393);
+/// assert_eq!(z[2], C1Scalar::from(last.to_string().len() as u64));
+///
+/// // 196418 still has 6 digits, but 317811 has 7, more than the brackets go to
+/// z = sc.reference_step(&z);
+/// let err = mock::check_step(8, &sc, z).unwrap_err();
+/// assert!(matches!(err, ExampleError::MockProver(_)), "{err}");
+///
+/// // Folded, ending at 21, of 2 digits
+/// let z_out = prove_and_verify(&sc, [0, 1, 1].map(C1Scalar::from), 7);
+/// assert_eq!(z_out, [13, 21, 2].map(C1Scalar::from));
+/// ```
+#[derive(Clone)]
+pub struct DigitCountFiboCircuit {
+    /// `(digits, lo, hi)` of every bracket, the digit counts from `1`
+    brackets: Vec<(u64, u64, u64)>,
+}
+
+impl DigitCountFiboCircuit {
+    /// A circuit with the brackets of `1` up to `max_digits` digits, at most
+    /// [`MAX_DECIMAL_DIGITS`]
+    pub fn new(max_digits: u32) -> Self {
+        assert!(
+            (1..=MAX_DECIMAL_DIGITS).contains(&max_digits),
+            "the brackets have 1 to {MAX_DECIMAL_DIGITS} digits, got {max_digits}"
+        );
+
+        Self {
+            brackets: (1..=max_digits)
+                .map(|digits| {
+                    let lo = if digits == 1 {
+                        0
+                    } else {
+                        10u64.pow(digits - 1)
+                    };
+                    (u64::from(digits), lo, 10u64.pow(digits))
+                })
+                .collect(),
+        }
+    }
+
+    /// Bracket of `value`, `None` if it has more digits than the widest one
+    fn bracket<F: PrimeField>(&self, value: &F) -> Option<(u64, u64, u64)> {
+        let value = gadgets::to_u128(value)?;
+        self.brackets
+            .iter()
+            .copied()
+            .find(|(_, lo, hi)| (*lo as u128..*hi as u128).contains(&value))
+    }
+}
+
+impl<F: PrimeField> StepCircuit<3, F> for DigitCountFiboCircuit {
+    type Config = DigitCountFiboConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.complex_selector(),
+            a: cs.advice_column(),
+            b: cs.advice_column(),
+            a_next: cs.advice_column(),
+            b_next: cs.advice_column(),
+            digits_next: cs.advice_column(),
+            lo: cs.advice_column(),
+            hi: cs.advice_column(),
+            lo_diff: cs.advice_column(),
+            hi_diff: cs.advice_column(),
+            // Filled with the brackets by `synthesize_step`, the same on every step, so the
+            // table is part of the circuit shape the public parameters are built from
+            table_digits: cs.lookup_table_column(),
+            table_lo: cs.lookup_table_column(),
+            table_hi: cs.lookup_table_column(),
+            range: RangeCheckConfig::configure(cs),
+        };
+
+        cs.enable_equality(config.a);
+        cs.enable_equality(config.b);
+        cs.enable_equality(config.a_next);
+        cs.enable_equality(config.b_next);
+        cs.enable_equality(config.digits_next);
+        cs.enable_equality(config.lo_diff);
+        cs.enable_equality(config.hi_diff);
+
+        cs.create_gate("digit-count-fibo", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let a = meta.query_advice(config.a, Rotation::cur());
+            let b = meta.query_advice(config.b, Rotation::cur());
+            let a_next = meta.query_advice(config.a_next, Rotation::cur());
+            let b_next = meta.query_advice(config.b_next, Rotation::cur());
+            let lo = meta.query_advice(config.lo, Rotation::cur());
+            let hi = meta.query_advice(config.hi, Rotation::cur());
+            let lo_diff = meta.query_advice(config.lo_diff, Rotation::cur());
+            let hi_diff = meta.query_advice(config.hi_diff, Rotation::cur());
+
+            let one = Expression::Constant(F::ONE);
+
+            vec![
+                s.clone() * (a_next - b.clone()),
+                s.clone() * (b_next.clone() - (a + b)),
+                s.clone() * (lo_diff - (b_next.clone() - lo)),
+                s * (hi_diff - (hi - one - b_next)),
+            ]
+        });
+
+        // The inputs are `(0, 0, 0)` where the selector is off, so the table has that row too; a
+        // step can't use it, as `hi - 1 - b_next` is then out of range
+        cs.lookup("digit-bracket", |meta| {
+            let s = meta.query_selector(config.s);
+            let digits = meta.query_advice(config.digits_next, Rotation::cur());
+            let lo = meta.query_advice(config.lo, Rotation::cur());
+            let hi = meta.query_advice(config.hi, Rotation::cur());
+
+            vec![
+                (s.clone() * digits, config.table_digits),
+                (s.clone() * lo, config.table_lo),
+                (s * hi, config.table_hi),
+            ]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; 3],
+    ) -> Result<[AssignedCell<F, F>; 3], SynthesisError> {
+        layouter.assign_table(
+            || "digit brackets",
+            |mut table| {
+                let rows = [(0, 0, 0)].into_iter().chain(self.brackets.iter().copied());
+                for (offset, (digits, lo, hi)) in rows.enumerate() {
+                    for (column, value) in [
+                        (config.table_digits, digits),
+                        (config.table_lo, lo),
+                        (config.table_hi, hi),
+                    ] {
+                        table.assign_cell(
+                            || "bracket",
+                            column,
+                            offset,
+                            || Value::known(F::from(value)),
+                        )?;
+                    }
+                }
+
+                Ok(())
+            },
+        )?;
+
+        let z_out = layouter.assign_region(
+            || "digit-count-fibo",
+            |mut region| {
+                // The digit count of the previous element is not an input of the step
+                let [a, b, _digits] = z_i;
+
+                config.s.enable(&mut region, 0)?;
+
+                let a = a.copy_advice(|| "a", &mut region, config.a, 0)?;
+                let b = b.copy_advice(|| "b", &mut region, config.b, 0)?;
+
+                let a_next =
+                    region.assign_advice(|| "a_next", config.a_next, 0, || b.value().copied())?;
+                let sum = a.value().copied() + b.value().copied();
+                let b_next = region.assign_advice(|| "b_next", config.b_next, 0, || sum)?;
+
+                // Without a bracket, the zero row of the table makes the step unsatisfiable
+                let bracket = sum.map(|sum| self.bracket(&sum).unwrap_or((0, 0, 0)));
+                let [digits, lo, hi] = [
+                    bracket.map(|(digits, _, _)| F::from(digits)),
+                    bracket.map(|(_, lo, _)| F::from(lo)),
+                    bracket.map(|(_, _, hi)| F::from(hi)),
+                ];
+                let digits_next =
+                    region.assign_advice(|| "digits_next", config.digits_next, 0, || digits)?;
+                region.assign_advice(|| "lo", config.lo, 0, || lo)?;
+                region.assign_advice(|| "hi", config.hi, 0, || hi)?;
+
+                let lo_diff = region.assign_advice(|| "lo_diff", config.lo_diff, 0, || sum - lo)?;
+                let hi_diff = region.assign_advice(
+                    || "hi_diff",
+                    config.hi_diff,
+                    0,
+                    || hi - Value::known(F::ONE) - sum,
+                )?;
+                config.range.assign(&mut region, 1, &lo_diff, DIFF_BITS)?;
+                config.range.assign(
+                    &mut region,
+                    1 + RangeCheckConfig::rows(DIFF_BITS),
+                    &hi_diff,
+                    DIFF_BITS,
+                )?;
+
+                Ok([a_next, b_next, digits_next])
+            },
+        )?;
+
+        Ok(z_out)
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<3, F> for DigitCountFiboCircuit {
+    fn reference_step(&self, [a, b, _digits]: &[F; 3]) -> [F; 3] {
+        let sum = *a + *b;
+        let digits = self.bracket(&sum).map_or(0, |(digits, _, _)| digits);
+        [*b, sum, F::from(digits)]
+    }
+}
@@ -0,0 +1,224 @@
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Expression, Fixed, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::{
+    gadgets::{self, RangeCheckConfig},
+    ReferenceStep,
+};
+
+/// Configuration for the [`DigitRootCircuit`]
+#[derive(Debug, Clone)]
+pub struct DigitRootConfig {
+    /// Enforces zero accumulators on the first row
+    s_init: Selector,
+    /// Enforces the per-digit relations
+    s_digit: Selector,
+    /// Digits of the input, most significant first
+    digit: Column<Advice>,
+    /// `base - 1 - digit`, range-checked to prove `digit < base`
+    rem: Column<Advice>,
+    /// Running value of the digits read so far, equal to the input on the last row
+    acc: Column<Advice>,
+    /// Running sum of the digits read so far, the output on the last row
+    sum: Column<Advice>,
+    base: Column<Fixed>,
+    range: RangeCheckConfig,
+}
+
+/// Circuit that replaces the state with the sum of its base-`b` digits each step, so the folded
+/// state converges to the digital root of `z_0` and stays there.
+///
+/// The input is decomposed into digit witnesses, with `input = sum(digit_i * b^i)` enforced by a
+/// running accumulator and `digit < b` by range-checking both `digit` and `b - 1 - digit`. The
+/// number of digits is fixed at construction, enough for any `u64` input.
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     circuits::{DigitRootCircuit, ReferenceStep},
+/// #     mock,
+/// #     testing::prove_and_verify,
+/// # };
+/// let sc = DigitRootCircuit::new(10);
+/// let mut z = [C1Scalar::from(987654321)];
+/// for expected in [45, 9, 9] {
+///     mock::check_step(9, &sc, z).unwrap();
+///     z = sc.reference_step(&z);
+///     assert_eq!(z, [C1Scalar::from(expected)]);
+/// }
+///
+/// // 9875 -> 29 -> 11 -> 2
+/// let z_out = prove_and_verify(&sc, [C1Scalar::from(9875)], 3);
+/// assert_eq!(z_out, [C1Scalar::from(2)]);
+/// ```
+#[derive(Clone)]
+pub struct DigitRootCircuit {
+    base: u64,
+    digits: usize,
+}
+
+impl DigitRootCircuit {
+    /// # Panics
+    /// If `base < 2`
+    pub fn new(base: u64) -> Self {
+        assert!(base >= 2, "base must be at least 2");
+
+        Self {
+            base,
+            digits: u64::MAX.ilog(base) as usize + 1,
+        }
+    }
+
+    /// Bits needed to range-check a single digit
+    fn digit_bits(&self) -> usize {
+        (u64::BITS - (self.base - 1).leading_zeros()) as usize
+    }
+
+    /// Digits of `value`, most significant first
+    ///
+    /// Out-of-range values give digits that don't add up to `value`, so the proof will fail
+    fn to_digits<F: PrimeField>(&self, value: &F) -> Vec<u64> {
+        let mut rest = gadgets::to_u128(value).unwrap_or_default();
+
+        let mut digits = (0..self.digits)
+            .map(|_| {
+                let digit = rest % self.base as u128;
+                rest /= self.base as u128;
+                digit as u64
+            })
+            .collect::<Vec<_>>();
+        digits.reverse();
+
+        digits
+    }
+}
+
+impl<F: PrimeField> StepCircuit<1, F> for DigitRootCircuit {
+    type Config = DigitRootConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s_init: cs.selector(),
+            s_digit: cs.selector(),
+            digit: cs.advice_column(),
+            rem: cs.advice_column(),
+            acc: cs.advice_column(),
+            sum: cs.advice_column(),
+            base: cs.fixed_column(),
+            range: RangeCheckConfig::configure(cs),
+        };
+
+        cs.enable_equality(config.digit);
+        cs.enable_equality(config.rem);
+        cs.enable_equality(config.acc);
+        cs.enable_equality(config.sum);
+
+        cs.create_gate("digit-root-init", |meta| {
+            let s = meta.query_selector(config.s_init);
+
+            let acc = meta.query_advice(config.acc, Rotation::cur());
+            let sum = meta.query_advice(config.sum, Rotation::cur());
+
+            vec![s.clone() * acc, s * sum]
+        });
+
+        cs.create_gate("digit-root-digit", |meta| {
+            let s = meta.query_selector(config.s_digit);
+
+            let digit = meta.query_advice(config.digit, Rotation::cur());
+            let rem = meta.query_advice(config.rem, Rotation::cur());
+            let acc = meta.query_advice(config.acc, Rotation::cur());
+            let acc_prev = meta.query_advice(config.acc, Rotation::prev());
+            let sum = meta.query_advice(config.sum, Rotation::cur());
+            let sum_prev = meta.query_advice(config.sum, Rotation::prev());
+            let base = meta.query_fixed(config.base, Rotation::cur());
+
+            vec![
+                s.clone() * (acc - (acc_prev * base.clone() + digit.clone())),
+                s.clone() * (sum - (sum_prev + digit.clone())),
+                s * (rem - (base - Expression::Constant(F::ONE) - digit)),
+            ]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; 1],
+    ) -> Result<[AssignedCell<F, F>; 1], SynthesisError> {
+        let base = F::from(self.base);
+        let bits = self.digit_bits();
+
+        let z_out = layouter.assign_region(
+            || "digit-root",
+            |mut region| {
+                let [z] = z_i;
+                let digits = z.value().map(|value| self.to_digits(value));
+
+                config.s_init.enable(&mut region, 0)?;
+                let mut acc =
+                    region.assign_advice(|| "acc", config.acc, 0, || Value::known(F::ZERO))?;
+                let mut sum =
+                    region.assign_advice(|| "sum", config.sum, 0, || Value::known(F::ZERO))?;
+
+                let mut to_check = Vec::with_capacity(2 * self.digits);
+                for index in 0..self.digits {
+                    let row = index + 1;
+
+                    config.s_digit.enable(&mut region, row)?;
+                    region.assign_fixed(|| "base", config.base, row, || Value::known(base))?;
+
+                    let digit = digits.as_ref().map(|digits| F::from(digits[index]));
+
+                    let next_acc = acc
+                        .value()
+                        .zip(digit)
+                        .map(|(acc, digit)| *acc * base + digit);
+                    acc = region.assign_advice(|| "acc", config.acc, row, || next_acc)?;
+
+                    let next_sum = sum.value().zip(digit).map(|(sum, digit)| *sum + digit);
+                    sum = region.assign_advice(|| "sum", config.sum, row, || next_sum)?;
+
+                    to_check.push(region.assign_advice(|| "digit", config.digit, row, || digit)?);
+                    to_check.push(region.assign_advice(
+                        || "rem",
+                        config.rem,
+                        row,
+                        || digit.map(|digit| base - F::ONE - digit),
+                    )?);
+                }
+
+                // The digits must add up to the input
+                region.constrain_equal(acc.cell(), z.cell())?;
+
+                for (index, cell) in to_check.iter().enumerate() {
+                    let offset = self.digits + 1 + index * RangeCheckConfig::rows(bits);
+                    config.range.assign(&mut region, offset, cell, bits)?;
+                }
+
+                Ok(sum)
+            },
+        )?;
+
+        Ok([z_out])
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<1, F> for DigitRootCircuit {
+    fn reference_step(&self, [z]: &[F; 1]) -> [F; 1] {
+        [F::from(self.to_digits(z).iter().sum::<u64>())]
+    }
+}
@@ -0,0 +1,221 @@
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Expression, Fixed, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{bn256::C1Scalar, PrimeField, StepCircuit},
+};
+
+use super::{
+    gadgets::{self, RangeCheckConfig},
+    ReferenceStep,
+};
+
+/// Configuration for the [`FiboModCircuit`], the step takes its first row, the rest are the range
+/// checks
+#[derive(Debug, Clone)]
+pub struct FiboModConfig {
+    s: Selector,
+    a: Column<Advice>,
+    b: Column<Advice>,
+    /// Hint: quotient of `a + b` by `p`, zero or one as both are reduced
+    q: Column<Advice>,
+    /// Hint: `(a + b) mod p`, the next element
+    r: Column<Advice>,
+    /// `p - 1 - r`, range-checked together with `r` to prove `r < p`
+    rem: Column<Advice>,
+    p: Column<Fixed>,
+    range: RangeCheckConfig,
+}
+
+/// Circuit that advances a Fibonacci pair modulo a small prime `p` each step:
+/// `[a, b] -> [b, (a + b) mod p]`
+///
+/// Unlike the [`crate::FibonacciCircuit`], which wraps around the (huge) native field modulus,
+/// the reduction here is explicit: `a + b = p * q + r` with a boolean `q` and `r < p`, proven by
+/// range-checking both `r` and `p - 1 - r`. The folded state is periodic with the Pisano period
+/// of `p`, e.g. `16` steps for `p = 7`. Both elements of `z_0` must already be reduced.
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     circuits::FiboModCircuit, mock, testing::prove_and_verify,
+/// # };
+/// let z_0 = [0, 1].map(C1Scalar::from);
+///
+/// // Back at `z_0` after the Pisano period of 7, and not before
+/// let sc = FiboModCircuit::new(7);
+/// assert_eq!(sc.pisano_period(), 16);
+/// let mut z = z_0;
+/// for step in 1..=16 {
+///     z = mock::check_step(7, &sc, z).unwrap();
+///     assert_eq!(z == z_0, step == 16, "step {step}");
+/// }
+///
+/// // An unreduced input doesn't satisfy the step
+/// mock::check_step(7, &sc, [7, 1].map(C1Scalar::from)).unwrap_err();
+///
+/// // Folded, the period of 2 is 3 steps: [0, 1] -> [1, 1] -> [1, 0] -> [0, 1] -> [1, 1]
+/// let sc = FiboModCircuit::new(2);
+/// assert_eq!(sc.pisano_period(), 3);
+/// assert_eq!(prove_and_verify(&sc, z_0, 3), z_0);
+/// assert_eq!(prove_and_verify(&sc, z_0, 4), prove_and_verify(&sc, z_0, 1));
+/// assert_eq!(prove_and_verify(&sc, z_0, 1), [1, 1].map(C1Scalar::from));
+/// ```
+#[derive(Clone)]
+pub struct FiboModCircuit {
+    p: u64,
+}
+
+impl FiboModCircuit {
+    /// # Panics
+    /// If `p < 2`
+    pub fn new(p: u64) -> Self {
+        assert!(p >= 2, "p must be at least 2");
+
+        Self { p }
+    }
+
+    /// Pisano period of `p`, the number of steps after which the state `[0, 1]` comes back
+    ///
+    /// Every state of the sequence repeats with this period. Computed by running the reference
+    /// step from `[0, 1]`, so it takes time linear in `p`, the period being at most `6 p`:
+    ///
+    /// ```
+    /// # use sirius_fibonacci_example::circuits::FiboModCircuit;
+    /// // OEIS A001175
+    /// let known = [(2, 3), (3, 8), (5, 20), (7, 16), (10, 60), (11, 10), (13, 28), (89, 44)];
+    /// for (p, period) in known {
+    ///     assert_eq!(FiboModCircuit::new(p).pisano_period(), period, "p = {p}");
+    /// }
+    /// ```
+    pub fn pisano_period(&self) -> u64 {
+        let start = [C1Scalar::from(0), C1Scalar::from(1)];
+
+        let mut z = self.reference_step(&start);
+        let mut period = 1;
+        while z != start {
+            z = self.reference_step(&z);
+            period += 1;
+        }
+        period
+    }
+
+    /// Bits needed to range-check a value below `p`
+    fn bits(&self) -> usize {
+        (u64::BITS - (self.p - 1).leading_zeros()) as usize
+    }
+
+    /// Quotient and remainder of `a + b` by `p`
+    ///
+    /// Unreduced inputs give values that don't satisfy the gate, so the proof will fail
+    fn reduce<F: PrimeField>(&self, a: &F, b: &F) -> (u64, u64) {
+        let [a, b] = [a, b].map(|value| gadgets::to_u128(value).unwrap_or_default());
+        let sum = a.saturating_add(b);
+        let p = self.p as u128;
+
+        ((sum / p) as u64, (sum % p) as u64)
+    }
+}
+
+impl<F: PrimeField> StepCircuit<2, F> for FiboModCircuit {
+    type Config = FiboModConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.selector(),
+            a: cs.advice_column(),
+            b: cs.advice_column(),
+            q: cs.advice_column(),
+            r: cs.advice_column(),
+            rem: cs.advice_column(),
+            p: cs.fixed_column(),
+            range: RangeCheckConfig::configure(cs),
+        };
+
+        cs.enable_equality(config.a);
+        cs.enable_equality(config.b);
+        cs.enable_equality(config.r);
+        cs.enable_equality(config.rem);
+
+        cs.create_gate("fibo-mod", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let a = meta.query_advice(config.a, Rotation::cur());
+            let b = meta.query_advice(config.b, Rotation::cur());
+            let q = meta.query_advice(config.q, Rotation::cur());
+            let r = meta.query_advice(config.r, Rotation::cur());
+            let rem = meta.query_advice(config.rem, Rotation::cur());
+            let p = meta.query_fixed(config.p, Rotation::cur());
+
+            let one = Expression::Constant(F::ONE);
+
+            vec![
+                s.clone() * (q.clone() * (one.clone() - q.clone())),
+                s.clone() * (a + b - (p.clone() * q + r.clone())),
+                s * (rem - (p - one - r)),
+            ]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; 2],
+    ) -> Result<[AssignedCell<F, F>; 2], SynthesisError> {
+        let p = F::from(self.p);
+        let bits = self.bits();
+
+        let z_out = layouter.assign_region(
+            || "fibo-mod",
+            |mut region| {
+                let [a, b] = z_i;
+
+                config.s.enable(&mut region, 0)?;
+                region.assign_fixed(|| "p", config.p, 0, || Value::known(p))?;
+
+                let a = a.copy_advice(|| "a", &mut region, config.a, 0)?;
+                let b = b.copy_advice(|| "b", &mut region, config.b, 0)?;
+
+                let reduced = a.value().zip(b.value()).map(|(a, b)| self.reduce(a, b));
+                region.assign_advice(|| "q", config.q, 0, || reduced.map(|(q, _)| F::from(q)))?;
+                let r = region.assign_advice(
+                    || "r",
+                    config.r,
+                    0,
+                    || reduced.map(|(_, r)| F::from(r)),
+                )?;
+                let rem = region.assign_advice(
+                    || "rem",
+                    config.rem,
+                    0,
+                    || r.value().map(|r| p - F::ONE - *r),
+                )?;
+
+                config.range.assign(&mut region, 1, &r, bits)?;
+                config
+                    .range
+                    .assign(&mut region, 1 + RangeCheckConfig::rows(bits), &rem, bits)?;
+
+                Ok([b, r])
+            },
+        )?;
+
+        Ok(z_out)
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<2, F> for FiboModCircuit {
+    fn reference_step(&self, [a, b]: &[F; 2]) -> [F; 2] {
+        let (_, r) = self.reduce(a, b);
+        [*b, F::from(r)]
+    }
+}
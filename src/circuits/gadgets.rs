@@ -0,0 +1,106 @@
+//! Building blocks shared by the step circuits
+
+use sirius::{
+    halo2_proofs::{
+        circuit::{Region, Value},
+        plonk::{Advice, Column, Error, Expression, Selector},
+        poly::Rotation,
+    },
+    ivc::step_circuit::{AssignedCell, ConstraintSystem},
+    prelude::PrimeField,
+};
+
+pub use crate::field::to_u128;
+
+/// Bit `index` of `value`, little-endian representation as in [`to_u128`]
+pub fn nth_bit<F: PrimeField>(value: &F, index: usize) -> bool {
+    let repr = value.to_repr();
+    repr.as_ref()
+        .get(index / 8)
+        .is_some_and(|byte| (byte >> (index % 8)) & 1 == 1)
+}
+
+/// Proves `value < 2^bits` by decomposing it into bits with a running sum
+///
+/// A check takes [`RangeCheckConfig::rows`] rows of a region: the first one holds a zero
+/// accumulator, then each row adds one bit, most significant first, as `acc = 2 * acc_prev + bit`.
+/// The last accumulator is constrained equal to the checked cell. The cost is linear in `bits`,
+/// so keep the checked ranges tight.
+#[derive(Debug, Clone)]
+pub struct RangeCheckConfig {
+    /// Enforces a zero accumulator on the first row of a check
+    s_first: Selector,
+    /// Enforces a boolean bit and the running sum
+    s_bit: Selector,
+    bit: Column<Advice>,
+    acc: Column<Advice>,
+}
+
+impl RangeCheckConfig {
+    pub fn configure<F: PrimeField>(cs: &mut ConstraintSystem<F>) -> Self {
+        let config = Self {
+            s_first: cs.selector(),
+            s_bit: cs.selector(),
+            bit: cs.advice_column(),
+            acc: cs.advice_column(),
+        };
+
+        cs.enable_equality(config.acc);
+
+        cs.create_gate("range-first", |meta| {
+            let s = meta.query_selector(config.s_first);
+            let acc = meta.query_advice(config.acc, Rotation::cur());
+
+            vec![s * acc]
+        });
+
+        cs.create_gate("range-bit", |meta| {
+            let s = meta.query_selector(config.s_bit);
+
+            let bit = meta.query_advice(config.bit, Rotation::cur());
+            let acc = meta.query_advice(config.acc, Rotation::cur());
+            let acc_prev = meta.query_advice(config.acc, Rotation::prev());
+
+            vec![
+                s.clone() * bit.clone() * (Expression::Constant(F::ONE) - bit.clone()),
+                s * (acc - acc_prev * F::from(2u64) - bit),
+            ]
+        });
+
+        config
+    }
+
+    /// Number of rows taken by a check of `bits` bits
+    pub fn rows(bits: usize) -> usize {
+        bits + 1
+    }
+
+    /// Check `value < 2^bits` at rows `offset..offset + Self::rows(bits)` of `region`
+    pub fn assign<F: PrimeField>(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: usize,
+        value: &AssignedCell<F, F>,
+        bits: usize,
+    ) -> Result<(), Error> {
+        self.s_first.enable(region, offset)?;
+        let mut acc = region.assign_advice(|| "acc", self.acc, offset, || Value::known(F::ZERO))?;
+
+        for (row, index) in (offset + 1..).zip((0..bits).rev()) {
+            self.s_bit.enable(region, row)?;
+
+            let bit = value
+                .value()
+                .map(|value| F::from(nth_bit(value, index) as u64));
+            region.assign_advice(|| "bit", self.bit, row, || bit)?;
+
+            let next = acc
+                .value()
+                .zip(bit)
+                .map(|(acc, bit)| *acc * F::from(2u64) + bit);
+            acc = region.assign_advice(|| "acc", self.acc, row, || next)?;
+        }
+
+        region.constrain_equal(acc.cell(), value.cell())
+    }
+}
@@ -0,0 +1,148 @@
+use sirius::{
+    halo2_proofs::{
+        plonk::{Advice, Column, Expression, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::ReferenceStep;
+
+/// Configuration for the [`IndexedFiboCircuit`], the step takes a single row
+#[derive(Debug, Clone)]
+pub struct IndexedFiboConfig {
+    s: Selector,
+    a: Column<Advice>,
+    b: Column<Advice>,
+    /// Index of `a` in the sequence
+    index: Column<Advice>,
+    /// Parity of `index`, `0` for even
+    parity: Column<Advice>,
+    /// `a + b`
+    next: Column<Advice>,
+    index_next: Column<Advice>,
+    parity_next: Column<Advice>,
+}
+
+/// The Fibonacci recurrence with the index of its state and the index parity carried along:
+/// `[a, b, index, parity] -> [b, a + b, index + 1, 1 - parity]`
+///
+/// Shows how to augment the state of a circuit with auxiliary values: the pair advances exactly
+/// as in the Fibonacci circuit (one element per step), and the extra state elements have their
+/// own constraints next to the recurrence. The next parity is constrained to be a bit that flips
+/// every step, so from `z_0 = [0, 1, 0, 0]` it is the parity of the index `a` has in the
+/// sequence, e.g. to prove a value is an even-indexed Fibonacci number.
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::circuits::{IndexedFiboCircuit, ReferenceStep};
+/// let mut z = [0, 1, 0, 0].map(C1Scalar::from);
+/// for _ in 0..7 {
+///     z = IndexedFiboCircuit.reference_step(&z);
+/// }
+/// // F(7) = 13, index 7 is odd
+/// assert_eq!(z, [13, 21, 7, 1].map(C1Scalar::from));
+/// ```
+#[derive(Clone, Default)]
+pub struct IndexedFiboCircuit;
+
+impl<F: PrimeField> StepCircuit<4, F> for IndexedFiboCircuit {
+    type Config = IndexedFiboConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.selector(),
+            a: cs.advice_column(),
+            b: cs.advice_column(),
+            index: cs.advice_column(),
+            parity: cs.advice_column(),
+            next: cs.advice_column(),
+            index_next: cs.advice_column(),
+            parity_next: cs.advice_column(),
+        };
+
+        cs.enable_equality(config.a);
+        cs.enable_equality(config.b);
+        cs.enable_equality(config.index);
+        cs.enable_equality(config.parity);
+        cs.enable_equality(config.next);
+        cs.enable_equality(config.index_next);
+        cs.enable_equality(config.parity_next);
+
+        cs.create_gate("indexed-fibo", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let a = meta.query_advice(config.a, Rotation::cur());
+            let b = meta.query_advice(config.b, Rotation::cur());
+            let index = meta.query_advice(config.index, Rotation::cur());
+            let parity = meta.query_advice(config.parity, Rotation::cur());
+            let next = meta.query_advice(config.next, Rotation::cur());
+            let index_next = meta.query_advice(config.index_next, Rotation::cur());
+            let parity_next = meta.query_advice(config.parity_next, Rotation::cur());
+
+            let one = Expression::Constant(F::ONE);
+            vec![
+                s.clone() * (next - (a + b)),
+                s.clone() * (index_next - (index + one.clone())),
+                s.clone() * parity_next.clone() * (one.clone() - parity_next.clone()),
+                s * (parity_next + parity - one),
+            ]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; 4],
+    ) -> Result<[AssignedCell<F, F>; 4], SynthesisError> {
+        let z_out = layouter.assign_region(
+            || "indexed-fibo",
+            |mut region| {
+                let [a, b, index, parity] = z_i;
+
+                config.s.enable(&mut region, 0)?;
+
+                let a = a.copy_advice(|| "a", &mut region, config.a, 0)?;
+                let b = b.copy_advice(|| "b", &mut region, config.b, 0)?;
+                let index = index.copy_advice(|| "index", &mut region, config.index, 0)?;
+                let parity = parity.copy_advice(|| "parity", &mut region, config.parity, 0)?;
+
+                let next = region.assign_advice(
+                    || "next",
+                    config.next,
+                    0,
+                    || a.value().copied() + b.value().copied(),
+                )?;
+                let index_next = region.assign_advice(
+                    || "index_next",
+                    config.index_next,
+                    0,
+                    || index.value().map(|index| *index + F::ONE),
+                )?;
+                let parity_next = region.assign_advice(
+                    || "parity_next",
+                    config.parity_next,
+                    0,
+                    || parity.value().map(|parity| F::ONE - parity),
+                )?;
+
+                Ok([b, next, index_next, parity_next])
+            },
+        )?;
+
+        Ok(z_out)
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<4, F> for IndexedFiboCircuit {
+    fn reference_step(&self, [a, b, index, parity]: &[F; 4]) -> [F; 4] {
+        [*b, *a + *b, *index + F::ONE, F::ONE - *parity]
+    }
+}
@@ -0,0 +1,185 @@
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Fixed, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::ReferenceStep;
+
+/// Round constants of the hash, one round per constant (hex digits of pi)
+pub const ROUND_CONSTANTS: [u64; 8] = [
+    0x243f_6a88_85a3_08d3,
+    0x1319_8a2e_0370_7344,
+    0xa409_3822_299f_31d0,
+    0x082e_fa98_ec4e_6c89,
+    0x4528_21e6_38d0_1377,
+    0xbe54_66cf_34e9_0c6c,
+    0xc0ac_29b7_c97c_50dd,
+    0x3f84_d5b5_b547_0917,
+];
+
+/// Configuration for the [`InputCommitmentCircuit`], one row per round plus the output row
+#[derive(Debug, Clone)]
+pub struct InputCommitmentConfig {
+    /// Enforces one round, from its row to the next
+    s: Selector,
+    /// Hash state, `acc` on the first row and the new `acc` on the last
+    h: Column<Advice>,
+    /// Per-step input, a private witness of the step, repeated on every row
+    input: Column<Advice>,
+    /// Hint: `(h + input + c)^2`
+    sq: Column<Advice>,
+    /// Hint: `(h + input + c)^4`
+    quad: Column<Advice>,
+    /// Round constant `c`
+    c: Column<Fixed>,
+}
+
+/// Circuit that folds the per-step input into a running commitment `acc' = H(acc, input)`, so the
+/// final state binds the whole input stream, in order
+///
+/// `H` is a MiMC-style hash: [`ROUND_CONSTANTS`]`.len()` rounds of `h = (h + input + c)^5`
+/// starting from `h = acc`. `sirius` v0.1.1 keeps its Poseidon gadget internal to the IVC
+/// circuit, so this stands in for it, it is a demonstration and not an audited hash. Like the
+/// [`super::SumCircuit`], each step gets its own instance.
+///
+/// ```
+/// # use sirius::{ff::Field, prelude::bn256::C1Scalar};
+/// # use sirius_fibonacci_example::{
+/// #     circuits::{InputCommitmentCircuit, ReferenceStep, ROUND_CONSTANTS},
+/// #     mock,
+/// #     testing::prove_and_verify,
+/// # };
+/// // The hash chain on the CPU
+/// let hash = |acc: C1Scalar, input: u64| {
+///     ROUND_CONSTANTS.iter().fold(acc, |h, c| {
+///         (h + C1Scalar::from(input) + C1Scalar::from(*c)).pow_vartime([5])
+///     })
+/// };
+///
+/// let inputs = [7, 1, 8];
+/// let mut z = [C1Scalar::from(0)];
+/// for input in inputs {
+///     let sc = InputCommitmentCircuit::new(input);
+///     mock::check_step(5, &sc, z).unwrap();
+///     z = sc.reference_step(&z);
+/// }
+/// let chain = inputs.iter().fold(C1Scalar::from(0), |acc, input| hash(acc, *input));
+/// assert_eq!(z, [chain]);
+///
+/// // The commitment binds the order of the inputs
+/// let reordered = [8, 1, 7].iter().fold(C1Scalar::from(0), |acc, input| hash(acc, *input));
+/// assert_ne!(reordered, chain);
+///
+/// let z_out = prove_and_verify(&InputCommitmentCircuit::new(7), [C1Scalar::from(0)], 2);
+/// assert_eq!(z_out, [hash(hash(C1Scalar::from(0), 7), 7)]);
+/// ```
+#[derive(Clone)]
+pub struct InputCommitmentCircuit {
+    input: u64,
+}
+
+impl InputCommitmentCircuit {
+    pub fn new(input: u64) -> Self {
+        Self { input }
+    }
+
+    fn round<F: PrimeField>(h: F, input: F, c: u64) -> F {
+        (h + input + F::from(c)).pow_vartime([5])
+    }
+}
+
+impl<F: PrimeField> StepCircuit<1, F> for InputCommitmentCircuit {
+    type Config = InputCommitmentConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.selector(),
+            h: cs.advice_column(),
+            input: cs.advice_column(),
+            sq: cs.advice_column(),
+            quad: cs.advice_column(),
+            c: cs.fixed_column(),
+        };
+
+        cs.enable_equality(config.h);
+
+        cs.create_gate("input-commitment-round", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let h = meta.query_advice(config.h, Rotation::cur());
+            let h_next = meta.query_advice(config.h, Rotation::next());
+            let input = meta.query_advice(config.input, Rotation::cur());
+            let input_next = meta.query_advice(config.input, Rotation::next());
+            let sq = meta.query_advice(config.sq, Rotation::cur());
+            let quad = meta.query_advice(config.quad, Rotation::cur());
+            let c = meta.query_fixed(config.c, Rotation::cur());
+
+            let t = h + input.clone() + c;
+
+            // The fifth power is split over the hints to keep the gate degree at three
+            vec![
+                s.clone() * (sq.clone() - t.clone() * t.clone()),
+                s.clone() * (quad.clone() - sq.clone() * sq),
+                s.clone() * (h_next - quad * t),
+                // The same input on every round
+                s * (input_next - input),
+            ]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; 1],
+    ) -> Result<[AssignedCell<F, F>; 1], SynthesisError> {
+        let input = Value::known(F::from(self.input));
+
+        let z_out = layouter.assign_region(
+            || "input-commitment",
+            |mut region| {
+                let [acc] = z_i;
+
+                let mut h = acc.copy_advice(|| "h", &mut region, config.h, 0)?;
+                for (row, c) in ROUND_CONSTANTS.iter().enumerate() {
+                    config.s.enable(&mut region, row)?;
+                    region.assign_fixed(|| "c", config.c, row, || Value::known(F::from(*c)))?;
+                    region.assign_advice(|| "input", config.input, row, || input)?;
+
+                    let t = h.value().copied() + input + Value::known(F::from(*c));
+                    let sq = t * t;
+                    let quad = sq * sq;
+                    region.assign_advice(|| "sq", config.sq, row, || sq)?;
+                    region.assign_advice(|| "quad", config.quad, row, || quad)?;
+
+                    h = region.assign_advice(|| "h", config.h, row + 1, || quad * t)?;
+                }
+                region.assign_advice(|| "input", config.input, ROUND_CONSTANTS.len(), || input)?;
+
+                Ok(h)
+            },
+        )?;
+
+        Ok([z_out])
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<1, F> for InputCommitmentCircuit {
+    fn reference_step(&self, [acc]: &[F; 1]) -> [F; 1] {
+        let input = F::from(self.input);
+
+        [ROUND_CONSTANTS
+            .iter()
+            .fold(*acc, |h, c| Self::round(h, input, *c))]
+    }
+}
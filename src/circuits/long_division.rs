@@ -0,0 +1,274 @@
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Expression, Fixed, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::{
+    gadgets::{self, RangeCheckConfig},
+    ReferenceStep,
+};
+
+/// Base of the dividend digits
+const BASE: u64 = 10;
+
+/// Bits of the range-checked digits, enough for `BASE - 1`
+const DIGIT_BITS: usize = 4;
+
+/// Bits of the range-checked remainder and its distance to the divisor, both below a `u64`
+/// divisor
+const REMAINDER_BITS: usize = 64;
+
+/// Configuration for the [`LongDivisionCircuit`], the step takes its first row, the rest are
+/// the range checks
+#[derive(Debug, Clone)]
+pub struct LongDivisionConfig {
+    s: Selector,
+    quotient: Column<Advice>,
+    remainder: Column<Advice>,
+    /// Dividend digit brought down, a private witness of the step
+    digit: Column<Advice>,
+    /// `BASE - 1 - digit`, range-checked to prove `digit < BASE`
+    digit_gap: Column<Advice>,
+    quotient_digit: Column<Advice>,
+    quotient_next: Column<Advice>,
+    remainder_next: Column<Advice>,
+    /// `divisor - 1 - remainder_next`, range-checked to prove `remainder_next < divisor`
+    remainder_gap: Column<Advice>,
+    divisor: Column<Fixed>,
+    range: RangeCheckConfig,
+}
+
+/// Circuit that runs one step of the schoolbook long division by a fixed `divisor` every step,
+/// on the state `[quotient, remainder]`
+///
+/// Each step brings down the next decimal digit `d` of the dividend, given per step, most
+/// significant first, and computes the next quotient digit `q` and remainder:
+///
+/// ```text
+/// 10 * remainder + d = divisor * q + remainder'
+/// quotient' = 10 * quotient + q
+/// ```
+///
+/// with `d < 10` and `remainder' < divisor` range-checked, and `q` a single range-checked digit.
+/// That proves the invariant `dividend = divisor * quotient + remainder` for the digits brought
+/// down so far, incrementally, without the dividend ever being in the state: scaling it by 10
+/// and adding `d` on both sides is exactly the step. The divisor is a fixed column, the same on
+/// every step. `z_0` is `[0, 0]`, the division of an empty dividend. The quotient isn't range
+/// checked, so it is exact as long as it stays below the field modulus. Like the
+/// [`super::SumCircuit`], each step gets its own instance.
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     circuits::{LongDivisionCircuit, ReferenceStep},
+/// #     error::ExampleError,
+/// #     mock,
+/// #     testing::prove_and_verify,
+/// # };
+/// // 9876543210 = 37 * 266933600 + 10
+/// let divisor = 37;
+/// let mut z = [C1Scalar::from(0); 2];
+/// for digit in [9, 8, 7, 6, 5, 4, 3, 2, 1, 0] {
+///     let sc = LongDivisionCircuit::new(digit, divisor);
+///     mock::check_step(8, &sc, z).unwrap();
+///     z = sc.reference_step(&z);
+/// }
+/// assert_eq!(z, [9876543210u64 / 37, 9876543210 % 37].map(C1Scalar::from));
+/// assert_eq!(z, [266933600, 10].map(C1Scalar::from));
+///
+/// // A dividend digit must be a decimal one
+/// let err = mock::check_step(8, &LongDivisionCircuit::new(10, divisor), z).unwrap_err();
+/// assert!(matches!(err, ExampleError::MockProver(_)), "{err}");
+///
+/// // Folded with the same digit every step: 555 = 7 * 79 + 2
+/// let z_out = prove_and_verify(&LongDivisionCircuit::new(5, 7), [C1Scalar::from(0); 2], 3);
+/// assert_eq!(z_out, [79, 2].map(C1Scalar::from));
+/// ```
+#[derive(Clone)]
+pub struct LongDivisionCircuit {
+    digit: u64,
+    divisor: u64,
+}
+
+impl LongDivisionCircuit {
+    /// # Panics
+    /// If `divisor` is zero
+    pub fn new(digit: u64, divisor: u64) -> Self {
+        assert!(divisor >= 1, "divisor must be at least 1");
+        Self { digit, divisor }
+    }
+
+    /// `(quotient digit, remainder)` of bringing the digit down after `remainder`, which must be
+    /// below the divisor
+    fn divide<F: PrimeField>(&self, remainder: &F) -> (u64, u64) {
+        let current = gadgets::to_u128(remainder)
+            .and_then(|remainder| remainder.checked_mul(BASE as u128))
+            .and_then(|current| current.checked_add(self.digit as u128))
+            .unwrap_or(u128::MAX);
+
+        let divisor = self.divisor as u128;
+        (
+            (current / divisor).try_into().unwrap_or(u64::MAX),
+            (current % divisor) as u64,
+        )
+    }
+}
+
+impl<F: PrimeField> StepCircuit<2, F> for LongDivisionCircuit {
+    type Config = LongDivisionConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.selector(),
+            quotient: cs.advice_column(),
+            remainder: cs.advice_column(),
+            digit: cs.advice_column(),
+            digit_gap: cs.advice_column(),
+            quotient_digit: cs.advice_column(),
+            quotient_next: cs.advice_column(),
+            remainder_next: cs.advice_column(),
+            remainder_gap: cs.advice_column(),
+            divisor: cs.fixed_column(),
+            range: RangeCheckConfig::configure(cs),
+        };
+
+        cs.enable_equality(config.quotient);
+        cs.enable_equality(config.remainder);
+        cs.enable_equality(config.digit);
+        cs.enable_equality(config.digit_gap);
+        cs.enable_equality(config.quotient_digit);
+        cs.enable_equality(config.quotient_next);
+        cs.enable_equality(config.remainder_next);
+        cs.enable_equality(config.remainder_gap);
+
+        cs.create_gate("long-division", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let quotient = meta.query_advice(config.quotient, Rotation::cur());
+            let remainder = meta.query_advice(config.remainder, Rotation::cur());
+            let digit = meta.query_advice(config.digit, Rotation::cur());
+            let digit_gap = meta.query_advice(config.digit_gap, Rotation::cur());
+            let quotient_digit = meta.query_advice(config.quotient_digit, Rotation::cur());
+            let quotient_next = meta.query_advice(config.quotient_next, Rotation::cur());
+            let remainder_next = meta.query_advice(config.remainder_next, Rotation::cur());
+            let remainder_gap = meta.query_advice(config.remainder_gap, Rotation::cur());
+            let divisor = meta.query_fixed(config.divisor, Rotation::cur());
+
+            let one = Expression::Constant(F::ONE);
+            let base = Expression::Constant(F::from(BASE));
+
+            vec![
+                s.clone()
+                    * (remainder * base.clone() + digit.clone()
+                        - (divisor.clone() * quotient_digit.clone() + remainder_next.clone())),
+                s.clone() * (quotient_next - (quotient * base.clone() + quotient_digit)),
+                s.clone() * (digit_gap - (base - one.clone() - digit)),
+                s * (remainder_gap - (divisor - one - remainder_next)),
+            ]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; 2],
+    ) -> Result<[AssignedCell<F, F>; 2], SynthesisError> {
+        let divisor = F::from(self.divisor);
+
+        let z_out = layouter.assign_region(
+            || "long-division",
+            |mut region| {
+                let [quotient, remainder] = z_i;
+
+                config.s.enable(&mut region, 0)?;
+                region.assign_fixed(|| "divisor", config.divisor, 0, || Value::known(divisor))?;
+
+                let quotient =
+                    quotient.copy_advice(|| "quotient", &mut region, config.quotient, 0)?;
+                let remainder =
+                    remainder.copy_advice(|| "remainder", &mut region, config.remainder, 0)?;
+
+                let digit = region.assign_advice(
+                    || "digit",
+                    config.digit,
+                    0,
+                    || Value::known(F::from(self.digit)),
+                )?;
+                // Computed in the field, so that a digit over `BASE - 1` wraps and fails its range
+                // check instead of underflowing here
+                let digit_gap = region.assign_advice(
+                    || "digit_gap",
+                    config.digit_gap,
+                    0,
+                    || Value::known(F::from(BASE - 1) - F::from(self.digit)),
+                )?;
+
+                let divided = remainder.value().map(|remainder| self.divide(remainder));
+                let quotient_digit = region.assign_advice(
+                    || "quotient_digit",
+                    config.quotient_digit,
+                    0,
+                    || divided.map(|(quotient_digit, _)| F::from(quotient_digit)),
+                )?;
+                let quotient_next = region.assign_advice(
+                    || "quotient_next",
+                    config.quotient_next,
+                    0,
+                    || {
+                        quotient.value().copied() * Value::known(F::from(BASE))
+                            + quotient_digit.value().copied()
+                    },
+                )?;
+                let remainder_next = region.assign_advice(
+                    || "remainder_next",
+                    config.remainder_next,
+                    0,
+                    || divided.map(|(_, remainder)| F::from(remainder)),
+                )?;
+                let remainder_gap = region.assign_advice(
+                    || "remainder_gap",
+                    config.remainder_gap,
+                    0,
+                    || Value::known(divisor - F::ONE) - remainder_next.value().copied(),
+                )?;
+
+                let mut offset = 1;
+                for (cell, bits) in [
+                    (&digit, DIGIT_BITS),
+                    (&digit_gap, DIGIT_BITS),
+                    (&quotient_digit, DIGIT_BITS),
+                    (&remainder_next, REMAINDER_BITS),
+                    (&remainder_gap, REMAINDER_BITS),
+                ] {
+                    config.range.assign(&mut region, offset, cell, bits)?;
+                    offset += RangeCheckConfig::rows(bits);
+                }
+
+                Ok([quotient_next, remainder_next])
+            },
+        )?;
+
+        Ok(z_out)
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<2, F> for LongDivisionCircuit {
+    fn reference_step(&self, [quotient, remainder]: &[F; 2]) -> [F; 2] {
+        let (quotient_digit, remainder) = self.divide(remainder);
+        [
+            *quotient * F::from(BASE) + F::from(quotient_digit),
+            F::from(remainder),
+        ]
+    }
+}
@@ -0,0 +1,60 @@
+//! Step circuits beyond the Fibonacci one, each showing a different folding pattern
+
+use sirius::prelude::PrimeField;
+
+mod average;
+mod bit_accum;
+mod bounded_fibo;
+mod challenge_accum;
+mod checksum;
+mod coeff_recurrence;
+mod dense_layer;
+mod digit_count_fibo;
+mod digit_root;
+mod fibo_mod;
+mod gadgets;
+mod indexed_fibo;
+mod input_commitment;
+mod long_division;
+mod mod_counter;
+mod opcode_lookup;
+mod pid;
+mod range_guard;
+mod saturating_sum;
+mod set_accumulator;
+mod sorted_pair;
+mod stack_machine;
+mod sum;
+mod window_average;
+
+pub use average::{fixed_point_average, AverageCircuit, AVERAGE_SCALE, AVERAGE_SCALE_BITS};
+pub use bit_accum::BitAccumCircuit;
+pub use bounded_fibo::BoundedFiboCircuit;
+pub use challenge_accum::ChallengeAccumCircuit;
+pub use checksum::{checksum, ChecksumCircuit};
+pub use coeff_recurrence::CoeffRecurrenceCircuit;
+pub use dense_layer::DenseLayerCircuit;
+pub use digit_count_fibo::{DigitCountFiboCircuit, MAX_DECIMAL_DIGITS};
+pub use digit_root::DigitRootCircuit;
+pub use fibo_mod::FiboModCircuit;
+pub use indexed_fibo::IndexedFiboCircuit;
+pub use input_commitment::{InputCommitmentCircuit, ROUND_CONSTANTS};
+pub use long_division::LongDivisionCircuit;
+pub use mod_counter::ModCounterCircuit;
+pub use opcode_lookup::{OpcodeLookupCircuit, OPCODES};
+pub use pid::{PidCircuit, PidGains, PID_SCALE, PID_SCALE_BITS};
+pub use range_guard::RangeGuardCircuit;
+pub use saturating_sum::SaturatingSumCircuit;
+pub use set_accumulator::{SetAccumulatorCircuit, SetOp};
+pub use sorted_pair::SortedPairCircuit;
+pub use stack_machine::{StackMachineCircuit, StackOp};
+pub use sum::SumCircuit;
+pub use window_average::WindowAverageCircuit;
+
+/// Out-of-circuit computation of one step of a step circuit
+///
+/// Lets the example follow the folded state (e.g. to report `z_out` or detect a fixpoint) without
+/// reading it back from the IVC. These are the witness values, the proof is what binds them.
+pub trait ReferenceStep<const ARITY: usize, F: PrimeField> {
+    fn reference_step(&self, z_i: &[F; ARITY]) -> [F; ARITY];
+}
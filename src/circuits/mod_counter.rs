@@ -0,0 +1,171 @@
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Expression, Fixed, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::ReferenceStep;
+
+/// Configuration for the [`ModCounterCircuit`], a single row holds the whole step
+#[derive(Debug, Clone)]
+pub struct ModCounterConfig {
+    /// Selector used to activate the gate that enforces the counter transition.
+    s: Selector,
+    /// Counter before the step, copied from `z_i`
+    z_in: Column<Advice>,
+    /// Counter after the step
+    z_out: Column<Advice>,
+    /// Hint: `1` when `z_in + 1` reached the modulus, `0` otherwise
+    wrap: Column<Advice>,
+    /// Hint: inverse of `z_in + 1 - m`, or zero if there is none
+    inv: Column<Advice>,
+    /// Modulus `m`
+    m: Column<Fixed>,
+}
+
+/// Circuit that increments a counter each step and wraps it back to zero at the modulus `m`, so the
+/// folded state goes `0, 1, .., m - 1, 0, 1, ..` cyclically.
+///
+/// The wrap is a conditional constraint `z_out = (1 - wrap) * (z_in + 1)`, where `wrap` is a
+/// hinted "is zero" flag of `z_in + 1 - m`. The circuit does not range-check the counter, so `z_0`
+/// must be in `0..m`.
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     circuits::{ModCounterCircuit, ReferenceStep},
+/// #     mock,
+/// #     testing::prove_and_verify,
+/// # };
+/// let sc = ModCounterCircuit::new(3);
+/// let mut z = [C1Scalar::from(0)];
+/// for expected in [1, 2, 0, 1] {
+///     mock::check_step(5, &sc, z).unwrap();
+///     z = sc.reference_step(&z);
+///     assert_eq!(z, [C1Scalar::from(expected)]);
+/// }
+///
+/// let z_out = prove_and_verify(&sc, [C1Scalar::from(1)], 4);
+/// assert_eq!(z_out, [C1Scalar::from(2)]);
+/// ```
+#[derive(Clone)]
+pub struct ModCounterCircuit {
+    modulus: u64,
+}
+
+impl ModCounterCircuit {
+    pub fn new(modulus: u64) -> Self {
+        Self { modulus }
+    }
+}
+
+impl<F: PrimeField> StepCircuit<1, F> for ModCounterCircuit {
+    type Config = ModCounterConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.selector(),
+            z_in: cs.advice_column(),
+            z_out: cs.advice_column(),
+            wrap: cs.advice_column(),
+            inv: cs.advice_column(),
+            m: cs.fixed_column(),
+        };
+
+        cs.enable_equality(config.z_in);
+        cs.enable_equality(config.z_out);
+
+        cs.create_gate("mod-counter", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let z_in = meta.query_advice(config.z_in, Rotation::cur());
+            let z_out = meta.query_advice(config.z_out, Rotation::cur());
+            let wrap = meta.query_advice(config.wrap, Rotation::cur());
+            let inv = meta.query_advice(config.inv, Rotation::cur());
+            let m = meta.query_fixed(config.m, Rotation::cur());
+
+            let one = Expression::Constant(F::ONE);
+            let next = z_in + one.clone();
+            let diff = next.clone() - m;
+
+            vec![
+                // `wrap` is one exactly when `diff` is zero
+                s.clone() * (diff.clone() * inv - (one.clone() - wrap.clone())),
+                s.clone() * (diff * wrap.clone()),
+                // Select between `z_in + 1` and `0`
+                s * (z_out - (one - wrap) * next),
+            ]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; 1],
+    ) -> Result<[AssignedCell<F, F>; 1], SynthesisError> {
+        let m = F::from(self.modulus);
+
+        let z_out = layouter.assign_region(
+            || "mod-counter",
+            |mut region| {
+                let [z] = z_i;
+
+                config.s.enable(&mut region, 0)?;
+                region.assign_fixed(|| "m", config.m, 0, || Value::known(m))?;
+
+                let z_in = z.copy_advice(|| "z_in", &mut region, config.z_in, 0)?;
+                let diff = z_in.value().map(|z| *z + F::ONE - m);
+
+                let wrap = diff.map(|diff| {
+                    if diff.is_zero_vartime() {
+                        F::ONE
+                    } else {
+                        F::ZERO
+                    }
+                });
+                region.assign_advice(|| "wrap", config.wrap, 0, || wrap)?;
+                region.assign_advice(
+                    || "inv",
+                    config.inv,
+                    0,
+                    || diff.map(|diff| diff.invert().unwrap_or(F::ZERO)),
+                )?;
+
+                region.assign_advice(
+                    || "z_out",
+                    config.z_out,
+                    0,
+                    || {
+                        z_in.value()
+                            .zip(wrap)
+                            .map(|(z, wrap)| (F::ONE - wrap) * (*z + F::ONE))
+                    },
+                )
+            },
+        )?;
+
+        Ok([z_out])
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<1, F> for ModCounterCircuit {
+    fn reference_step(&self, [z]: &[F; 1]) -> [F; 1] {
+        let next = *z + F::ONE;
+
+        if next == F::from(self.modulus) {
+            [F::ZERO]
+        } else {
+            [next]
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Expression, Selector, TableColumn},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::ReferenceStep;
+
+/// Valid opcodes, a subset of the EVM ones: `STOP`, `ADD`, `MUL`, `SUB`, `DIV`, `LT`, `EQ` and
+/// `JUMP`
+///
+/// Must contain `0`: rows with the selector off look up `0`
+pub const OPCODES: [u64; 8] = [0x00, 0x01, 0x02, 0x03, 0x04, 0x10, 0x14, 0x56];
+
+/// Every opcode is below this, so the trace packs one opcode per digit in this base
+const OPCODE_BASE: u64 = 0x100;
+
+/// Configuration for the [`OpcodeLookupCircuit`], the step takes a single row
+#[derive(Debug, Clone)]
+pub struct OpcodeLookupConfig {
+    s: Selector,
+    /// Packed trace of the opcodes so far
+    trace: Column<Advice>,
+    /// Per-step opcode, a private witness of the step
+    opcode: Column<Advice>,
+    /// `trace * OPCODE_BASE + opcode`
+    out: Column<Advice>,
+    /// Fixed table of the [`OPCODES`]
+    table: TableColumn,
+}
+
+/// Circuit that appends a per-step opcode to a packed trace, `trace' = trace * 0x100 + opcode`,
+/// proving the opcode is one of the [`OPCODES`] with a lookup argument into a fixed table
+///
+/// Unlike a range check, which proves the opcode is in an interval bit by bit, the lookup
+/// proves membership in an arbitrary set with a single constraint. An opcode outside the table
+/// makes the step unsatisfiable. Like the [`super::SumCircuit`], each step gets its own
+/// instance.
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     circuits::{OpcodeLookupCircuit, ReferenceStep},
+/// #     mock,
+/// #     testing::prove_and_verify,
+/// # };
+/// // ADD, MUL, LT
+/// let mut z = [C1Scalar::from(0)];
+/// for opcode in [0x01, 0x02, 0x10] {
+///     let sc = OpcodeLookupCircuit::new(opcode);
+///     mock::check_step(5, &sc, z).unwrap();
+///     z = sc.reference_step(&z);
+/// }
+/// assert_eq!(z, [C1Scalar::from(0x01_02_10)]);
+///
+/// // `0x05` (SDIV) is not in the table
+/// mock::check_step(5, &OpcodeLookupCircuit::new(0x05), z).unwrap_err();
+///
+/// let z_out = prove_and_verify(&OpcodeLookupCircuit::new(0x56), [C1Scalar::from(0)], 2);
+/// assert_eq!(z_out, [C1Scalar::from(0x56_56)]);
+/// ```
+#[derive(Clone)]
+pub struct OpcodeLookupCircuit {
+    opcode: u64,
+}
+
+impl OpcodeLookupCircuit {
+    pub fn new(opcode: u64) -> Self {
+        Self { opcode }
+    }
+}
+
+impl<F: PrimeField> StepCircuit<1, F> for OpcodeLookupCircuit {
+    type Config = OpcodeLookupConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.complex_selector(),
+            trace: cs.advice_column(),
+            opcode: cs.advice_column(),
+            out: cs.advice_column(),
+            // Filled with the `OPCODES` by `synthesize_step`, the same on every step, so the
+            // table is part of the circuit shape the public parameters are built from
+            table: cs.lookup_table_column(),
+        };
+
+        cs.enable_equality(config.trace);
+        cs.enable_equality(config.out);
+
+        cs.create_gate("opcode-trace", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let trace = meta.query_advice(config.trace, Rotation::cur());
+            let opcode = meta.query_advice(config.opcode, Rotation::cur());
+            let out = meta.query_advice(config.out, Rotation::cur());
+
+            let base = Expression::Constant(F::from(OPCODE_BASE));
+            vec![s * (out - (trace * base + opcode))]
+        });
+
+        // A complex selector, as it is used in a lookup: the input is `0` where it is off, which
+        // is why `OPCODES` contains `0`
+        cs.lookup("opcode-in-table", |meta| {
+            let s = meta.query_selector(config.s);
+            let opcode = meta.query_advice(config.opcode, Rotation::cur());
+
+            vec![(s * opcode, config.table)]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; 1],
+    ) -> Result<[AssignedCell<F, F>; 1], SynthesisError> {
+        layouter.assign_table(
+            || "opcodes",
+            |mut table| {
+                for (offset, opcode) in OPCODES.iter().enumerate() {
+                    table.assign_cell(
+                        || "opcode",
+                        config.table,
+                        offset,
+                        || Value::known(F::from(*opcode)),
+                    )?;
+                }
+
+                Ok(())
+            },
+        )?;
+
+        let z_out = layouter.assign_region(
+            || "opcode-trace",
+            |mut region| {
+                let [trace] = z_i;
+
+                config.s.enable(&mut region, 0)?;
+
+                let trace = trace.copy_advice(|| "trace", &mut region, config.trace, 0)?;
+                let opcode = Value::known(F::from(self.opcode));
+                region.assign_advice(|| "opcode", config.opcode, 0, || opcode)?;
+
+                region.assign_advice(
+                    || "out",
+                    config.out,
+                    0,
+                    || trace.value().copied() * Value::known(F::from(OPCODE_BASE)) + opcode,
+                )
+            },
+        )?;
+
+        Ok([z_out])
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<1, F> for OpcodeLookupCircuit {
+    fn reference_step(&self, [trace]: &[F; 1]) -> [F; 1] {
+        [*trace * F::from(OPCODE_BASE) + F::from(self.opcode)]
+    }
+}
@@ -0,0 +1,242 @@
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Fixed, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::ReferenceStep;
+use crate::field;
+
+/// Fractional bits of the fixed-point PID gains, and so of the output
+pub const PID_SCALE_BITS: u32 = 16;
+
+/// A fixed-point gain `g` stands for `g / PID_SCALE`
+pub const PID_SCALE: i64 = 1 << PID_SCALE_BITS;
+
+/// Proportional, integral and derivative gains of the [`PidCircuit`], fixed-point with
+/// [`PID_SCALE_BITS`] fractional bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PidGains {
+    pub kp: i64,
+    pub ki: i64,
+    pub kd: i64,
+}
+
+impl PidGains {
+    /// The fixed-point gains nearest to `kp`, `ki` & `kd`
+    pub fn from_f64(kp: f64, ki: f64, kd: f64) -> Self {
+        let fixed = |gain: f64| (gain * PID_SCALE as f64).round() as i64;
+        Self {
+            kp: fixed(kp),
+            ki: fixed(ki),
+            kd: fixed(kd),
+        }
+    }
+}
+
+/// Configuration for the [`PidCircuit`], a single row holds the whole step
+#[derive(Debug, Clone)]
+pub struct PidConfig {
+    s: Selector,
+    integral: Column<Advice>,
+    prev_error: Column<Advice>,
+    /// Per-step error, a private witness of the step
+    error: Column<Advice>,
+    integral_next: Column<Advice>,
+    prev_error_next: Column<Advice>,
+    output_next: Column<Advice>,
+    kp: Column<Fixed>,
+    ki: Column<Fixed>,
+    kd: Column<Fixed>,
+}
+
+/// Circuit that runs one update of a discrete PID controller every step, on the state
+/// `[integral, prev_error, output]`:
+///
+/// ```text
+/// integral' = integral + error
+/// prev_error' = error
+/// output' = kp * error + ki * integral' + kd * (error - prev_error)
+/// ```
+///
+/// The error is a signed integer given per step, the gains are fixed-point (see [`PidGains`])
+/// and the same on every step, so they are fixed columns. Negative values wrap around the field
+/// modulus, which the update is linear in, so the state is exact as long as it stays far from
+/// the modulus; read it back with [`field::to_i128`]. The output keeps the [`PID_SCALE_BITS`]
+/// fractional bits of the gains: the next update doesn't depend on it, so it is never rescaled.
+/// `z_0` is `[0, 0, 0]` for a controller at rest. Like the [`super::SumCircuit`], each step gets
+/// its own instance.
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     circuits::{PidCircuit, PidGains, ReferenceStep, PID_SCALE},
+/// #     field, mock,
+/// #     testing::prove_and_verify,
+/// # };
+/// let (kp, ki, kd) = (0.6, 0.1, 0.05);
+/// let gains = PidGains::from_f64(kp, ki, kd);
+/// let errors = [10, 7, 3, -2, -4, 1, 0, 5];
+///
+/// let mut z = [C1Scalar::from(0); 3];
+/// // The same controller in floating point
+/// let (mut integral, mut prev_error) = (0.0, 0.0);
+/// for error in errors {
+///     let sc = PidCircuit::new(error, gains);
+///     mock::check_step(5, &sc, z).unwrap();
+///     z = sc.reference_step(&z);
+///
+///     let error = error as f64;
+///     integral += error;
+///     let output = kp * error + ki * integral + kd * (error - prev_error);
+///     prev_error = error;
+///
+///     let folded = field::to_i128(&z[2]).unwrap() as f64 / PID_SCALE as f64;
+///     // Only the gains are rounded, to 1 / PID_SCALE
+///     assert!((folded - output).abs() < 1e-3, "{folded} != {output}");
+/// }
+/// assert_eq!(field::to_i128(&z[0]), Some(errors.iter().sum::<i64>() as i128));
+///
+/// // Folded with the same error on every step
+/// let z_out = prove_and_verify(&PidCircuit::new(-2, gains), [C1Scalar::from(0); 3], 3);
+/// assert_eq!(field::to_i128(&z_out[0]), Some(-6));
+/// assert_eq!(field::to_i128(&z_out[1]), Some(-2));
+/// ```
+#[derive(Clone)]
+pub struct PidCircuit {
+    error: i64,
+    gains: PidGains,
+}
+
+impl PidCircuit {
+    pub fn new(error: i64, gains: PidGains) -> Self {
+        Self { error, gains }
+    }
+}
+
+impl<F: PrimeField> StepCircuit<3, F> for PidCircuit {
+    type Config = PidConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.selector(),
+            integral: cs.advice_column(),
+            prev_error: cs.advice_column(),
+            error: cs.advice_column(),
+            integral_next: cs.advice_column(),
+            prev_error_next: cs.advice_column(),
+            output_next: cs.advice_column(),
+            kp: cs.fixed_column(),
+            ki: cs.fixed_column(),
+            kd: cs.fixed_column(),
+        };
+
+        cs.enable_equality(config.integral);
+        cs.enable_equality(config.prev_error);
+        cs.enable_equality(config.integral_next);
+        cs.enable_equality(config.prev_error_next);
+        cs.enable_equality(config.output_next);
+
+        cs.create_gate("pid", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let integral = meta.query_advice(config.integral, Rotation::cur());
+            let prev_error = meta.query_advice(config.prev_error, Rotation::cur());
+            let error = meta.query_advice(config.error, Rotation::cur());
+            let integral_next = meta.query_advice(config.integral_next, Rotation::cur());
+            let prev_error_next = meta.query_advice(config.prev_error_next, Rotation::cur());
+            let output_next = meta.query_advice(config.output_next, Rotation::cur());
+            let kp = meta.query_fixed(config.kp, Rotation::cur());
+            let ki = meta.query_fixed(config.ki, Rotation::cur());
+            let kd = meta.query_fixed(config.kd, Rotation::cur());
+
+            vec![
+                s.clone() * (integral_next.clone() - (integral + error.clone())),
+                s.clone() * (prev_error_next - error.clone()),
+                s * (output_next
+                    - (kp * error.clone() + ki * integral_next + kd * (error - prev_error))),
+            ]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; 3],
+    ) -> Result<[AssignedCell<F, F>; 3], SynthesisError> {
+        let error = field::from_i64::<F>(self.error);
+        let [kp, ki, kd] = [self.gains.kp, self.gains.ki, self.gains.kd].map(field::from_i64::<F>);
+
+        let z_out = layouter.assign_region(
+            || "pid",
+            |mut region| {
+                // The previous output is not an input of the update
+                let [integral, prev_error, _output] = z_i;
+
+                config.s.enable(&mut region, 0)?;
+                region.assign_fixed(|| "kp", config.kp, 0, || Value::known(kp))?;
+                region.assign_fixed(|| "ki", config.ki, 0, || Value::known(ki))?;
+                region.assign_fixed(|| "kd", config.kd, 0, || Value::known(kd))?;
+
+                let integral =
+                    integral.copy_advice(|| "integral", &mut region, config.integral, 0)?;
+                let prev_error =
+                    prev_error.copy_advice(|| "prev_error", &mut region, config.prev_error, 0)?;
+                region.assign_advice(|| "error", config.error, 0, || Value::known(error))?;
+
+                let integral_next = region.assign_advice(
+                    || "integral_next",
+                    config.integral_next,
+                    0,
+                    || integral.value().map(|integral| *integral + error),
+                )?;
+                let prev_error_next = region.assign_advice(
+                    || "prev_error_next",
+                    config.prev_error_next,
+                    0,
+                    || Value::known(error),
+                )?;
+                let output_next = region.assign_advice(
+                    || "output_next",
+                    config.output_next,
+                    0,
+                    || {
+                        integral_next.value().zip(prev_error.value()).map(
+                            |(integral, prev_error)| {
+                                kp * error + ki * integral + kd * (error - prev_error)
+                            },
+                        )
+                    },
+                )?;
+
+                Ok([integral_next, prev_error_next, output_next])
+            },
+        )?;
+
+        Ok(z_out)
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<3, F> for PidCircuit {
+    fn reference_step(&self, [integral, prev_error, _output]: &[F; 3]) -> [F; 3] {
+        let error = field::from_i64::<F>(self.error);
+        let [kp, ki, kd] = [self.gains.kp, self.gains.ki, self.gains.kd].map(field::from_i64::<F>);
+
+        let integral = *integral + error;
+        [
+            integral,
+            error,
+            kp * error + ki * integral + kd * (error - *prev_error),
+        ]
+    }
+}
@@ -0,0 +1,176 @@
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Fixed, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::{gadgets::RangeCheckConfig, ReferenceStep};
+
+/// Bounds and readings are `u64`, so both distances to the bounds fit into 64 bits when in range
+const DISTANCE_BITS: usize = 64;
+
+/// Configuration for the [`RangeGuardCircuit`], the step takes its first row, the rest are the
+/// range checks
+#[derive(Debug, Clone)]
+pub struct RangeGuardConfig {
+    s: Selector,
+    /// Per-step reading, a private witness of the step and the output
+    reading: Column<Advice>,
+    /// `reading - lo`, range-checked to prove `lo <= reading`
+    above_lo: Column<Advice>,
+    /// `hi - reading`, range-checked to prove `reading <= hi`
+    below_hi: Column<Advice>,
+    lo: Column<Fixed>,
+    hi: Column<Fixed>,
+    range: RangeCheckConfig,
+}
+
+/// Circuit that proves the per-step reading lies in `[lo, hi]` and carries it forward as the state
+///
+/// Each bound is proven by range-checking the distance to it into 64 bits: if the reading is out
+/// of bounds, that distance wraps around the field and the step is unsatisfiable. The previous
+/// state isn't used, the folded IVC attests that every reading of the stream was in bounds, with
+/// the last one as `z_out`. Like the [`super::SumCircuit`], each step gets its own instance.
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     circuits::{RangeGuardCircuit, ReferenceStep},
+/// #     mock,
+/// #     testing::prove_and_verify,
+/// # };
+/// let mut z = [C1Scalar::from(0)];
+/// for reading in [21, 23, 0, 100] {
+///     let sc = RangeGuardCircuit::new(reading, 0, 100);
+///     mock::check_step(8, &sc, z).unwrap();
+///     z = sc.reference_step(&z);
+/// }
+/// assert_eq!(z, [C1Scalar::from(100)]);
+///
+/// // Out of bounds on either side
+/// mock::check_step(8, &RangeGuardCircuit::new(101, 0, 100), z).unwrap_err();
+/// mock::check_step(8, &RangeGuardCircuit::new(4, 5, 10), z).unwrap_err();
+///
+/// let z_out = prove_and_verify(&RangeGuardCircuit::new(42, 0, 100), [C1Scalar::from(0)], 2);
+/// assert_eq!(z_out, [C1Scalar::from(42)]);
+/// ```
+#[derive(Clone)]
+pub struct RangeGuardCircuit {
+    reading: u64,
+    lo: u64,
+    hi: u64,
+}
+
+impl RangeGuardCircuit {
+    /// # Panics
+    /// If `lo > hi`
+    pub fn new(reading: u64, lo: u64, hi: u64) -> Self {
+        assert!(lo <= hi, "lo must not be above hi");
+
+        Self { reading, lo, hi }
+    }
+}
+
+impl<F: PrimeField> StepCircuit<1, F> for RangeGuardCircuit {
+    type Config = RangeGuardConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.selector(),
+            reading: cs.advice_column(),
+            above_lo: cs.advice_column(),
+            below_hi: cs.advice_column(),
+            lo: cs.fixed_column(),
+            hi: cs.fixed_column(),
+            range: RangeCheckConfig::configure(cs),
+        };
+
+        cs.enable_equality(config.reading);
+        cs.enable_equality(config.above_lo);
+        cs.enable_equality(config.below_hi);
+
+        cs.create_gate("range-guard", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let reading = meta.query_advice(config.reading, Rotation::cur());
+            let above_lo = meta.query_advice(config.above_lo, Rotation::cur());
+            let below_hi = meta.query_advice(config.below_hi, Rotation::cur());
+            let lo = meta.query_fixed(config.lo, Rotation::cur());
+            let hi = meta.query_fixed(config.hi, Rotation::cur());
+
+            vec![
+                s.clone() * (above_lo - (reading.clone() - lo)),
+                s * (below_hi - (hi - reading)),
+            ]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        _z_i: &[AssignedCell<F, F>; 1],
+    ) -> Result<[AssignedCell<F, F>; 1], SynthesisError> {
+        let reading = F::from(self.reading);
+        let lo = F::from(self.lo);
+        let hi = F::from(self.hi);
+
+        let z_out = layouter.assign_region(
+            || "range-guard",
+            |mut region| {
+                config.s.enable(&mut region, 0)?;
+                region.assign_fixed(|| "lo", config.lo, 0, || Value::known(lo))?;
+                region.assign_fixed(|| "hi", config.hi, 0, || Value::known(hi))?;
+
+                let reading = region.assign_advice(
+                    || "reading",
+                    config.reading,
+                    0,
+                    || Value::known(reading),
+                )?;
+                let above_lo = region.assign_advice(
+                    || "above_lo",
+                    config.above_lo,
+                    0,
+                    || reading.value().map(|reading| *reading - lo),
+                )?;
+                let below_hi = region.assign_advice(
+                    || "below_hi",
+                    config.below_hi,
+                    0,
+                    || reading.value().map(|reading| hi - *reading),
+                )?;
+
+                config
+                    .range
+                    .assign(&mut region, 1, &above_lo, DISTANCE_BITS)?;
+                config.range.assign(
+                    &mut region,
+                    1 + RangeCheckConfig::rows(DISTANCE_BITS),
+                    &below_hi,
+                    DISTANCE_BITS,
+                )?;
+
+                Ok(reading)
+            },
+        )?;
+
+        Ok([z_out])
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<1, F> for RangeGuardCircuit {
+    /// The reading, the proof of an out-of-bounds one fails instead
+    fn reference_step(&self, _z_i: &[F; 1]) -> [F; 1] {
+        [F::from(self.reading)]
+    }
+}
@@ -0,0 +1,201 @@
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Expression, Fixed, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::{
+    gadgets::{self, RangeCheckConfig},
+    ReferenceStep,
+};
+
+/// Bits of the range-checked distance to the cap: the sum of two `u64` values fits into 65 bits
+const DIFF_BITS: usize = 65;
+
+/// Configuration for the [`SaturatingSumCircuit`], the step takes its first row, the rest is the
+/// range check
+#[derive(Debug, Clone)]
+pub struct SaturatingSumConfig {
+    s: Selector,
+    z_in: Column<Advice>,
+    /// Per-step input, a private witness of the step
+    addend: Column<Advice>,
+    /// Hint: `1` when `z_in + addend` is above the cap, `0` otherwise
+    over: Column<Advice>,
+    /// Distance between the sum and the cap, range-checked to prove the comparison
+    diff: Column<Advice>,
+    z_out: Column<Advice>,
+    cap: Column<Fixed>,
+    range: RangeCheckConfig,
+}
+
+/// Circuit that adds a per-step addend to a running total, capped at a maximum:
+/// `z_out = min(z_in + addend, cap)`
+///
+/// The clamp selects between the sum and the cap with a boolean `over` hint, which is proven by
+/// range-checking the distance to the cap: `sum - cap - 1` when over, `cap - sum` otherwise, so a
+/// wrong hint would need a distance that wraps around the field. This holds as long as `z_0` fits
+/// into `u64`, every later state is at most the cap. Like the [`super::SumCircuit`], each step gets
+/// its own instance.
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     circuits::{ReferenceStep, SaturatingSumCircuit},
+/// #     mock,
+/// #     testing::prove_and_verify,
+/// # };
+/// let sc = SaturatingSumCircuit::new(4, 10);
+/// let mut z = [C1Scalar::from(0)];
+/// for expected in [4, 8, 10, 10] {
+///     mock::check_step(7, &sc, z).unwrap();
+///     z = sc.reference_step(&z);
+///     assert_eq!(z, [C1Scalar::from(expected)]);
+/// }
+///
+/// let z_out = prove_and_verify(&SaturatingSumCircuit::new(7, 10), [C1Scalar::from(0)], 3);
+/// assert_eq!(z_out, [C1Scalar::from(10)]);
+/// ```
+#[derive(Clone)]
+pub struct SaturatingSumCircuit {
+    addend: u64,
+    cap: u64,
+}
+
+impl SaturatingSumCircuit {
+    pub fn new(addend: u64, cap: u64) -> Self {
+        Self { addend, cap }
+    }
+
+    /// Whether `z_in + addend` is above the cap, `z_in` must fit into `u64`
+    fn is_over<F: PrimeField>(&self, z_in: &F) -> bool {
+        gadgets::to_u128(z_in)
+            .unwrap_or(u128::MAX)
+            .saturating_add(self.addend as u128)
+            > self.cap as u128
+    }
+}
+
+impl<F: PrimeField> StepCircuit<1, F> for SaturatingSumCircuit {
+    type Config = SaturatingSumConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.selector(),
+            z_in: cs.advice_column(),
+            addend: cs.advice_column(),
+            over: cs.advice_column(),
+            diff: cs.advice_column(),
+            z_out: cs.advice_column(),
+            cap: cs.fixed_column(),
+            range: RangeCheckConfig::configure(cs),
+        };
+
+        cs.enable_equality(config.z_in);
+        cs.enable_equality(config.diff);
+        cs.enable_equality(config.z_out);
+
+        cs.create_gate("saturating-sum", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let z_in = meta.query_advice(config.z_in, Rotation::cur());
+            let addend = meta.query_advice(config.addend, Rotation::cur());
+            let over = meta.query_advice(config.over, Rotation::cur());
+            let diff = meta.query_advice(config.diff, Rotation::cur());
+            let z_out = meta.query_advice(config.z_out, Rotation::cur());
+            let cap = meta.query_fixed(config.cap, Rotation::cur());
+
+            let one = Expression::Constant(F::ONE);
+            let sum = z_in + addend;
+            let under = one.clone() - over.clone();
+
+            vec![
+                s.clone() * (over.clone() * under.clone()),
+                // Select between the cap and the sum
+                s.clone() * (z_out - (over.clone() * cap.clone() + under.clone() * sum.clone())),
+                s * (diff - (over * (sum.clone() - cap.clone() - one) + under * (cap - sum))),
+            ]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; 1],
+    ) -> Result<[AssignedCell<F, F>; 1], SynthesisError> {
+        let addend = F::from(self.addend);
+        let cap = F::from(self.cap);
+
+        let z_out = layouter.assign_region(
+            || "saturating-sum",
+            |mut region| {
+                let [z] = z_i;
+
+                config.s.enable(&mut region, 0)?;
+                region.assign_fixed(|| "cap", config.cap, 0, || Value::known(cap))?;
+
+                let z_in = z.copy_advice(|| "z_in", &mut region, config.z_in, 0)?;
+                region.assign_advice(|| "addend", config.addend, 0, || Value::known(addend))?;
+
+                let sum = z_in.value().map(|z| *z + addend);
+                let over = z_in.value().map(|z| self.is_over(z));
+                region.assign_advice(
+                    || "over",
+                    config.over,
+                    0,
+                    || over.map(|over| F::from(over as u64)),
+                )?;
+
+                let diff = region.assign_advice(
+                    || "diff",
+                    config.diff,
+                    0,
+                    || {
+                        sum.zip(over).map(
+                            |(sum, over)| {
+                                if over {
+                                    sum - cap - F::ONE
+                                } else {
+                                    cap - sum
+                                }
+                            },
+                        )
+                    },
+                )?;
+                config.range.assign(&mut region, 1, &diff, DIFF_BITS)?;
+
+                region.assign_advice(
+                    || "z_out",
+                    config.z_out,
+                    0,
+                    || {
+                        sum.zip(over)
+                            .map(|(sum, over)| if over { cap } else { sum })
+                    },
+                )
+            },
+        )?;
+
+        Ok([z_out])
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<1, F> for SaturatingSumCircuit {
+    fn reference_step(&self, [z]: &[F; 1]) -> [F; 1] {
+        if self.is_over(z) {
+            [F::from(self.cap)]
+        } else {
+            [*z + F::from(self.addend)]
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Fixed, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::ReferenceStep;
+
+/// One step of the [`SetAccumulatorCircuit`]
+///
+/// There is no membership proof: in a prime field the accumulator of the set without an element
+/// exists for a non-member too, so a step witnessing it would verify for any element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", content = "element", rename_all = "kebab-case")]
+pub enum SetOp {
+    /// Add the element to the set
+    Add(u64),
+}
+
+impl SetOp {
+    pub fn element(self) -> u64 {
+        match self {
+            Self::Add(element) => element,
+        }
+    }
+}
+
+/// Parses `add:<element>`
+impl FromStr for SetOp {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (op, element) = s
+            .split_once(':')
+            .ok_or_else(|| format!("`{s}` is not `add:<element>`"))?;
+        let element = element
+            .parse()
+            .map_err(|err| format!("element of `{s}`: {err}"))?;
+
+        match op {
+            "add" => Ok(Self::Add(element)),
+            _ => Err(format!("unknown set operation `{op}`, expected `add`")),
+        }
+    }
+}
+
+impl fmt::Display for SetOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Add(element) => write!(f, "add:{element}"),
+        }
+    }
+}
+
+/// Point the accumulator polynomial is evaluated at, `2^64`, so that `point - element` is never
+/// zero for a `u64` element
+fn point<F: PrimeField>() -> F {
+    F::from_u128(1 << 64)
+}
+
+/// Configuration for the [`SetAccumulatorCircuit`], a single row holds the whole step
+#[derive(Debug, Clone)]
+pub struct SetAccumulatorConfig {
+    s: Selector,
+    acc: Column<Advice>,
+    element: Column<Advice>,
+    acc_next: Column<Advice>,
+    point: Column<Fixed>,
+}
+
+/// Circuit that keeps an accumulator of a set of elements in its 1-element state, adding an
+/// element every step
+///
+/// The accumulator is the product `(s - x_1) * (s - x_2) * ...` over the elements `x_i` added so
+/// far, at the point `s = 2^64`, so `z_0 = [1]` for the empty set. Adding `x` multiplies it by
+/// `s - x`, like the exponent of an RSA accumulator, so the accumulator doesn't depend on the
+/// order the elements are added in. The element is a private witness of the step, given per step
+/// by a [`SetOp`]; like the [`super::SumCircuit`], each step gets its own instance.
+///
+/// Membership can't be proven against this accumulator: every non-zero value is invertible in a
+/// prime field, so the accumulator `acc / (s - x)` of the set without `x` exists for a non-member
+/// `x` too. A sound membership proof needs a group of unknown order, as in an RSA accumulator, or
+/// a pairing-based one with a secret point.
+///
+/// ```
+/// # use sirius::prelude::{bn256::C1Scalar, PrimeField};
+/// # use sirius_fibonacci_example::{
+/// #     circuits::{ReferenceStep, SetAccumulatorCircuit, SetOp},
+/// #     mock,
+/// #     testing::prove_and_verify,
+/// # };
+/// let s = C1Scalar::from_u128(1 << 64);
+/// let acc = (s - C1Scalar::from(3)) * (s - C1Scalar::from(5)) * (s - C1Scalar::from(8));
+///
+/// // In any order
+/// for elements in [[3, 5, 8], [8, 3, 5]] {
+///     let mut z = [C1Scalar::from(1)];
+///     for element in elements {
+///         let sc = SetAccumulatorCircuit::new(SetOp::Add(element));
+///         z = mock::check_step(5, &sc, z).unwrap();
+///     }
+///     assert_eq!(z, [acc]);
+/// }
+///
+/// assert!("prove:5".parse::<SetOp>().is_err());
+///
+/// // Folded, adding 7 on both steps
+/// let z_out = prove_and_verify(&SetAccumulatorCircuit::new(SetOp::Add(7)), [C1Scalar::from(1)], 2);
+/// assert_eq!(z_out, [(s - C1Scalar::from(7)) * (s - C1Scalar::from(7))]);
+/// ```
+#[derive(Clone)]
+pub struct SetAccumulatorCircuit {
+    op: SetOp,
+}
+
+impl SetAccumulatorCircuit {
+    pub fn new(op: SetOp) -> Self {
+        Self { op }
+    }
+}
+
+impl<F: PrimeField> StepCircuit<1, F> for SetAccumulatorCircuit {
+    type Config = SetAccumulatorConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.selector(),
+            acc: cs.advice_column(),
+            element: cs.advice_column(),
+            acc_next: cs.advice_column(),
+            point: cs.fixed_column(),
+        };
+
+        cs.enable_equality(config.acc);
+        cs.enable_equality(config.acc_next);
+
+        cs.create_gate("set-accumulator", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let acc = meta.query_advice(config.acc, Rotation::cur());
+            let element = meta.query_advice(config.element, Rotation::cur());
+            let acc_next = meta.query_advice(config.acc_next, Rotation::cur());
+            let point = meta.query_fixed(config.point, Rotation::cur());
+
+            vec![s * (acc_next - acc * (point - element))]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; 1],
+    ) -> Result<[AssignedCell<F, F>; 1], SynthesisError> {
+        let element = F::from(self.op.element());
+
+        let z_out = layouter.assign_region(
+            || "set-accumulator",
+            |mut region| {
+                let [acc] = z_i;
+
+                config.s.enable(&mut region, 0)?;
+                region.assign_fixed(|| "point", config.point, 0, || Value::known(point::<F>()))?;
+
+                let acc = acc.copy_advice(|| "acc", &mut region, config.acc, 0)?;
+                region.assign_advice(|| "element", config.element, 0, || Value::known(element))?;
+
+                let acc_next = region.assign_advice(
+                    || "acc_next",
+                    config.acc_next,
+                    0,
+                    || acc.value().map(|acc| self.reference_step(&[*acc])[0]),
+                )?;
+
+                Ok([acc_next])
+            },
+        )?;
+
+        Ok(z_out)
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<1, F> for SetAccumulatorCircuit {
+    fn reference_step(&self, [acc]: &[F; 1]) -> [F; 1] {
+        [*acc * (point::<F>() - F::from(self.op.element()))]
+    }
+}
@@ -0,0 +1,165 @@
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Expression, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::{gadgets::RangeCheckConfig, ReferenceStep};
+
+/// Values are `u64`, so the distance between two sorted ones fits into 64 bits
+const DISTANCE_BITS: usize = 64;
+
+/// Configuration for the [`SortedPairCircuit`], the step takes its first row, the rest is the
+/// range check
+#[derive(Debug, Clone)]
+pub struct SortedPairConfig {
+    s: Selector,
+    /// Value of the previous step
+    prev: Column<Advice>,
+    /// Number of values so far
+    count: Column<Advice>,
+    /// Per-step value, a private witness of the step and the next `prev`
+    value: Column<Advice>,
+    /// `value - prev`, range-checked to prove `prev <= value`
+    distance: Column<Advice>,
+    /// `count + 1`
+    count_next: Column<Advice>,
+    range: RangeCheckConfig,
+}
+
+/// Circuit that takes a new value every step and proves it is not below the previous one:
+/// `[prev, count] -> [value, count + 1]`
+///
+/// `prev <= value` is proven by range-checking `value - prev` into 64 bits: an unsorted value
+/// wraps that distance around the field and the step is unsatisfiable. The folded IVC attests
+/// the whole stream is sorted, with its last value and length as `z_out`. `z_0[0]` must be a
+/// `u64` too, e.g. `0` or the smallest value allowed. Like the [`super::SumCircuit`], each step
+/// gets its own instance.
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     circuits::{ReferenceStep, SortedPairCircuit},
+/// #     mock,
+/// #     testing::prove_and_verify,
+/// # };
+/// let mut z = [C1Scalar::from(0); 2];
+/// for value in [2, 3, 3, 8] {
+///     let sc = SortedPairCircuit::new(value);
+///     mock::check_step(7, &sc, z).unwrap();
+///     z = sc.reference_step(&z);
+/// }
+/// assert_eq!(z, [8, 4].map(C1Scalar::from));
+///
+/// // 5 after 8 is unsorted
+/// mock::check_step(7, &SortedPairCircuit::new(5), z).unwrap_err();
+///
+/// let z_out = prove_and_verify(&SortedPairCircuit::new(5), [C1Scalar::from(0); 2], 3);
+/// assert_eq!(z_out, [5, 3].map(C1Scalar::from));
+/// ```
+#[derive(Clone)]
+pub struct SortedPairCircuit {
+    value: u64,
+}
+
+impl SortedPairCircuit {
+    pub fn new(value: u64) -> Self {
+        Self { value }
+    }
+}
+
+impl<F: PrimeField> StepCircuit<2, F> for SortedPairCircuit {
+    type Config = SortedPairConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.selector(),
+            prev: cs.advice_column(),
+            count: cs.advice_column(),
+            value: cs.advice_column(),
+            distance: cs.advice_column(),
+            count_next: cs.advice_column(),
+            range: RangeCheckConfig::configure(cs),
+        };
+
+        cs.enable_equality(config.prev);
+        cs.enable_equality(config.count);
+        cs.enable_equality(config.value);
+        cs.enable_equality(config.distance);
+        cs.enable_equality(config.count_next);
+
+        cs.create_gate("sorted-pair", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let prev = meta.query_advice(config.prev, Rotation::cur());
+            let count = meta.query_advice(config.count, Rotation::cur());
+            let value = meta.query_advice(config.value, Rotation::cur());
+            let distance = meta.query_advice(config.distance, Rotation::cur());
+            let count_next = meta.query_advice(config.count_next, Rotation::cur());
+
+            vec![
+                s.clone() * (distance - (value - prev)),
+                s * (count_next - (count + Expression::Constant(F::ONE))),
+            ]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; 2],
+    ) -> Result<[AssignedCell<F, F>; 2], SynthesisError> {
+        layouter.assign_region(
+            || "sorted-pair",
+            |mut region| {
+                let [prev, count] = z_i;
+
+                config.s.enable(&mut region, 0)?;
+
+                let prev = prev.copy_advice(|| "prev", &mut region, config.prev, 0)?;
+                let count = count.copy_advice(|| "count", &mut region, config.count, 0)?;
+                let value = region.assign_advice(
+                    || "value",
+                    config.value,
+                    0,
+                    || Value::known(F::from(self.value)),
+                )?;
+                let distance = region.assign_advice(
+                    || "distance",
+                    config.distance,
+                    0,
+                    || value.value().copied() - prev.value().copied(),
+                )?;
+                let count_next = region.assign_advice(
+                    || "count_next",
+                    config.count_next,
+                    0,
+                    || count.value().map(|count| *count + F::ONE),
+                )?;
+
+                config
+                    .range
+                    .assign(&mut region, 1, &distance, DISTANCE_BITS)?;
+
+                Ok([value, count_next])
+            },
+        )
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<2, F> for SortedPairCircuit {
+    /// The value and the length so far, the proof of an unsorted value fails instead
+    fn reference_step(&self, [_prev, count]: &[F; 2]) -> [F; 2] {
+        [F::from(self.value), *count + F::ONE]
+    }
+}
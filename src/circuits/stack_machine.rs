@@ -0,0 +1,296 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Expression, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::ReferenceStep;
+
+/// Instruction of one step of the [`StackMachineCircuit`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", content = "value", rename_all = "kebab-case")]
+pub enum StackOp {
+    /// Push the value on top of the stack
+    Push(u64),
+    /// Drop the top of the stack
+    Pop,
+    /// Replace the two topmost values with their sum
+    Add,
+}
+
+/// Parses `push:<value>`, `pop` or `add`
+impl FromStr for StackOp {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("push", value)) => value
+                .parse()
+                .map(Self::Push)
+                .map_err(|err| format!("value of `{s}`: {err}")),
+            None if s == "pop" => Ok(Self::Pop),
+            None if s == "add" => Ok(Self::Add),
+            _ => Err(format!(
+                "unknown stack instruction `{s}`, expected `push:<value>`, `pop` or `add`"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for StackOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Push(value) => write!(f, "push:{value}"),
+            Self::Pop => write!(f, "pop"),
+            Self::Add => write!(f, "add"),
+        }
+    }
+}
+
+/// Configuration for the [`StackMachineCircuit`], a single row holds the whole step
+#[derive(Debug, Clone)]
+pub struct StackMachineConfig<const K: usize> {
+    s: Selector,
+    /// Stack before the step, top first
+    stack: [Column<Advice>; K],
+    /// One-hot decoding of the instruction, private witnesses of the step
+    is_push: Column<Advice>,
+    is_pop: Column<Advice>,
+    is_add: Column<Advice>,
+    /// Value pushed, `0` for the other instructions
+    value: Column<Advice>,
+    stack_next: [Column<Advice>; K],
+}
+
+/// Circuit that runs a tiny stack machine, its `K`-element state being the stack, top first,
+/// and executes one [`StackOp`] of a program every step
+///
+/// The stack has a fixed depth of `K` slots, the empty ones holding `0`, so `z_0` is `[0; K]` for
+/// an empty stack. Pushing shifts the stack down and drops its bottom slot if it was full, popping
+/// shifts it up and fills the bottom slot with `0`, and adding pops the two topmost values and
+/// pushes their sum. Popping or adding on a stack that is too shallow reads the empty slots as
+/// `0`, there is no error state. The sum is in the field, with no overflow check.
+///
+/// The instruction is decoded into three boolean flags of which exactly one is set, and every
+/// slot of the next stack is constrained to the sum of the three transitions, each multiplied by
+/// its flag, so the one gate covers every instruction. The instruction and pushed value are
+/// private witnesses of the step; like the [`super::SumCircuit`], each step gets its own
+/// instance.
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     circuits::{ReferenceStep, StackMachineCircuit, StackOp},
+/// #     mock,
+/// #     testing::prove_and_verify,
+/// # };
+/// // (4 + 7) + (10 + 3), then push and drop 1
+/// let program = "push:4,push:7,add,push:10,push:3,add,add,push:1,pop"
+///     .split(',')
+///     .map(|op| op.parse::<StackOp>().unwrap())
+///     .collect::<Vec<_>>();
+///
+/// let mut z = [C1Scalar::from(0); 4];
+/// for op in &program {
+///     let sc = StackMachineCircuit::<4>::new(*op);
+///     mock::check_step(5, &sc, z).unwrap();
+///     z = sc.reference_step(&z);
+/// }
+///
+/// // On the CPU with an unbounded stack
+/// let mut stack = vec![];
+/// for op in &program {
+///     match op {
+///         StackOp::Push(value) => stack.push(*value),
+///         StackOp::Pop => {
+///             stack.pop();
+///         }
+///         StackOp::Add => {
+///             let sum = stack.pop().unwrap() + stack.pop().unwrap();
+///             stack.push(sum);
+///         }
+///     }
+/// }
+/// assert_eq!(stack, [24]);
+/// assert_eq!(z, [24, 0, 0, 0].map(C1Scalar::from));
+///
+/// // Pushing onto a full stack drops its bottom
+/// let z = [1, 2, 3, 4].map(C1Scalar::from);
+/// let sc = StackMachineCircuit::<4>::new(StackOp::Push(9));
+/// mock::check_step(5, &sc, z).unwrap();
+/// assert_eq!(sc.reference_step(&z), [9, 1, 2, 3].map(C1Scalar::from));
+///
+/// // Folded, pushing 5 on every step
+/// let sc = StackMachineCircuit::<4>::new(StackOp::Push(5));
+/// let z_out = prove_and_verify(&sc, [C1Scalar::from(0); 4], 3);
+/// assert_eq!(z_out, [5, 5, 5, 0].map(C1Scalar::from));
+/// ```
+#[derive(Clone)]
+pub struct StackMachineCircuit<const K: usize> {
+    op: StackOp,
+}
+
+impl<const K: usize> StackMachineCircuit<K> {
+    /// # Panics
+    /// If `K` is below `2`, the depth `add` needs
+    pub fn new(op: StackOp) -> Self {
+        assert!(K >= 2, "the stack must be at least 2 deep");
+        Self { op }
+    }
+
+    /// `stack` shifted down under `top`
+    fn pushed<T: Clone>(stack: &[T; K], top: T) -> [T; K] {
+        std::array::from_fn(|index| match index {
+            0 => top.clone(),
+            _ => stack[index - 1].clone(),
+        })
+    }
+
+    /// `stack` without its top, shifted up over `empty`
+    fn popped<T: Clone>(stack: &[T; K], empty: T) -> [T; K] {
+        std::array::from_fn(|index| stack.get(index + 1).unwrap_or(&empty).clone())
+    }
+}
+
+impl<const K: usize, F: PrimeField> StepCircuit<K, F> for StackMachineCircuit<K> {
+    type Config = StackMachineConfig<K>;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.selector(),
+            stack: [(); K].map(|_| cs.advice_column()),
+            is_push: cs.advice_column(),
+            is_pop: cs.advice_column(),
+            is_add: cs.advice_column(),
+            value: cs.advice_column(),
+            stack_next: [(); K].map(|_| cs.advice_column()),
+        };
+
+        for column in config.stack.iter().chain(&config.stack_next) {
+            cs.enable_equality(*column);
+        }
+
+        cs.create_gate("stack-machine", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let stack = config
+                .stack
+                .map(|column| meta.query_advice(column, Rotation::cur()));
+            let is_push = meta.query_advice(config.is_push, Rotation::cur());
+            let is_pop = meta.query_advice(config.is_pop, Rotation::cur());
+            let is_add = meta.query_advice(config.is_add, Rotation::cur());
+            let value = meta.query_advice(config.value, Rotation::cur());
+            let stack_next = config
+                .stack_next
+                .map(|column| meta.query_advice(column, Rotation::cur()));
+
+            let zero = Expression::Constant(F::ZERO);
+            let one = Expression::Constant(F::ONE);
+
+            let pushed = Self::pushed(&stack, value);
+            let popped = Self::popped(&stack, zero);
+            let mut added = popped.clone();
+            added[0] = stack[0].clone() + stack[1].clone();
+
+            let mut constraints = [&is_push, &is_pop, &is_add]
+                .into_iter()
+                .map(|flag| s.clone() * flag.clone() * (one.clone() - flag.clone()))
+                .collect::<Vec<_>>();
+            constraints.push(s.clone() * (is_push.clone() + is_pop.clone() + is_add.clone() - one));
+            for (index, next) in stack_next.into_iter().enumerate() {
+                constraints.push(
+                    s.clone()
+                        * (next
+                            - (is_push.clone() * pushed[index].clone()
+                                + is_pop.clone() * popped[index].clone()
+                                + is_add.clone() * added[index].clone())),
+                );
+            }
+            constraints
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; K],
+    ) -> Result<[AssignedCell<F, F>; K], SynthesisError> {
+        let (flags, value) = match self.op {
+            StackOp::Push(value) => ([1, 0, 0], value),
+            StackOp::Pop => ([0, 1, 0], 0),
+            StackOp::Add => ([0, 0, 1], 0),
+        };
+
+        layouter.assign_region(
+            || "stack-machine",
+            |mut region| {
+                config.s.enable(&mut region, 0)?;
+
+                let mut stack = Value::known(vec![]);
+                for (cell, column) in z_i.iter().zip(config.stack) {
+                    let cell = cell.copy_advice(|| "stack", &mut region, column, 0)?;
+                    stack = stack.zip(cell.value()).map(|(mut stack, value)| {
+                        stack.push(*value);
+                        stack
+                    });
+                }
+
+                for (column, flag) in [config.is_push, config.is_pop, config.is_add]
+                    .into_iter()
+                    .zip(flags)
+                {
+                    region.assign_advice(|| "flag", column, 0, || Value::known(F::from(flag)))?;
+                }
+                region.assign_advice(
+                    || "value",
+                    config.value,
+                    0,
+                    || Value::known(F::from(value)),
+                )?;
+
+                let stack_next = stack.map(|stack| {
+                    let stack: [F; K] = stack.try_into().expect("one cell per stack slot");
+                    self.reference_step(&stack)
+                });
+                let mut cells = vec![];
+                for (index, column) in config.stack_next.into_iter().enumerate() {
+                    cells.push(region.assign_advice(
+                        || "stack_next",
+                        column,
+                        0,
+                        || stack_next.map(|stack| stack[index]),
+                    )?);
+                }
+
+                Ok(cells.try_into().expect("one cell per stack slot"))
+            },
+        )
+    }
+}
+
+impl<const K: usize, F: PrimeField> ReferenceStep<K, F> for StackMachineCircuit<K> {
+    fn reference_step(&self, stack: &[F; K]) -> [F; K] {
+        match self.op {
+            StackOp::Push(value) => Self::pushed(stack, F::from(value)),
+            StackOp::Pop => Self::popped(stack, F::ZERO),
+            StackOp::Add => {
+                let mut added = Self::popped(stack, F::ZERO);
+                added[0] = stack[0] + stack[1];
+                added
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::ReferenceStep;
+
+/// Configuration for the [`SumCircuit`], a single row holds the whole step
+#[derive(Debug, Clone)]
+pub struct SumConfig {
+    s: Selector,
+    z_in: Column<Advice>,
+    /// Per-step input, a private witness of the step
+    addend: Column<Advice>,
+    z_out: Column<Advice>,
+}
+
+/// Circuit that adds a per-step addend to a running total: `z_out = z_in + addend`
+///
+/// Unlike the other circuits, each step needs different data, so each step gets its own instance,
+/// see `run_ivc_per_step`
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     circuits::{ReferenceStep, SumCircuit},
+/// #     mock,
+/// #     testing::prove_and_verify,
+/// # };
+/// let mut z = [C1Scalar::from(1)];
+/// for addend in [3, 4, 5] {
+///     let sc = SumCircuit::new(addend);
+///     mock::check_step(5, &sc, z).unwrap();
+///     z = sc.reference_step(&z);
+/// }
+/// assert_eq!(z, [C1Scalar::from(1 + 3 + 4 + 5)]);
+///
+/// let z_out = prove_and_verify(&SumCircuit::new(2), [C1Scalar::from(0)], 3);
+/// assert_eq!(z_out, [C1Scalar::from(6)]);
+/// ```
+#[derive(Clone)]
+pub struct SumCircuit {
+    addend: u64,
+}
+
+impl SumCircuit {
+    pub fn new(addend: u64) -> Self {
+        Self { addend }
+    }
+}
+
+impl<F: PrimeField> StepCircuit<1, F> for SumCircuit {
+    type Config = SumConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.selector(),
+            z_in: cs.advice_column(),
+            addend: cs.advice_column(),
+            z_out: cs.advice_column(),
+        };
+
+        cs.enable_equality(config.z_in);
+        cs.enable_equality(config.z_out);
+
+        cs.create_gate("sum", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let z_in = meta.query_advice(config.z_in, Rotation::cur());
+            let addend = meta.query_advice(config.addend, Rotation::cur());
+            let z_out = meta.query_advice(config.z_out, Rotation::cur());
+
+            vec![s * (z_in + addend - z_out)]
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; 1],
+    ) -> Result<[AssignedCell<F, F>; 1], SynthesisError> {
+        let addend = Value::known(F::from(self.addend));
+
+        let z_out = layouter.assign_region(
+            || "sum",
+            |mut region| {
+                let [z] = z_i;
+
+                config.s.enable(&mut region, 0)?;
+
+                let z_in = z.copy_advice(|| "z_in", &mut region, config.z_in, 0)?;
+                region.assign_advice(|| "addend", config.addend, 0, || addend)?;
+                region.assign_advice(
+                    || "z_out",
+                    config.z_out,
+                    0,
+                    || z_in.value().copied() + addend,
+                )
+            },
+        )?;
+
+        Ok([z_out])
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<1, F> for SumCircuit {
+    fn reference_step(&self, [z]: &[F; 1]) -> [F; 1] {
+        [*z + F::from(self.addend)]
+    }
+}
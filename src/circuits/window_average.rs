@@ -0,0 +1,320 @@
+use sirius::{
+    halo2_proofs::{
+        circuit::Value,
+        plonk::{Advice, Column, Expression, Fixed, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{PrimeField, StepCircuit},
+};
+
+use super::{
+    gadgets::{self, RangeCheckConfig},
+    ReferenceStep, AVERAGE_SCALE, AVERAGE_SCALE_BITS,
+};
+
+/// Bits of the range-checked input values
+const VALUE_BITS: usize = 32;
+
+/// A weighted mean is at most the largest value, so its scaled form fits into this many bits
+const AVERAGE_BITS: usize = VALUE_BITS + AVERAGE_SCALE_BITS as usize;
+
+/// The weights are `u32`, so their total and the gap between it and the remainder fit a `u64`
+const GAP_BITS: usize = 64;
+
+/// Configuration for the [`WindowAverageCircuit`], the step takes its first row, the rest are
+/// the range checks
+#[derive(Debug, Clone)]
+pub struct WindowAverageConfig<const W: usize> {
+    s: Selector,
+    /// Window before the step, oldest value first
+    window: [Column<Advice>; W],
+    /// Per-step value shifted in, a private witness of the step
+    value: Column<Advice>,
+    /// Window after the step: `window[1..]` then `value`
+    window_next: [Column<Advice>; W],
+    /// Weight of each window position, fixed
+    weights: [Column<Fixed>; W],
+    /// Fixed-point weighted average of `window_next`, range-checked into [`AVERAGE_BITS`]
+    average: Column<Advice>,
+    /// Remainder of the scaled division by the total weight
+    rem: Column<Advice>,
+    /// `total - 1 - rem`, range-checked to prove `rem < total`
+    gap: Column<Advice>,
+    range: RangeCheckConfig,
+}
+
+/// Circuit that keeps the last `W` inputs as its whole state, shifting in a new value every step,
+/// and proves their fixed-weight moving average: `[x_1, .., x_W] -> [x_2, .., x_W, value]`
+///
+/// The weights, one per window position from the oldest, are given once and are the same on every
+/// step, so they are fixed columns like those of the [`super::DenseLayerCircuit`]. The step
+/// constrains the shift, then proves the fixed-point average `a` of the new window the way the
+/// [`super::AverageCircuit`] does, by multiplication: `sum(w_i x_i) * AVERAGE_SCALE = a * total
+/// + rem` with `total = sum(w_i)`, range-checking `a` and `total - 1 - rem`, so that
+/// `a = floor(sum(w_i x_i) * AVERAGE_SCALE / total)`, see [`WindowAverageCircuit::average`]. The
+/// average is not part of the state, which is the exact window, so it can be recomputed from any
+/// `z_out`. The shifted-in values are range-checked to `u32`. `z_0` is `[0; W]` for an empty
+/// window, whose first `W - 1` averages count the zeros. Like the [`super::SumCircuit`], each
+/// step gets its own instance.
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     circuits::{ReferenceStep, WindowAverageCircuit, AVERAGE_SCALE},
+/// #     error::ExampleError,
+/// #     mock,
+/// # };
+/// // A ramp through a window of 4 weighting the newest value most
+/// let weights = [1, 2, 3, 4];
+/// let ramp = 1..=10u64;
+///
+/// let mut z = [C1Scalar::from(0); 4];
+/// let mut window = vec![0; 4];
+/// for value in ramp {
+///     let sc = WindowAverageCircuit::new(value, weights);
+///     mock::check_step(8, &sc, z).unwrap();
+///     z = sc.reference_step(&z);
+///
+///     // On the CPU in floating point
+///     window.remove(0);
+///     window.push(value);
+///     let weighted = window.iter().zip(weights).map(|(x, w)| (x * w as u64) as f64);
+///     let expected = weighted.sum::<f64>() / weights.iter().sum::<u32>() as f64;
+///
+///     let average = sc.average(&z).unwrap() as f64 / AVERAGE_SCALE as f64;
+///     assert!((average - expected).abs() < 1.0 / AVERAGE_SCALE as f64);
+/// }
+/// assert_eq!(z, [7, 8, 9, 10].map(C1Scalar::from));
+///
+/// // On a ramp of slope 1 the weighted mean lags the newest value by `sum(w_i (W - i)) / total`
+/// let sc = WindowAverageCircuit::new(11, weights);
+/// assert_eq!(sc.average(&z), Some(9 * AVERAGE_SCALE as u128));
+///
+/// // Values are `u32`
+/// let err = mock::check_step(8, &WindowAverageCircuit::new(1 << 32, weights), z).unwrap_err();
+/// assert!(matches!(err, ExampleError::MockProver(_)), "{err}");
+/// ```
+#[derive(Clone)]
+pub struct WindowAverageCircuit<const W: usize> {
+    value: u64,
+    weights: [u32; W],
+}
+
+impl<const W: usize> WindowAverageCircuit<W> {
+    /// # Panics
+    /// If the weights are all zero
+    pub fn new(value: u64, weights: [u32; W]) -> Self {
+        assert!(
+            weights.iter().any(|weight| *weight > 0),
+            "the window weights must not all be zero"
+        );
+        Self { value, weights }
+    }
+
+    fn total(&self) -> u128 {
+        self.weights.iter().map(|weight| *weight as u128).sum()
+    }
+
+    /// Fixed-point weighted average of `window` the step proves, with [`AVERAGE_SCALE`] as its
+    /// scale: `floor(sum(w_i x_i) * AVERAGE_SCALE / sum(w_i))`
+    ///
+    /// `None` if the weighted sum overflows
+    pub fn average<F: PrimeField>(&self, window: &[F]) -> Option<u128> {
+        Some(self.scaled_sum(window)? / self.total())
+    }
+
+    /// `sum(w_i x_i) * AVERAGE_SCALE`, `None` on overflow
+    fn scaled_sum<F: PrimeField>(&self, window: &[F]) -> Option<u128> {
+        let mut weighted = 0u128;
+        for (value, weight) in window.iter().zip(self.weights) {
+            weighted =
+                weighted.checked_add(gadgets::to_u128(value)?.checked_mul(weight as u128)?)?;
+        }
+        weighted.checked_mul(AVERAGE_SCALE as u128)
+    }
+
+    /// `(average, rem, gap)` of the new window, zeros if it overflows, which the range checks
+    /// then reject
+    fn division<F: PrimeField>(&self, window: &[F]) -> (u128, u128, u128) {
+        let total = self.total();
+        self.scaled_sum(window).map_or((0, 0, 0), |scaled| {
+            let rem = scaled % total;
+            (scaled / total, rem, total - 1 - rem)
+        })
+    }
+
+    /// `window` without its oldest value, then `value`
+    fn shifted<T: Clone>(window: &[T; W], value: T) -> [T; W] {
+        std::array::from_fn(|index| window.get(index + 1).unwrap_or(&value).clone())
+    }
+}
+
+impl<const W: usize, F: PrimeField> StepCircuit<W, F> for WindowAverageCircuit<W> {
+    type Config = WindowAverageConfig<W>;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        let config = Self::Config {
+            s: cs.selector(),
+            window: [(); W].map(|_| cs.advice_column()),
+            value: cs.advice_column(),
+            window_next: [(); W].map(|_| cs.advice_column()),
+            weights: [(); W].map(|_| cs.fixed_column()),
+            average: cs.advice_column(),
+            rem: cs.advice_column(),
+            gap: cs.advice_column(),
+            range: RangeCheckConfig::configure(cs),
+        };
+
+        for column in config.window.iter().chain(&config.window_next) {
+            cs.enable_equality(*column);
+        }
+        cs.enable_equality(config.value);
+        cs.enable_equality(config.average);
+        cs.enable_equality(config.gap);
+
+        cs.create_gate("window-average", |meta| {
+            let s = meta.query_selector(config.s);
+
+            let window = config
+                .window
+                .map(|column| meta.query_advice(column, Rotation::cur()));
+            let value = meta.query_advice(config.value, Rotation::cur());
+            let window_next = config
+                .window_next
+                .map(|column| meta.query_advice(column, Rotation::cur()));
+            let weights = config
+                .weights
+                .map(|column| meta.query_fixed(column, Rotation::cur()));
+            let average = meta.query_advice(config.average, Rotation::cur());
+            let rem = meta.query_advice(config.rem, Rotation::cur());
+            let gap = meta.query_advice(config.gap, Rotation::cur());
+
+            let zero = Expression::Constant(F::ZERO);
+            let one = Expression::Constant(F::ONE);
+            let total = weights
+                .iter()
+                .fold(zero.clone(), |total, weight| total + weight.clone());
+            let weighted = weights
+                .iter()
+                .zip(&window_next)
+                .fold(zero, |sum, (weight, value)| {
+                    sum + weight.clone() * value.clone()
+                });
+
+            let mut constraints = Self::shifted(&window, value)
+                .into_iter()
+                .zip(window_next)
+                .map(|(shifted, next)| s.clone() * (next - shifted))
+                .collect::<Vec<_>>();
+            constraints.push(
+                s.clone()
+                    * (weighted * F::from(AVERAGE_SCALE) - (average * total.clone() + rem.clone())),
+            );
+            constraints.push(s * (gap - (total - one - rem)));
+            constraints
+        });
+
+        config
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; W],
+    ) -> Result<[AssignedCell<F, F>; W], SynthesisError> {
+        layouter.assign_region(
+            || "window-average",
+            |mut region| {
+                config.s.enable(&mut region, 0)?;
+
+                for (column, weight) in config.weights.iter().zip(self.weights) {
+                    region.assign_fixed(
+                        || "weight",
+                        *column,
+                        0,
+                        || Value::known(F::from(weight as u64)),
+                    )?;
+                }
+
+                let mut window = vec![];
+                for (cell, column) in z_i.iter().zip(config.window) {
+                    window.push(cell.copy_advice(|| "window", &mut region, column, 0)?);
+                }
+                let value = region.assign_advice(
+                    || "value",
+                    config.value,
+                    0,
+                    || Value::known(F::from(self.value)),
+                )?;
+
+                let mut window_next = vec![];
+                for (cell, column) in window
+                    .iter()
+                    .skip(1)
+                    .chain([&value])
+                    .zip(config.window_next)
+                {
+                    window_next.push(region.assign_advice(
+                        || "window_next",
+                        column,
+                        0,
+                        || cell.value().copied(),
+                    )?);
+                }
+                let window_next: [_; W] =
+                    window_next.try_into().expect("one cell per window column");
+
+                let mut values = Value::known(vec![]);
+                for cell in &window_next {
+                    values = values.zip(cell.value()).map(|(mut values, value)| {
+                        values.push(*value);
+                        values
+                    });
+                }
+                let division = values.map(|values| self.division(&values));
+
+                let average = region.assign_advice(
+                    || "average",
+                    config.average,
+                    0,
+                    || division.map(|(average, _, _)| F::from_u128(average)),
+                )?;
+                region.assign_advice(
+                    || "rem",
+                    config.rem,
+                    0,
+                    || division.map(|(_, rem, _)| F::from_u128(rem)),
+                )?;
+                let gap = region.assign_advice(
+                    || "gap",
+                    config.gap,
+                    0,
+                    || division.map(|(_, _, gap)| F::from_u128(gap)),
+                )?;
+
+                let mut offset = 1;
+                for (cell, bits) in [
+                    (&value, VALUE_BITS),
+                    (&average, AVERAGE_BITS),
+                    (&gap, GAP_BITS),
+                ] {
+                    config.range.assign(&mut region, offset, cell, bits)?;
+                    offset += RangeCheckConfig::rows(bits);
+                }
+
+                Ok(window_next)
+            },
+        )
+    }
+}
+
+impl<const W: usize, F: PrimeField> ReferenceStep<W, F> for WindowAverageCircuit<W> {
+    fn reference_step(&self, window: &[F; W]) -> [F; W] {
+        Self::shifted(window, F::from(self.value))
+    }
+}
@@ -0,0 +1,117 @@
+//! Running the same workload on every supported curve cycle
+
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    config::RunConfig, curve::Cycle, error::ExampleError, field, progress::StepEvent, run_circuit,
+    CircuitSpec,
+};
+
+/// Outcome of the run on one cycle
+#[derive(Debug)]
+pub struct CycleTrial {
+    pub cycle: Cycle,
+    /// Time from the start of the setup to the created IVC, `None` if the setup didn't finish
+    pub setup: Option<Duration>,
+    /// Time from the created IVC to the last folded step, `None` if the setup didn't finish
+    pub fold: Option<Duration>,
+    /// Hex of every `z_out` element
+    pub result: Result<Vec<String>, ExampleError>,
+}
+
+/// Every cycle with its trial, in the order of [`Cycle::ALL`]
+pub struct CycleReport {
+    pub trials: Vec<CycleTrial>,
+}
+
+impl CycleReport {
+    /// Whether every cycle succeeded with the same `z_out`
+    ///
+    /// The fields of the cycles differ, so this only holds while the computation doesn't wrap
+    /// around either modulus
+    pub fn z_out_agrees(&self) -> bool {
+        let mut outputs = self.trials.iter().map(|trial| trial.result.as_ref().ok());
+        match outputs.next() {
+            Some(Some(first)) => outputs.all(|z_out| z_out == Some(first)),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for CycleReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let millis = |duration: Option<Duration>| {
+            duration.map_or_else(
+                || "-".to_owned(),
+                |duration| duration.as_millis().to_string(),
+            )
+        };
+
+        writeln!(
+            f,
+            "{:>15} | {:>5} | {:>10} | {:>10} | {:>10}",
+            "cycle", "works", "setup_ms", "fold_ms", "proof_size"
+        )?;
+        for trial in &self.trials {
+            // sirius v0.1.1 can't serialize a folded IVC, so there is no proof size to report
+            writeln!(
+                f,
+                "{:>15} | {:>5} | {:>10} | {:>10} | {:>10}",
+                trial.cycle.name(),
+                trial.result.is_ok(),
+                millis(trial.setup),
+                millis(trial.fold),
+                "-"
+            )?;
+        }
+
+        if self.z_out_agrees() {
+            writeln!(f, "z_out agrees across cycles")
+        } else {
+            writeln!(f, "z_out differs across cycles, or a cycle failed")
+        }
+    }
+}
+
+/// Run `circuit` with `config` on each of [`Cycle::ALL`]
+///
+/// The harness folds on the cycle of `sirius::prelude::bn256`, the only one sirius v0.1.1 has
+/// default public parameters for, so the report has a single row until another cycle is added.
+pub fn compare_curves(circuit: &CircuitSpec, config: &RunConfig) -> CycleReport {
+    let trials = Cycle::ALL
+        .into_iter()
+        .map(|cycle| {
+            let mut setup_start = None;
+            let mut setup = None;
+            let mut fold_start = None;
+            let mut fold = None;
+            let mut on_event = |event: StepEvent| match event {
+                StepEvent::SetupStarted => setup_start = Some(Instant::now()),
+                StepEvent::SetupFinished => {
+                    setup = setup_start.map(|start| start.elapsed());
+                    fold_start = Some(Instant::now());
+                }
+                StepEvent::StepFolded { .. } => fold = fold_start.map(|start| start.elapsed()),
+                StepEvent::FoldingFinished { .. } => {}
+            };
+
+            let result = match cycle {
+                Cycle::Bn256Grumpkin => run_circuit(circuit, config, Some(&mut on_event)),
+            }
+            .map(|summary| summary.z_out.iter().map(field::to_hex).collect());
+
+            CycleTrial {
+                cycle,
+                setup,
+                // Only the base step, folded during the setup
+                fold: fold.or(setup.map(|_| Duration::ZERO)),
+                result,
+            }
+        })
+        .collect();
+
+    CycleReport { trials }
+}
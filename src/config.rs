@@ -0,0 +1,561 @@
+//! Run parameters of the example and their validation
+
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use sirius::{
+    ff::Field,
+    prelude::bn256::{C1Scalar, C2Scalar},
+};
+
+use crate::{
+    cache,
+    error::ExampleError,
+    field::{self, FieldFormat},
+    secondary::SecondaryCircuit,
+};
+
+/// Sirius needs at least this table size for its service (folding) part of the step circuit
+pub const MIN_CIRCUIT_TABLE_SIZE: usize = 17;
+
+/// Commitment keys above this size take a lot of memory and setup time, most likely a typo
+pub const MAX_COMMITMENT_KEY_SIZE: usize = 28;
+
+/// Everything that parametrizes a single prove-and-verify run
+///
+/// Can be read from a TOML file, where missing fields take their default value and field elements
+/// are decimal or `0x`-prefixed hex strings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RunConfig {
+    /// Number of folding steps, including the base step made by `IVC::new`
+    pub fold_step_count: usize,
+    /// Number of Fibonacci elements proven by each step
+    pub elements_num: usize,
+    pub primary_commitment_key_size: usize,
+    pub primary_circuit_table_size: usize,
+    /// Input to be passed on the zero step to the primary circuit, must have `A1` elements
+    #[serde(with = "field::serde_vec")]
+    pub primary_z_0: Vec<C1Scalar>,
+    pub secondary_commitment_key_size: usize,
+    pub secondary_circuit_table_size: usize,
+    /// Input to be passed on the zero step to the secondary circuit, must have `A2` elements
+    #[serde(with = "field::serde_vec")]
+    pub secondary_z_0: Vec<C2Scalar>,
+    /// Step circuit folded on the secondary curve
+    pub secondary_circuit: SecondaryCircuit,
+    /// Folder with the cached commitment keys
+    pub cache_dir: PathBuf,
+    /// How many times to retry a transient cache IO failure
+    pub cache_retries: u32,
+    /// Stop folding early once a step leaves the state unchanged
+    ///
+    /// ```
+    /// # use sirius::prelude::bn256::C1Scalar;
+    /// # use sirius_fibonacci_example::{
+    /// #     config::RunConfig, run_circuit, testing::test_cache_dir, CircuitSpec,
+    /// # };
+    /// // 0, 4, 8, 10, 10, ...
+    /// let circuit = CircuitSpec::SaturatingSum { addends: vec![4], cap: 10 };
+    /// let config = RunConfig {
+    ///     fold_step_count: 6,
+    ///     primary_z_0: vec![C1Scalar::from(0)],
+    ///     halt_on_fixpoint: true,
+    ///     cache_dir: test_cache_dir(),
+    ///     ..RunConfig::default()
+    /// };
+    ///
+    /// let summary = run_circuit(&circuit, &config, None).unwrap();
+    /// // The step from 10 to 10 is not folded
+    /// assert_eq!(summary.steps, 3);
+    /// assert_eq!(summary.z_out, [C1Scalar::from(10)]);
+    /// assert!(summary.verification.unwrap().all_passed());
+    ///
+    /// let config = RunConfig { halt_on_fixpoint: false, ..config };
+    /// let summary = run_circuit(&circuit, &config, None).unwrap();
+    /// assert_eq!(summary.steps, config.fold_step_count);
+    /// assert_eq!(summary.z_out, [C1Scalar::from(10)]);
+    /// ```
+    pub halt_on_fixpoint: bool,
+    /// Print a summary of the IVC structure once it is created
+    pub describe_ivc: bool,
+    /// Stop folding after the step that exceeded this many seconds of folding, the partial IVC is
+    /// still verified
+    ///
+    /// ```
+    /// # use std::{thread, time::Duration};
+    /// # use sirius::prelude::bn256::C1Scalar;
+    /// # use sirius_fibonacci_example::{
+    /// #     config::RunConfig, progress::StepEvent, run_circuit, CircuitSpec,
+    /// # };
+    /// let cache = tempfile::tempdir().unwrap();
+    /// let config = RunConfig {
+    ///     fold_step_count: 5,
+    ///     max_time: Some(0.05),
+    ///     cache_dir: cache.path().to_path_buf(),
+    ///     ..RunConfig::default()
+    /// };
+    ///
+    /// // The first fold goes over the budget, whatever the speed of the machine
+    /// let mut slow_first_fold = |event: StepEvent| {
+    ///     if event == (StepEvent::StepFolded { step: 1, steps: 5 }) {
+    ///         thread::sleep(Duration::from_millis(100));
+    ///     }
+    /// };
+    /// let summary = run_circuit(&CircuitSpec::Fibonacci, &config, Some(&mut slow_first_fold)).unwrap();
+    /// // Not strict: the base step and the one fold are kept, and verified
+    /// assert_eq!(summary.steps, 2);
+    /// assert_eq!(summary.z_out, [C1Scalar::from(6765), C1Scalar::from(10946)]);
+    /// let verification = summary.verification.unwrap();
+    /// assert!(verification.all_passed(), "{verification}");
+    /// ```
+    pub max_time: Option<f64>,
+    /// Fold every step but skip the final verification, the IVC stays unverified
+    pub no_verify: bool,
+    /// How the field elements of the run are printed
+    pub field_format: FieldFormat,
+    /// On a failed fold step or verification, collect a [`crate::diagnostic::FailureDiagnostic`]
+    /// into the error
+    pub keep_going_on_verify_fail: bool,
+    /// Number of the latest states a [`crate::runner::FoldRunner`] keeps a checkpoint of, to
+    /// [`crate::runner::FoldRunner::rewind`] to, `0` keeps none
+    pub checkpoints: usize,
+}
+
+impl Default for RunConfig {
+    fn default() -> Self {
+        Self {
+            fold_step_count: crate::FOLD_STEP_COUNT,
+            elements_num: crate::ELEMENTS_NUM,
+            primary_commitment_key_size: crate::PRIMARY_COMMITMENT_KEY_SIZE,
+            primary_circuit_table_size: crate::PRIMARY_CIRCUIT_TABLE_SIZE,
+            primary_z_0: vec![C1Scalar::ZERO, C1Scalar::ONE],
+            secondary_commitment_key_size: crate::SECONDARY_COMMITMENT_KEY_SIZE,
+            secondary_circuit_table_size: crate::SECONDARY_CIRCUIT_TABLE_SIZE,
+            secondary_z_0: crate::SECONDARY_Z_0.to_vec(),
+            secondary_circuit: SecondaryCircuit::Trivial,
+            cache_dir: PathBuf::from(".cache"),
+            cache_retries: 3,
+            halt_on_fixpoint: false,
+            describe_ivc: false,
+            max_time: None,
+            no_verify: false,
+            field_format: FieldFormat::Auto,
+            keep_going_on_verify_fail: false,
+            checkpoints: 0,
+        }
+    }
+}
+
+/// Outcome of one validation check
+#[derive(Debug)]
+pub struct Check {
+    pub name: &'static str,
+    pub result: Result<(), String>,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.result {
+            Ok(()) => write!(f, "[pass] {}", self.name),
+            Err(reason) => write!(f, "[FAIL] {}: {reason}", self.name),
+        }
+    }
+}
+
+pub(crate) fn check(name: &'static str, ok: bool, reason: impl FnOnce() -> String) -> Check {
+    Check {
+        name,
+        result: if ok { Ok(()) } else { Err(reason()) },
+    }
+}
+
+impl RunConfig {
+    /// Read the config from a TOML file
+    ///
+    /// An unknown key, e.g. a misspelled field, is an error rather than silently ignored:
+    ///
+    /// ```
+    /// # use sirius_fibonacci_example::{config::RunConfig, error::ExampleError};
+    /// let dir = tempfile::tempdir().unwrap();
+    /// let path = dir.path().join("config.toml");
+    /// std::fs::write(&path, "fold_step_count = 3\nelement_num = 4\n").unwrap();
+    ///
+    /// let err = RunConfig::from_toml_file(&path).unwrap_err();
+    /// assert!(matches!(err, ExampleError::ConfigFile { .. }), "{err}");
+    /// assert!(err.to_string().contains("element_num"), "{err}");
+    /// ```
+    pub fn from_toml_file(path: &Path) -> Result<Self, ExampleError> {
+        let config_file_err = |err: String| ExampleError::ConfigFile {
+            path: path.to_path_buf(),
+            err,
+        };
+
+        let content = fs::read_to_string(path).map_err(|err| config_file_err(err.to_string()))?;
+        toml::from_str(&content).map_err(|err| config_file_err(err.to_string()))
+    }
+
+    /// The config as TOML, [`RunConfig::from_toml_file`] reads the same config back
+    ///
+    /// ```
+    /// # use sirius::prelude::bn256::C1Scalar;
+    /// # use sirius_fibonacci_example::{
+    /// #     config::RunConfig, field::FieldFormat, secondary::SecondaryCircuit,
+    /// # };
+    /// let dir = tempfile::tempdir().unwrap();
+    /// let path = dir.path().join("config.toml");
+    ///
+    /// let custom = RunConfig {
+    ///     primary_z_0: vec![-C1Scalar::from(1), C1Scalar::from(u64::MAX)],
+    ///     secondary_circuit: SecondaryCircuit::Counter,
+    ///     max_time: Some(1.5),
+    ///     field_format: FieldFormat::Montgomery,
+    ///     ..RunConfig::default()
+    /// };
+    /// for config in [RunConfig::default(), custom] {
+    ///     std::fs::write(&path, config.to_toml()).unwrap();
+    ///     assert_eq!(RunConfig::from_toml_file(&path).unwrap(), config);
+    /// }
+    ///
+    /// // So does the commented default config
+    /// std::fs::write(&path, RunConfig::schema()).unwrap();
+    /// assert_eq!(RunConfig::from_toml_file(&path).unwrap(), RunConfig::default());
+    /// ```
+    pub fn to_toml(&self) -> String {
+        toml::to_string_pretty(self).expect("config is always representable in TOML")
+    }
+
+    /// Example config file with every field at its default, each commented with its meaning and
+    /// allowed range
+    ///
+    /// Fields and defaults come from serializing [`RunConfig::default`], so they can't drift from
+    /// the struct, only the comments come from [`field_docs`]. Unset optional fields are left
+    /// commented out, so the output parses back into the default config.
+    pub fn schema() -> String {
+        let toml::Value::Table(mut defaults) =
+            toml::Value::try_from(Self::default()).expect("config is always representable in TOML")
+        else {
+            unreachable!("a struct is serialized as a table")
+        };
+
+        let mut schema = String::new();
+        for (name, doc) in field_docs() {
+            for line in doc.lines() {
+                schema.push_str(&format!("# {line}\n"));
+            }
+            match defaults.remove(name) {
+                Some(value) => schema.push_str(&format!("{name} = {value}\n\n")),
+                None => schema.push_str(&format!("# {name} =\n\n")),
+            }
+        }
+        // Fields added to the struct, but not yet to `field_docs`
+        for (name, value) in defaults {
+            schema.push_str(&format!("{name} = {value}\n\n"));
+        }
+
+        schema
+    }
+
+    /// Run every check, without doing any setup or proving, nor writing to the filesystem
+    ///
+    /// `primary_arity` & `secondary_arity` are the arities of the circuits this config will be
+    /// used with. The secondary side follows [`crate::A2`], a `secondary_z_0` of another length
+    /// is rejected before any setup:
+    ///
+    /// ```
+    /// # use sirius::{ff::Field, prelude::bn256::C2Scalar};
+    /// # use sirius_fibonacci_example::{
+    /// #     config::RunConfig, error::ExampleError, run_circuit, testing::test_cache_dir,
+    /// #     CircuitSpec, A1, A2,
+    /// # };
+    /// let config = RunConfig {
+    ///     cache_dir: test_cache_dir(),
+    ///     ..RunConfig::default()
+    /// };
+    /// assert_eq!(config.secondary_z_0.len(), A2);
+    /// assert!(config.validate(A1, A2).iter().all(|check| check.result.is_ok()));
+    ///
+    /// let mismatched = RunConfig {
+    ///     secondary_z_0: vec![C2Scalar::ZERO; A2 + 1],
+    ///     ..config
+    /// };
+    /// let failed = mismatched
+    ///     .validate(A1, A2)
+    ///     .into_iter()
+    ///     .filter(|check| check.result.is_err())
+    ///     .map(|check| check.name)
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(failed, ["secondary z_0 length"]);
+    ///
+    /// let err = run_circuit(&CircuitSpec::Fibonacci, &mismatched, None).err().unwrap();
+    /// assert!(matches!(err, ExampleError::InvalidConfig(_)), "{err}");
+    /// ```
+    pub fn validate(&self, primary_arity: usize, secondary_arity: usize) -> Vec<Check> {
+        let mut checks = self.parameter_checks(primary_arity, secondary_arity);
+        checks.push(Check {
+            name: "cache dir writable",
+            result: cache::check_writable(&self.cache_dir)
+                .map_err(|err| format!("{:?}: {err}", self.cache_dir)),
+        });
+        checks
+    }
+
+    /// Every check of [`RunConfig::validate`] but the cache dir one
+    fn parameter_checks(&self, primary_arity: usize, secondary_arity: usize) -> Vec<Check> {
+        vec![
+            check("fold step count", self.fold_step_count >= 1, || {
+                "at least one step is required".to_owned()
+            }),
+            check("elements num", self.elements_num >= 1, || {
+                "a step must prove at least one element, an empty block enforces no gate".to_owned()
+            }),
+            check(
+                "primary table size",
+                self.primary_circuit_table_size >= MIN_CIRCUIT_TABLE_SIZE,
+                || {
+                    format!(
+                        "{} is less than the minimum {MIN_CIRCUIT_TABLE_SIZE}",
+                        self.primary_circuit_table_size
+                    )
+                },
+            ),
+            check(
+                "secondary table size",
+                self.secondary_circuit_table_size >= MIN_CIRCUIT_TABLE_SIZE,
+                || {
+                    format!(
+                        "{} is less than the minimum {MIN_CIRCUIT_TABLE_SIZE}",
+                        self.secondary_circuit_table_size
+                    )
+                },
+            ),
+            check(
+                "primary z_0 length",
+                self.primary_z_0.len() == primary_arity,
+                || {
+                    format!(
+                        "got {} elements, but the primary arity is {primary_arity}",
+                        self.primary_z_0.len()
+                    )
+                },
+            ),
+            check(
+                "secondary circuit arity",
+                self.secondary_circuit.arity() == secondary_arity,
+                || {
+                    format!(
+                        "the {} circuit has arity {}, but the secondary arity is \
+                         {secondary_arity}",
+                        self.secondary_circuit,
+                        self.secondary_circuit.arity()
+                    )
+                },
+            ),
+            check(
+                "secondary z_0 length",
+                self.secondary_z_0.len() == secondary_arity,
+                || {
+                    format!(
+                        "got {} elements, but the secondary arity is {secondary_arity}",
+                        self.secondary_z_0.len()
+                    )
+                },
+            ),
+            check(
+                "max time",
+                self.max_time
+                    .map_or(true, |max| max.is_finite() && max > 0.0),
+                || format!("{:?} is not a positive number of seconds", self.max_time),
+            ),
+            check_key_size(
+                "primary commitment key size",
+                self.primary_commitment_key_size,
+                self.primary_circuit_table_size,
+            ),
+            check_key_size(
+                "secondary commitment key size",
+                self.secondary_commitment_key_size,
+                self.secondary_circuit_table_size,
+            ),
+        ]
+    }
+
+    /// Same as [`RunConfig::validate`], but returns the first failed check as an error
+    ///
+    /// An unwritable cache dir is reported first, as [`ExampleError::CacheDirNotWritable`]: the
+    /// key setup would otherwise fail on it only after the expensive part. Unlike `validate`, which
+    /// only reads its metadata, this probes the cache dir by creating and removing a file in it,
+    /// or in its nearest existing ancestor, as a missing one is not created:
+    ///
+    /// ```
+    /// # use sirius_fibonacci_example::{config::RunConfig, error::ExampleError, A1, A2};
+    /// let dir = tempfile::tempdir().unwrap();
+    /// let missing = RunConfig {
+    ///     cache_dir: dir.path().join("keys"),
+    ///     ..RunConfig::default()
+    /// };
+    /// missing.ensure_valid(A1, A2).unwrap();
+    /// assert!(!missing.cache_dir.exists());
+    ///
+    /// let file = dir.path().join("file");
+    /// std::fs::write(&file, "").unwrap();
+    /// let not_a_dir = RunConfig {
+    ///     cache_dir: file,
+    ///     ..RunConfig::default()
+    /// };
+    /// let err = not_a_dir.ensure_valid(A1, A2).unwrap_err();
+    /// assert!(matches!(err, ExampleError::CacheDirNotWritable { .. }), "{err}");
+    ///
+    /// # #[cfg(unix)]
+    /// # {
+    /// use std::{fs, os::unix::fs::PermissionsExt};
+    ///
+    /// let read_only = dir.path().join("read-only");
+    /// fs::create_dir(&read_only).unwrap();
+    /// fs::set_permissions(&read_only, fs::Permissions::from_mode(0o555)).unwrap();
+    /// // Root writes to it anyway, so does the probe
+    /// let privileged = fs::write(read_only.join("privileged"), "").is_ok();
+    ///
+    /// for cache_dir in [read_only.clone(), read_only.join("keys")] {
+    ///     let config = RunConfig {
+    ///         cache_dir,
+    ///         ..RunConfig::default()
+    ///     };
+    ///     match config.ensure_valid(A1, A2) {
+    ///         Err(ExampleError::CacheDirNotWritable { path, .. }) if !privileged => {
+    ///             assert_eq!(path, config.cache_dir)
+    ///         }
+    ///         Ok(()) if privileged => {}
+    ///         result => panic!("{result:?}"),
+    ///     }
+    /// }
+    /// // The probes left nothing behind
+    /// let entries = fs::read_dir(&read_only).unwrap().count();
+    /// assert_eq!(entries, usize::from(privileged));
+    /// # }
+    /// ```
+    pub fn ensure_valid(
+        &self,
+        primary_arity: usize,
+        secondary_arity: usize,
+    ) -> Result<(), ExampleError> {
+        cache::probe_writable(&self.cache_dir).map_err(|err| {
+            ExampleError::CacheDirNotWritable {
+                path: self.cache_dir.clone(),
+                err,
+            }
+        })?;
+
+        match self
+            .parameter_checks(primary_arity, secondary_arity)
+            .into_iter()
+            .find(|check| check.result.is_err())
+        {
+            Some(failed) => Err(ExampleError::InvalidConfig(failed.to_string())),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Meaning and allowed range of every [`RunConfig`] field, in the order of [`RunConfig::schema`]
+fn field_docs() -> Vec<(&'static str, String)> {
+    let key_size = |side: &str| {
+        format!(
+            "Commitment key size of the {side} circuit, from its table size to \
+             {MAX_COMMITMENT_KEY_SIZE}"
+        )
+    };
+    let table_size = |side: &str| {
+        format!("Table size (log2 of the rows) of the {side} circuit, at least {MIN_CIRCUIT_TABLE_SIZE}")
+    };
+
+    vec![
+        (
+            "fold_step_count",
+            "Number of folding steps, including the base step, at least 1".to_owned(),
+        ),
+        (
+            "elements_num",
+            "Number of Fibonacci elements proven by each step, at least 1".to_owned(),
+        ),
+        ("primary_commitment_key_size", key_size("primary")),
+        ("primary_circuit_table_size", table_size("primary")),
+        (
+            "primary_z_0",
+            "Input of the primary circuit on the zero step, one decimal or 0x-hex string per\n\
+             element, as many as the circuit arity"
+                .to_owned(),
+        ),
+        ("secondary_commitment_key_size", key_size("secondary")),
+        ("secondary_circuit_table_size", table_size("secondary")),
+        (
+            "secondary_z_0",
+            format!(
+                "Input of the secondary circuit on the zero step, {} elements",
+                crate::A2
+            ),
+        ),
+        (
+            "secondary_circuit",
+            format!(
+                "Step circuit folded on the secondary curve, one of: {}",
+                SecondaryCircuit::ALL.map(SecondaryCircuit::name).join(", ")
+            ),
+        ),
+        (
+            "cache_dir",
+            "Folder with the cached commitment keys, must be writable".to_owned(),
+        ),
+        (
+            "cache_retries",
+            "How many times to retry a transient cache IO failure".to_owned(),
+        ),
+        (
+            "halt_on_fixpoint",
+            "Stop folding early once a step leaves the state unchanged".to_owned(),
+        ),
+        (
+            "describe_ivc",
+            "Print a summary of the IVC structure once it is created".to_owned(),
+        ),
+        (
+            "max_time",
+            "Folding time budget in seconds, a positive number, unset for no budget".to_owned(),
+        ),
+        (
+            "no_verify",
+            "Skip the final verification, for prove-only benchmarks; the IVC stays unverified"
+                .to_owned(),
+        ),
+        (
+            "field_format",
+            format!(
+                "How field elements are printed, one of: {}",
+                FieldFormat::ALL.map(FieldFormat::name).join(", ")
+            ),
+        ),
+        (
+            "keep_going_on_verify_fail",
+            "On a failed fold step or verification, dump the state of the run and every check \
+             before failing"
+                .to_owned(),
+        ),
+        (
+            "checkpoints",
+            "Number of the latest states kept to rewind the fold runner to, 0 keeps none"
+                .to_owned(),
+        ),
+    ]
+}
+
+/// The key must be able to commit the whole table, the upper bound catches typos
+fn check_key_size(name: &'static str, key_size: usize, table_size: usize) -> Check {
+    check(
+        name,
+        (table_size..=MAX_COMMITMENT_KEY_SIZE).contains(&key_size),
+        || format!("{key_size} is outside the sane range {table_size}..={MAX_COMMITMENT_KEY_SIZE}"),
+    )
+}
@@ -0,0 +1,208 @@
+//! Columns and gates of a step circuit as a graphviz DOT graph, written by `--dump-constraint-dot`
+//!
+//! Every column the circuit configured is a box, every gate an ellipse with an edge to each cell
+//! it queries, labeled with the rotation of the query: `cur` for the row the gate is on, `prev` and
+//! `next` for its neighbours. The fibonacci gate of a block spread over several columns shows its
+//! rotation window that way. Only the gates are drawn, not the lookups or the equality
+//! constraints, which halo2 keeps in arguments of their own. Render it with e.g.
+//! `dot -Tsvg circuit.dot -o circuit.svg`.
+
+use std::{collections::BTreeSet, fmt};
+
+use sirius::{
+    halo2_proofs::plonk::Expression,
+    ivc::step_circuit::ConstraintSystem,
+    prelude::{PrimeField, StepCircuit},
+};
+
+/// Kind of a column of the constraint system, in the order they are drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColumnKind {
+    Selector,
+    Fixed,
+    Advice,
+    Instance,
+}
+
+impl ColumnKind {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Selector => "selector",
+            Self::Fixed => "fixed",
+            Self::Advice => "advice",
+            Self::Instance => "instance",
+        }
+    }
+
+    fn shape(self) -> &'static str {
+        match self {
+            Self::Selector => "box, style=rounded",
+            Self::Fixed => "box, style=filled, fillcolor=lightgrey",
+            Self::Advice => "box",
+            Self::Instance => "box, style=dashed",
+        }
+    }
+}
+
+/// Cell a gate queries: a column, at a rotation from the row of the gate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Query {
+    pub kind: ColumnKind,
+    pub column: usize,
+    /// `0` for selectors, which are only queried on the row of the gate
+    pub rotation: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GateNode {
+    pub name: String,
+    /// Every cell queried by any of the constraints of the gate
+    pub queries: BTreeSet<Query>,
+}
+
+/// Columns and gates of a configured constraint system
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintGraph {
+    /// Number of columns of each kind
+    pub columns: Vec<(ColumnKind, usize)>,
+    pub gates: Vec<GateNode>,
+}
+
+impl ConstraintGraph {
+    /// Configure `SC` on an empty constraint system and read its columns and gates
+    ///
+    /// ```
+    /// # use sirius::prelude::bn256::C1Scalar;
+    /// # use sirius_fibonacci_example::{
+    /// #     constraint_graph::{ColumnKind, ConstraintGraph, Query},
+    /// #     WideFibonacciCircuit, A1,
+    /// # };
+    /// let graph = ConstraintGraph::of::<A1, C1Scalar, WideFibonacciCircuit<2>>();
+    ///
+    /// // Over two columns, the last element of each row is the sum of the two before it, one of
+    /// // which is on the previous row
+    /// let queries = &graph.gates[0].queries;
+    /// let advice = |column, rotation| Query { kind: ColumnKind::Advice, column, rotation };
+    /// assert!(queries.contains(&advice(0, -1)));
+    /// assert!(queries.contains(&advice(1, -1)));
+    /// assert!(queries.contains(&advice(0, 0)));
+    ///
+    /// let dot = graph.to_string();
+    /// assert!(dot.starts_with("digraph constraint_system {"));
+    /// assert!(dot.contains("\"advice_0\" [label=\"advice 0\""));
+    /// assert!(dot.contains("\"gate_0\" -> \"advice_1\" [label=\"prev\"]"));
+    /// graphviz_rust::parse(&dot).unwrap();
+    /// ```
+    pub fn of<const ARITY: usize, F: PrimeField, SC: StepCircuit<ARITY, F>>() -> Self {
+        let mut cs = ConstraintSystem::<F>::default();
+        SC::configure(&mut cs);
+        Self::from_constraint_system(&cs)
+    }
+
+    pub fn from_constraint_system<F: PrimeField>(cs: &ConstraintSystem<F>) -> Self {
+        Self {
+            columns: vec![
+                (ColumnKind::Selector, cs.num_selectors()),
+                (ColumnKind::Fixed, cs.num_fixed_columns()),
+                (ColumnKind::Advice, cs.num_advice_columns()),
+                (ColumnKind::Instance, cs.num_instance_columns()),
+            ],
+            gates: cs
+                .gates()
+                .iter()
+                .map(|gate| {
+                    let mut queries = BTreeSet::new();
+                    for polynomial in gate.polynomials() {
+                        collect_queries(polynomial, &mut queries);
+                    }
+                    GateNode {
+                        name: gate.name().to_owned(),
+                        queries,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+fn collect_queries<F: PrimeField>(expression: &Expression<F>, queries: &mut BTreeSet<Query>) {
+    let (kind, column, rotation) = match expression {
+        Expression::Selector(selector) => (ColumnKind::Selector, selector.index(), 0),
+        Expression::Fixed(fixed) => (ColumnKind::Fixed, fixed.column_index(), fixed.rotation().0),
+        Expression::Advice(advice) => (
+            ColumnKind::Advice,
+            advice.column_index(),
+            advice.rotation().0,
+        ),
+        Expression::Instance(instance) => (
+            ColumnKind::Instance,
+            instance.column_index(),
+            instance.rotation().0,
+        ),
+        Expression::Negated(inner) | Expression::Scaled(inner, _) => {
+            return collect_queries(inner, queries);
+        }
+        Expression::Sum(lhs, rhs) | Expression::Product(lhs, rhs) => {
+            collect_queries(lhs, queries);
+            return collect_queries(rhs, queries);
+        }
+        // Constants and challenges are not cells
+        _ => return,
+    };
+    queries.insert(Query {
+        kind,
+        column,
+        rotation,
+    });
+}
+
+fn node(kind: ColumnKind, column: usize) -> String {
+    format!("{}_{column}", kind.name())
+}
+
+fn rotation_label(rotation: i32) -> String {
+    match rotation {
+        0 => "cur".to_owned(),
+        -1 => "prev".to_owned(),
+        1 => "next".to_owned(),
+        _ => format!("rot {rotation}"),
+    }
+}
+
+impl fmt::Display for ConstraintGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "digraph constraint_system {{")?;
+        writeln!(f, "  rankdir=LR;")?;
+        writeln!(f, "  node [fontname=\"monospace\"];")?;
+
+        for (kind, count) in &self.columns {
+            for column in 0..*count {
+                writeln!(
+                    f,
+                    "  \"{}\" [label=\"{} {column}\", shape={}];",
+                    node(*kind, column),
+                    kind.name(),
+                    kind.shape()
+                )?;
+            }
+        }
+
+        for (index, gate) in self.gates.iter().enumerate() {
+            writeln!(
+                f,
+                "  \"gate_{index}\" [label=\"gate {index}: {}\", shape=ellipse];",
+                gate.name.escape_default()
+            )?;
+            for query in &gate.queries {
+                writeln!(
+                    f,
+                    "  \"gate_{index}\" -> \"{}\" [label=\"{}\"];",
+                    node(query.kind, query.column),
+                    rotation_label(query.rotation)
+                )?;
+            }
+        }
+
+        writeln!(f, "}}")
+    }
+}
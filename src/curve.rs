@@ -0,0 +1,170 @@
+//! Curves of the supported IVC cycles and the setup of their commitment keys
+
+use std::{fmt, io, path::Path};
+
+use sirius::{
+    halo2_proofs::arithmetic::CurveAffine,
+    prelude::{
+        bn256::{C1Affine, C1Scalar, C2Affine, C2Scalar},
+        CommitmentKey, PrimeField,
+    },
+};
+
+use crate::cache;
+
+/// Curve whose commitment key can be loaded from the cache or set up
+///
+/// The setup is the same for every curve, so adding one to a cycle only takes an impl
+pub trait CurveSetup {
+    type Affine: CurveAffine;
+
+    /// Name of the curve, also used as the name of its cache file
+    const NAME: &'static str;
+
+    /// Load the commitment key of size `k` from `dir`, or generate and cache it
+    ///
+    /// `dir` is created if missing. Every curve of the cycle caches its key next to the others:
+    ///
+    /// ```
+    /// # use std::{fs, path::Path};
+    /// # use sirius_fibonacci_example::curve::{Bn256, CurveSetup, Grumpkin};
+    /// /// Cached files of `dir`, without the locks
+    /// let cached = |dir: &Path| -> Vec<String> {
+    ///     let mut names = fs::read_dir(dir)
+    ///         .unwrap()
+    ///         .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+    ///         .filter(|name| !name.ends_with(".lock"))
+    ///         .collect::<Vec<_>>();
+    ///     names.sort();
+    ///     names
+    /// };
+    ///
+    /// let tmp = tempfile::tempdir().unwrap();
+    /// let dir = tmp.path().join("keys");
+    ///
+    /// Bn256::load_or_setup(&dir, 8, 0).unwrap();
+    /// let primary = cached(&dir);
+    /// assert!(!primary.is_empty());
+    ///
+    /// Grumpkin::load_or_setup(&dir, 8, 0).unwrap();
+    /// let both = cached(&dir);
+    /// assert!(both.len() > primary.len(), "{both:?}");
+    ///
+    /// // Loaded back from the cache, nothing is written
+    /// Bn256::load_or_setup(&dir, 8, 0).unwrap();
+    /// Grumpkin::load_or_setup(&dir, 8, 0).unwrap();
+    /// assert_eq!(cached(&dir), both);
+    /// ```
+    fn load_or_setup(
+        dir: &Path,
+        k: usize,
+        retries: u32,
+    ) -> io::Result<CommitmentKey<Self::Affine>> {
+        // Safety: because the cache file is correct
+        unsafe { cache::load_or_setup::<Self::Affine>(dir, Self::NAME, k, retries) }
+    }
+}
+
+/// Primary curve of the default cycle
+pub struct Bn256;
+
+impl CurveSetup for Bn256 {
+    type Affine = C1Affine;
+    const NAME: &'static str = "bn256";
+}
+
+/// Secondary curve of the default cycle
+pub struct Grumpkin;
+
+impl CurveSetup for Grumpkin {
+    type Affine = C2Affine;
+    const NAME: &'static str = "grumpkin";
+}
+
+/// Cycle of curves an IVC can be folded on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cycle {
+    /// [`Bn256`] primary, [`Grumpkin`] secondary, the cycle of `sirius::prelude::bn256`
+    Bn256Grumpkin,
+}
+
+impl Cycle {
+    /// Every cycle sirius v0.1.1 provides default public parameters for
+    pub const ALL: [Self; 1] = [Self::Bn256Grumpkin];
+
+    /// `primary/secondary` curve names
+    pub fn name(self) -> &'static str {
+        self.info().name
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        supported_curves()
+            .iter()
+            .find(|info| info.name == name)
+            .map(|info| info.cycle)
+    }
+
+    /// Metadata of the cycle, its entry in [`supported_curves`]
+    pub fn info(self) -> &'static CurveCycleInfo {
+        supported_curves()
+            .iter()
+            .find(|info| info.cycle == self)
+            .expect("every cycle is supported")
+    }
+}
+
+/// What a supported cycle is made of
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurveCycleInfo {
+    pub cycle: Cycle,
+    /// `primary/secondary` curve names, the `--curve` value of the cycle
+    pub name: &'static str,
+    pub primary: &'static str,
+    pub secondary: &'static str,
+    /// Bits of the scalar field of the primary curve, the one the primary circuit computes in
+    pub primary_field_bits: u32,
+    /// Bits of the scalar field of the secondary curve
+    pub secondary_field_bits: u32,
+}
+
+impl fmt::Display for CurveCycleInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} primary ({}-bit scalar field), {} secondary ({}-bit scalar field)",
+            self.primary, self.primary_field_bits, self.secondary, self.secondary_field_bits
+        )
+    }
+}
+
+static SUPPORTED_CURVES: [CurveCycleInfo; Cycle::ALL.len()] = [CurveCycleInfo {
+    cycle: Cycle::Bn256Grumpkin,
+    name: "bn256/grumpkin",
+    primary: Bn256::NAME,
+    secondary: Grumpkin::NAME,
+    primary_field_bits: C1Scalar::NUM_BITS,
+    secondary_field_bits: C2Scalar::NUM_BITS,
+}];
+
+/// Every cycle the example can run, in the order of [`Cycle::ALL`]
+///
+/// The one place curve support is listed, `--curve` takes its names:
+///
+/// ```
+/// # use std::collections::HashSet;
+/// # use sirius_fibonacci_example::curve::{supported_curves, Cycle};
+/// let curves = supported_curves();
+/// assert!(!curves.is_empty());
+///
+/// let names = curves.iter().map(|info| info.name).collect::<HashSet<_>>();
+/// assert_eq!(names.len(), curves.len(), "duplicate cycle names");
+/// for info in curves {
+///     assert_ne!(info.primary, info.secondary);
+///     assert_eq!(info.name, format!("{}/{}", info.primary, info.secondary));
+///     assert_eq!(Cycle::from_name(info.name), Some(info.cycle));
+/// }
+/// assert_eq!(curves.iter().map(|info| info.cycle).collect::<Vec<_>>(), Cycle::ALL);
+/// ```
+pub fn supported_curves() -> &'static [CurveCycleInfo] {
+    &SUPPORTED_CURVES
+}
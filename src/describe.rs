@@ -0,0 +1,79 @@
+//! Informational summary of the IVC built by the example
+
+use std::fmt;
+
+use sirius::{
+    ivc::step_circuit::ConstraintSystem,
+    prelude::{PrimeField, StepCircuit},
+};
+
+/// Dimensions of a step circuit, as seen by its own `configure`
+///
+/// Sirius v0.1.1 does not expose the dimensions of the whole folding circuit from `pp` or `IVC`,
+/// so these only cover the step circuit part, without the folding verifier around it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepCircuitShape {
+    pub advice_columns: usize,
+    pub fixed_columns: usize,
+    pub selectors: usize,
+    pub gates: usize,
+    pub degree: usize,
+}
+
+impl StepCircuitShape {
+    /// Configure `SC` on an empty constraint system and read its dimensions
+    pub fn of<const ARITY: usize, F: PrimeField, SC: StepCircuit<ARITY, F>>() -> Self {
+        let mut cs = ConstraintSystem::<F>::default();
+        SC::configure(&mut cs);
+
+        Self {
+            advice_columns: cs.num_advice_columns(),
+            fixed_columns: cs.num_fixed_columns(),
+            selectors: cs.num_selectors(),
+            gates: cs.gates().len(),
+            degree: cs.degree(),
+        }
+    }
+}
+
+impl fmt::Display for StepCircuitShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} advice & {} fixed columns, {} selectors, {} gates, max degree {}",
+            self.advice_columns, self.fixed_columns, self.selectors, self.gates, self.degree
+        )
+    }
+}
+
+/// One side (primary or secondary) of the IVC
+pub struct SideDescription {
+    pub arity: usize,
+    pub table_size: usize,
+    pub commitment_key_size: usize,
+    pub shape: StepCircuitShape,
+}
+
+impl fmt::Display for SideDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "arity {}, table 2^{} rows, commitment key 2^{}, step circuit: {}",
+            self.arity, self.table_size, self.commitment_key_size, self.shape
+        )
+    }
+}
+
+/// Summary printed by `--describe-ivc`
+pub struct IvcDescription {
+    pub primary: SideDescription,
+    pub secondary: SideDescription,
+}
+
+impl fmt::Display for IvcDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "ivc structure:")?;
+        writeln!(f, "  primary:   {}", self.primary)?;
+        writeln!(f, "  secondary: {}", self.secondary)
+    }
+}
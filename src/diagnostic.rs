@@ -0,0 +1,105 @@
+//! What a failed run dumps with [`RunConfig::keep_going_on_verify_fail`], to debug its circuit
+
+use std::fmt;
+
+use sirius::prelude::bn256::{C1Scalar, C2Scalar};
+
+use crate::{
+    config::{Check, RunConfig},
+    field::FieldFormat,
+};
+
+/// State of a run at its failure, with every check run up to it
+///
+/// A fold step that fails is where the debug mode of sirius found the step unsatisfiable, so it
+/// is a verification failure too: the steps folded before it are then verified, to tell a broken
+/// step from an IVC that was already unsound. Returned inside [`crate::error::ExampleError::Diagnosed`]:
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     config::RunConfig, error::ExampleError, run_circuit, CircuitSpec,
+/// # };
+/// let cache = tempfile::tempdir().unwrap();
+/// let config = RunConfig {
+///     fold_step_count: 3,
+///     primary_z_0: vec![C1Scalar::from(0)],
+///     keep_going_on_verify_fail: true,
+///     cache_dir: cache.path().to_path_buf(),
+///     ..RunConfig::default()
+/// };
+/// // The second reading is out of bounds, so the step that folds it is unsatisfiable
+/// let broken = CircuitSpec::RangeGuard {
+///     readings: vec![5, 100],
+///     lo: 0,
+///     hi: 10,
+/// };
+///
+/// let err = run_circuit(&broken, &config, None).err().unwrap();
+/// let ExampleError::Diagnosed { err, diagnostic } = err else {
+///     panic!("no diagnostic: {err}")
+/// };
+/// assert!(matches!(*err, ExampleError::FoldStep { step: 1, .. }), "{err}");
+/// // Stopped after the base step, then verified it
+/// assert_eq!(diagnostic.steps, 1);
+/// assert_eq!(diagnostic.z_out, [C1Scalar::from(5)]);
+/// let checks = diagnostic.checks.iter().map(|check| check.name).collect::<Vec<_>>();
+/// assert_eq!(checks, ["fold step", "ivc verify"]);
+/// assert!(diagnostic.checks[0].result.is_err());
+/// assert!(diagnostic.to_string().contains("[FAIL] fold step"));
+/// ```
+#[derive(Debug)]
+pub struct FailureDiagnostic {
+    /// Config of the run, as TOML
+    pub config: String,
+    /// Steps folded before the failure, including the base step
+    pub steps: usize,
+    /// Output of the last folded step
+    pub z_out: Vec<C1Scalar>,
+    /// Output of the last folded step of the secondary circuit
+    pub secondary_z_out: Vec<C2Scalar>,
+    /// Every check run, the failed ones included
+    pub checks: Vec<Check>,
+    pub field_format: FieldFormat,
+}
+
+impl FailureDiagnostic {
+    pub(crate) fn new(
+        config: &RunConfig,
+        steps: usize,
+        z_out: &[C1Scalar],
+        secondary_z_out: &[C2Scalar],
+        checks: Vec<Check>,
+    ) -> Self {
+        Self {
+            config: config.to_toml(),
+            steps,
+            z_out: z_out.to_vec(),
+            secondary_z_out: secondary_z_out.to_vec(),
+            checks,
+            field_format: config.field_format,
+        }
+    }
+}
+
+impl fmt::Display for FailureDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "diagnostic of the failed run:")?;
+        writeln!(f, "  steps folded: {}", self.steps)?;
+        writeln!(f, "  z_out: {}", self.field_format.display_all(&self.z_out))?;
+        writeln!(
+            f,
+            "  secondary z_out: {}",
+            self.field_format.display_all(&self.secondary_z_out)
+        )?;
+        writeln!(f, "  checks:")?;
+        for check in &self.checks {
+            writeln!(f, "    {check}")?;
+        }
+        write!(f, "  config:")?;
+        for line in self.config.lines() {
+            write!(f, "\n    {line}")?;
+        }
+        Ok(())
+    }
+}
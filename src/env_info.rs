@@ -0,0 +1,62 @@
+//! Host a run was proven on, recorded in its proof bundle by `--env-dump` to compare timings
+//! across machines
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Details of the host that bear on the timing of a run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvInfo {
+    /// OS with its version, e.g. `Linux 22.04 Ubuntu`, or only its family if the version is unknown
+    pub os: String,
+    pub arch: String,
+    /// Logical CPUs available to the process
+    pub cpu_count: usize,
+    /// Total RAM in bytes
+    pub total_memory: u64,
+    /// Threads of the rayon pool that sirius folds and commits on
+    pub thread_pool_size: usize,
+}
+
+#[cfg(feature = "env-dump")]
+impl EnvInfo {
+    /// Environment of this process, enabled by the `env-dump` feature
+    ///
+    /// ```
+    /// # use sirius_fibonacci_example::env_info::EnvInfo;
+    /// let env = EnvInfo::current();
+    /// assert!(!env.os.is_empty());
+    /// assert!(!env.arch.is_empty());
+    /// assert!(env.cpu_count > 0);
+    /// assert!(env.total_memory > 0);
+    /// assert!(env.thread_pool_size > 0);
+    /// ```
+    pub fn current() -> Self {
+        let mut system = sysinfo::System::new();
+        system.refresh_memory();
+
+        Self {
+            os: sysinfo::System::long_os_version()
+                .unwrap_or_else(|| std::env::consts::OS.to_owned()),
+            arch: std::env::consts::ARCH.to_owned(),
+            cpu_count: std::thread::available_parallelism().map_or(1, |count| count.get()),
+            total_memory: system.total_memory(),
+            thread_pool_size: rayon::current_num_threads(),
+        }
+    }
+}
+
+impl fmt::Display for EnvInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}, {} CPUs, {} MiB RAM, {} threads",
+            self.os,
+            self.arch,
+            self.cpu_count,
+            self.total_memory >> 20,
+            self.thread_pool_size
+        )
+    }
+}
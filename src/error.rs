@@ -0,0 +1,120 @@
+use std::{io, path::PathBuf};
+
+use crate::{diagnostic::FailureDiagnostic, remedy::Undersized, warning::WarningCounts};
+
+/// Errors that stop the example before it reports success
+///
+/// Sirius errors are kept as their debug representation, since the example only reports them
+#[derive(Debug, thiserror::Error)]
+pub enum ExampleError {
+    #[error("invalid configuration: {0}")]
+    InvalidConfig(String),
+    #[error("config file {path:?}: {err}")]
+    ConfigFile { path: PathBuf, err: String },
+    #[error("commitment key cache: {0}")]
+    Cache(#[from] io::Error),
+    #[error("cache dir {path:?} is not writable ({err}), pass a writable `--cache-dir`")]
+    CacheDirNotWritable { path: PathBuf, err: io::Error },
+    #[error("failed to create `IVC`: {0}")]
+    IvcCreate(String),
+    #[error("failed to run fold step {step}: {err}")]
+    FoldStep { step: usize, err: String },
+    #[error("failed to verify ivc: {0}")]
+    Verify(String),
+    /// `err` of a run with `keep_going_on_verify_fail`, with the state of the run when it failed
+    #[error("{err}\n{diagnostic}")]
+    Diagnosed {
+        err: Box<ExampleError>,
+        diagnostic: Box<FailureDiagnostic>,
+    },
+    #[error("metrics endpoint: {0}")]
+    Metrics(io::Error),
+    #[error("proof bundle {path:?}: {err}")]
+    Bundle { path: PathBuf, err: String },
+    #[error("proof bundle does not match the verified run: {0}")]
+    BundleMismatch(String),
+    #[error("separate verifier process: {0}")]
+    VerifierProcess(String),
+    #[error("snapshot {path:?}: {err}")]
+    Snapshot { path: PathBuf, err: String },
+    #[error("output diverges from the snapshot {path:?}:\n{diff}")]
+    SnapshotMismatch { path: PathBuf, diff: String },
+    #[error("public transcript {path:?}: {err}")]
+    Transcript { path: PathBuf, err: io::Error },
+    #[error("mock prover found violated constraints:\n{0}")]
+    MockProver(String),
+    #[error("step assigned z_out {got}, but its reference step computes {expected}")]
+    StepOutputMismatch { got: String, expected: String },
+    #[error("checksum {got} does not match the expected {expected}")]
+    ChecksumMismatch { expected: String, got: String },
+    #[error("{got} steps were proven, not the expected {expected}")]
+    StepCountMismatch { expected: usize, got: usize },
+    #[error("can't rewind {steps} steps, only the {available} before are checkpointed")]
+    Rewind { steps: usize, available: usize },
+    #[error("unsupported: {0}")]
+    Unsupported(String),
+    #[error("log file {path:?}: {err}")]
+    LogFile { path: PathBuf, err: io::Error },
+    #[error("constraint system graph {path:?}: {err}")]
+    ConstraintDot { path: PathBuf, err: io::Error },
+    #[error("OS entropy: {0}")]
+    Entropy(String),
+    #[error("input stream: {0}")]
+    InputStream(String),
+    #[error("{0} (a warning, failing because of `--strict`)")]
+    Strict(String),
+    #[error("more than {budget} warnings, aborting: {counts}")]
+    WarningBudget {
+        budget: usize,
+        counts: WarningCounts,
+    },
+    /// The folding time budget `max_time` ran out after `steps` steps in strict mode, where a
+    /// partial run is a failure; otherwise folding just stops with a warning
+    ///
+    /// ```
+    /// # use sirius_fibonacci_example::{
+    /// #     config::RunConfig, error::{ExampleError, TIMEOUT_EXIT_CODE}, run_circuit, warning,
+    /// #     CircuitSpec,
+    /// # };
+    /// let cache = tempfile::tempdir().unwrap();
+    /// let config = RunConfig {
+    ///     fold_step_count: 3,
+    ///     max_time: Some(1e-9),
+    ///     cache_dir: cache.path().to_path_buf(),
+    ///     ..RunConfig::default()
+    /// };
+    ///
+    /// warning::set_strict(true);
+    /// let err = run_circuit(&CircuitSpec::Fibonacci, &config, None).err().unwrap();
+    /// // Only the base step made it within the budget
+    /// assert!(matches!(err, ExampleError::Timeout { steps: 1, .. }), "{err}");
+    /// assert_eq!(err.exit_code(), TIMEOUT_EXIT_CODE);
+    /// ```
+    #[error("folding time budget of {max_time}s exceeded after {steps} steps (failing because of `--strict`)")]
+    Timeout { steps: usize, max_time: f64 },
+}
+
+/// Exit code of the example on [`ExampleError::Timeout`], any other error exits with `1`
+pub const TIMEOUT_EXIT_CODE: u8 = 3;
+
+impl ExampleError {
+    /// Table or commitment key to increase, if that is what made sirius fail
+    pub fn undersized(&self) -> Option<Undersized> {
+        match self {
+            Self::IvcCreate(err) | Self::FoldStep { err, .. } | Self::Verify(err) => {
+                Undersized::classify(err)
+            }
+            Self::Diagnosed { err, .. } => err.undersized(),
+            _ => None,
+        }
+    }
+
+    /// Process exit code the example fails with, so scripts can tell a timeout from the rest
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::Timeout { .. } => TIMEOUT_EXIT_CODE,
+            Self::Diagnosed { err, .. } => err.exit_code(),
+            _ => 1,
+        }
+    }
+}
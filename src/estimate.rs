@@ -0,0 +1,102 @@
+//! Rough cost of a run, from its config alone
+//!
+//! The model is deliberately simple: the commitment key setup is linear in the number of key
+//! points, and every fold step is linear in the rows of both circuits, which is what the
+//! commitments of a step are computed over. The per-unit times were measured once with the
+//! default config on a laptop-class CPU (release build), so read the times as an order of
+//! magnitude, not a promise.
+
+use std::{fmt, time::Duration};
+
+use crate::config::RunConfig;
+
+/// Bytes of one point of a commitment key, two 32-byte coordinates
+const KEY_POINT_BYTES: u64 = 64;
+
+/// Nanoseconds to generate one commitment key point, on setup without a cached key
+const SETUP_NS_PER_KEY_POINT: u64 = 5_000;
+
+/// Nanoseconds per table row of either circuit, for one fold step
+const FOLD_NS_PER_ROW: u64 = 1_000;
+
+/// Estimated cost of a run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CostEstimate {
+    pub primary_rows: u64,
+    pub secondary_rows: u64,
+    /// Memory of both commitment keys, also their size in the cache
+    pub key_bytes: u64,
+    /// Setup of both commitment keys, when they are not cached yet
+    pub setup: Duration,
+    /// All fold steps plus the verification, which costs about one more step
+    pub fold: Duration,
+}
+
+impl CostEstimate {
+    /// Estimate the run of `config`, without any setup
+    ///
+    /// Every figure only grows with the step count, the table sizes and the key sizes:
+    ///
+    /// ```
+    /// # use sirius_fibonacci_example::{config::RunConfig, estimate::CostEstimate};
+    /// let config = RunConfig::default();
+    /// let more_steps = RunConfig {
+    ///     fold_step_count: config.fold_step_count * 2,
+    ///     ..config.clone()
+    /// };
+    /// let bigger_table = RunConfig {
+    ///     primary_circuit_table_size: config.primary_circuit_table_size + 1,
+    ///     ..config.clone()
+    /// };
+    ///
+    /// let base = CostEstimate::of(&config);
+    /// assert!(CostEstimate::of(&more_steps).fold > base.fold);
+    /// assert!(CostEstimate::of(&bigger_table).primary_rows > base.primary_rows);
+    /// assert!(CostEstimate::of(&bigger_table).fold > base.fold);
+    /// ```
+    pub fn of(config: &RunConfig) -> Self {
+        let rows = |table_size: usize| 1u64 << table_size;
+        let primary_rows = rows(config.primary_circuit_table_size);
+        let secondary_rows = rows(config.secondary_circuit_table_size);
+        let key_points =
+            rows(config.primary_commitment_key_size) + rows(config.secondary_commitment_key_size);
+
+        // Verification costs about one more fold step
+        let steps = config.fold_step_count as u64 + 1;
+
+        Self {
+            primary_rows,
+            secondary_rows,
+            key_bytes: key_points * KEY_POINT_BYTES,
+            setup: Duration::from_nanos(SETUP_NS_PER_KEY_POINT.saturating_mul(key_points)),
+            fold: Duration::from_nanos(
+                FOLD_NS_PER_ROW
+                    .saturating_mul(primary_rows + secondary_rows)
+                    .saturating_mul(steps),
+            ),
+        }
+    }
+}
+
+impl fmt::Display for CostEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "estimated cost (rough, see `estimate` module docs):")?;
+        writeln!(f, "  primary rows:           {}", self.primary_rows)?;
+        writeln!(f, "  secondary rows:         {}", self.secondary_rows)?;
+        writeln!(
+            f,
+            "  commitment key memory:  {:.1} MiB",
+            self.key_bytes as f64 / (1024.0 * 1024.0)
+        )?;
+        writeln!(
+            f,
+            "  key setup (uncached):   {:.1} s",
+            self.setup.as_secs_f64()
+        )?;
+        writeln!(
+            f,
+            "  folding & verification: {:.1} s",
+            self.fold.as_secs_f64()
+        )
+    }
+}
@@ -0,0 +1,367 @@
+//! Conversions between field elements and their text form
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use sirius::prelude::PrimeField;
+
+/// Big-endian hex of `value` with the `0x` prefix, as the field's `Debug` prints it
+///
+/// Relies on the little-endian representation, as used by the bn256 & grumpkin scalar fields
+pub fn to_hex<F: PrimeField>(value: &F) -> String {
+    let repr = value.to_repr();
+    let hex = repr
+        .as_ref()
+        .iter()
+        .rev()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+
+    format!("0x{hex}")
+}
+
+/// Value of `value` as an integer, if it fits into `u128`
+///
+/// Relies on the little-endian representation, as used by the bn256 & grumpkin scalar fields
+pub fn to_u128<F: PrimeField>(value: &F) -> Option<u128> {
+    let repr = value.to_repr();
+    let (low, high) = repr.as_ref().split_at(16);
+
+    high.iter()
+        .all(|byte| *byte == 0)
+        .then(|| u128::from_le_bytes(low.try_into().unwrap()))
+}
+
+/// `value` as a field element, a negative one wrapping around the modulus to `p - |value|`
+pub fn from_i64<F: PrimeField>(value: i64) -> F {
+    let magnitude = F::from(value.unsigned_abs());
+    if value < 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// Value of `value` as a signed integer, reading `p - x` as `-x`, if it fits into `i128`
+///
+/// The inverse of [`from_i64`]:
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::field::{from_i64, to_i128};
+/// for value in [0, 1, -1, i64::MIN, i64::MAX] {
+///     assert_eq!(to_i128(&from_i64::<C1Scalar>(value)), Some(value as i128));
+/// }
+/// ```
+pub fn to_i128<F: PrimeField>(value: &F) -> Option<i128> {
+    match to_u128(value) {
+        Some(positive) => i128::try_from(positive).ok(),
+        None => to_u128(&-*value)
+            .and_then(|negative| i128::try_from(negative).ok())
+            .map(|negative| -negative),
+    }
+}
+
+/// Decimal of `value` if it fits into `u128`, its [`to_hex`] otherwise
+pub fn to_readable<F: PrimeField>(value: &F) -> String {
+    to_u128(value).map_or_else(|| to_hex(value), |value| value.to_string())
+}
+
+/// Decimal of `value`, however large
+///
+/// Relies on the little-endian representation, as [`to_hex`] does
+pub fn to_decimal<F: PrimeField>(value: &F) -> String {
+    // Big-endian, divided by 10 until zero, the remainders are the digits from the last one
+    let mut bytes = value
+        .to_repr()
+        .as_ref()
+        .iter()
+        .rev()
+        .copied()
+        .collect::<Vec<_>>();
+    let mut digits = vec![];
+    while bytes.iter().any(|byte| *byte != 0) {
+        let mut remainder = 0;
+        for byte in &mut bytes {
+            let value = remainder * 256 + u32::from(*byte);
+            *byte = (value / 10) as u8;
+            remainder = value % 10;
+        }
+        digits.push(char::from_digit(remainder, 10).unwrap());
+    }
+
+    if digits.is_empty() {
+        return "0".to_owned();
+    }
+    digits.into_iter().rev().collect()
+}
+
+/// Big-endian hex of the Montgomery form `value * R mod p` of `value`, with `R = 2^256` for the
+/// bn256 & grumpkin scalar fields, which is what their arithmetic works on in memory
+pub fn to_montgomery_hex<F: PrimeField>(value: &F) -> String {
+    let bits = F::Repr::default().as_ref().len() * 8;
+    let r = (0..bits).fold(F::ONE, |r, _| r.double());
+
+    to_hex(&(*value * r))
+}
+
+/// How field elements are printed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FieldFormat {
+    /// Decimal if the value fits into `u128`, hex otherwise, see [`to_readable`]
+    #[default]
+    Auto,
+    /// Decimal, however large, see [`to_decimal`]
+    Decimal,
+    /// Big-endian hex, see [`to_hex`]
+    Hex,
+    /// Big-endian hex of the Montgomery form, for debugging, see [`to_montgomery_hex`]
+    Montgomery,
+}
+
+impl FieldFormat {
+    pub const ALL: [Self; 4] = [Self::Auto, Self::Decimal, Self::Hex, Self::Montgomery];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Decimal => "decimal",
+            Self::Hex => "hex",
+            Self::Montgomery => "montgomery",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|format| format.name() == name)
+    }
+
+    /// `value` displayed in this format
+    pub fn display<F: PrimeField>(self, value: &F) -> FieldDisplay<'_, F> {
+        FieldDisplay {
+            value,
+            format: self,
+        }
+    }
+
+    /// Every element of `values` in this format, as a `[a, b, ...]` list
+    pub fn display_all<F: PrimeField>(self, values: &[F]) -> String {
+        let values = values
+            .iter()
+            .map(|value| self.display(value).to_string())
+            .collect::<Vec<_>>();
+        format!("[{}]", values.join(", "))
+    }
+}
+
+impl fmt::Display for FieldFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// How `--print-z-out` prints the final `z_out` on its own, for a script to capture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueEncoding {
+    /// Decimal elements separated by spaces, see [`to_decimal`]
+    Dec,
+    /// Big-endian hex elements separated by spaces, see [`to_hex`]
+    Hex,
+    /// JSON array of the hex elements, as in the files of the example
+    Json,
+}
+
+impl ValueEncoding {
+    pub const ALL: [Self; 3] = [Self::Dec, Self::Hex, Self::Json];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Dec => "dec",
+            Self::Hex => "hex",
+            Self::Json => "json",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|encoding| encoding.name() == name)
+    }
+
+    /// `values` in this encoding, on a single line
+    ///
+    /// ```
+    /// # use sirius::prelude::bn256::C1Scalar;
+    /// # use sirius_fibonacci_example::field::ValueEncoding;
+    /// // `z_out` of the default run, the 50th and 51st Fibonacci numbers
+    /// let z_out = [12586269025u64, 20365011074].map(C1Scalar::from);
+    /// let hex = |digits| format!("0x{digits:0>64}");
+    ///
+    /// assert_eq!(ValueEncoding::Dec.encode(&z_out), "12586269025 20365011074");
+    /// assert_eq!(
+    ///     ValueEncoding::Hex.encode(&z_out),
+    ///     format!("{} {}", hex("2ee333961"), hex("4bdd96882"))
+    /// );
+    /// assert_eq!(
+    ///     ValueEncoding::Json.encode(&z_out),
+    ///     format!(r#"["{}","{}"]"#, hex("2ee333961"), hex("4bdd96882"))
+    /// );
+    /// ```
+    pub fn encode<F: PrimeField>(self, values: &[F]) -> String {
+        match self {
+            Self::Dec => values.iter().map(to_decimal).collect::<Vec<_>>().join(" "),
+            Self::Hex => values.iter().map(to_hex).collect::<Vec<_>>().join(" "),
+            Self::Json => serde_json::to_string(&values.iter().map(to_hex).collect::<Vec<_>>())
+                .expect("strings are always representable"),
+        }
+    }
+}
+
+/// A field element displayed in a [`FieldFormat`]
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::field::{parse_field, FieldFormat};
+/// let small = C1Scalar::from(89);
+/// // `p - 1`, too large for `u128`
+/// let large = -C1Scalar::from(1);
+/// let shown = |format: FieldFormat| {
+///     [small, large].map(|value| format.display(&value).to_string())
+/// };
+///
+/// let p_minus_one_hex = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000";
+/// let p_minus_one = "21888242871839275222246405745257275088548364400416034343698204186575808495616";
+/// let hex_89 = format!("0x{:0>64}", "59");
+/// assert_eq!(shown(FieldFormat::Auto), ["89", p_minus_one_hex]);
+/// assert_eq!(shown(FieldFormat::Decimal), ["89", p_minus_one]);
+/// assert_eq!(shown(FieldFormat::Hex), [hex_89.as_str(), p_minus_one_hex]);
+///
+/// // The Montgomery form of `x` is `x * R`: that of `1` is `R`, that of `p - 1` is `p - R`
+/// let r = FieldFormat::Montgomery.display(&C1Scalar::from(1)).to_string();
+/// let r = parse_field::<C1Scalar>(&r).unwrap();
+/// let hex = |value: C1Scalar| FieldFormat::Hex.display(&value).to_string();
+/// assert_eq!(shown(FieldFormat::Montgomery), [hex(small * r), hex(-r)]);
+/// ```
+pub struct FieldDisplay<'a, F> {
+    value: &'a F,
+    format: FieldFormat,
+}
+
+impl<F: PrimeField> fmt::Display for FieldDisplay<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self.format {
+            FieldFormat::Auto => to_readable(self.value),
+            FieldFormat::Decimal => to_decimal(self.value),
+            FieldFormat::Hex => to_hex(self.value),
+            FieldFormat::Montgomery => to_montgomery_hex(self.value),
+        };
+        f.write_str(&text)
+    }
+}
+
+/// Why a string is not a field element
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseFieldError {
+    #[error("no digits")]
+    Empty,
+    #[error("invalid digit {0:?}")]
+    InvalidDigit(char),
+    #[error("value does not fit in the field, it must be less than the modulus")]
+    OutOfRange,
+}
+
+/// Parse a decimal or `0x`-prefixed big-endian hex value, leading zeros allowed
+///
+/// Unlike [`PrimeField::from_str_vartime`], a value at or above the modulus is rejected instead
+/// of silently reduced. Relies on the little-endian representation, as [`to_hex`] does.
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::field::{parse_field, ParseFieldError};
+/// // The modulus of the bn256 scalar field
+/// const MODULUS: &str =
+///     "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+/// const MODULUS_PLUS_ONE: &str =
+///     "21888242871839275222246405745257275088548364400416034343698204186575808495618";
+///
+/// let parse = parse_field::<C1Scalar>;
+/// assert_eq!(parse("1234"), Ok(C1Scalar::from(1234)));
+/// assert_eq!(parse("0x4d2"), Ok(C1Scalar::from(1234)));
+/// assert_eq!(parse("0x4D2"), Ok(C1Scalar::from(1234)));
+/// assert_eq!(parse("0"), Ok(C1Scalar::from(0)));
+///
+/// // Leading zeros change nothing
+/// assert_eq!(parse("0001234"), Ok(C1Scalar::from(1234)));
+/// assert_eq!(parse("0x00004d2"), Ok(C1Scalar::from(1234)));
+///
+/// assert_eq!(parse(""), Err(ParseFieldError::Empty));
+/// assert_eq!(parse("0x"), Err(ParseFieldError::Empty));
+/// assert_eq!(parse("12a4"), Err(ParseFieldError::InvalidDigit('a')));
+/// assert_eq!(parse("0x4g2"), Err(ParseFieldError::InvalidDigit('g')));
+/// assert_eq!(parse("-1"), Err(ParseFieldError::InvalidDigit('-')));
+/// assert_eq!(parse(" 1"), Err(ParseFieldError::InvalidDigit(' ')));
+///
+/// let max = parse_field::<C1Scalar>(
+///     "21888242871839275222246405745257275088548364400416034343698204186575808495616",
+/// );
+/// assert_eq!(max, Ok(-C1Scalar::from(1)));
+///
+/// let huge = "9".repeat(100);
+/// let huge_hex = format!("0x{}", "f".repeat(64));
+/// for out_of_range in [MODULUS, MODULUS_PLUS_ONE, &huge, &huge_hex] {
+///     let parsed = parse_field::<C1Scalar>(out_of_range);
+///     assert_eq!(parsed, Err(ParseFieldError::OutOfRange));
+/// }
+/// ```
+pub fn parse_field<F: PrimeField>(s: &str) -> Result<F, ParseFieldError> {
+    let (digits, radix) = match s.strip_prefix("0x") {
+        Some(hex) => (hex, 16),
+        None => (s, 10),
+    };
+    if digits.is_empty() {
+        return Err(ParseFieldError::Empty);
+    }
+
+    // `repr = repr * radix + digit` for every digit, as a little-endian big integer
+    let mut repr = F::Repr::default();
+    for c in digits.chars() {
+        let mut carry = c.to_digit(radix).ok_or(ParseFieldError::InvalidDigit(c))?;
+        for byte in repr.as_mut() {
+            let value = u32::from(*byte) * radix + carry;
+            *byte = value as u8;
+            carry = value >> 8;
+        }
+        if carry != 0 {
+            return Err(ParseFieldError::OutOfRange);
+        }
+    }
+
+    Option::from(F::from_repr(repr)).ok_or(ParseFieldError::OutOfRange)
+}
+
+/// `#[serde(with = "field::serde_vec")]` for a `Vec` of field elements
+///
+/// Serialized as hex strings, deserialized from decimal or hex strings
+pub mod serde_vec {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use sirius::prelude::PrimeField;
+
+    pub fn serialize<F: PrimeField, S: Serializer>(
+        values: &[F],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(values.iter().map(super::to_hex))
+    }
+
+    pub fn deserialize<'de, F: PrimeField, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<F>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|s| {
+                super::parse_field(s)
+                    .map_err(|err| D::Error::custom(format!("invalid field element `{s}`: {err}")))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,81 @@
+//! Polynomial degree of the gates of a step circuit, printed by `--dump-gate-degree`
+//!
+//! The degree of a gate is that of its highest-degree constraint, each selector and queried cell
+//! counting as one: the fibonacci gate `s * (a + b - c)` has degree 2. halo2 evaluates the
+//! constraints on a domain as many times larger than the table as the degree of the whole
+//! constraint system, and sirius folds a gate of degree `d` with `d - 1` cross terms to commit
+//! on every step, so a higher degree makes every step more expensive, not only the table larger.
+
+use std::fmt;
+
+use sirius::{
+    ivc::step_circuit::ConstraintSystem,
+    prelude::{PrimeField, StepCircuit},
+};
+
+/// Degree of every gate created by the `configure` of a step circuit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GateDegrees {
+    /// Degree of each gate, in the order `configure` created them
+    pub gates: Vec<usize>,
+    /// Degree of the whole constraint system: the gates, but also the permutation argument of
+    /// the equality constraints and the lookups
+    pub system: usize,
+}
+
+impl GateDegrees {
+    /// Configure `SC` on an empty constraint system and read the degree of its gates
+    ///
+    /// ```
+    /// # use sirius::prelude::bn256::C1Scalar;
+    /// # use sirius_fibonacci_example::{gate_degree::GateDegrees, FibonacciCircuit, A1};
+    /// let degrees = GateDegrees::of::<A1, C1Scalar, FibonacciCircuit>();
+    /// // `s * (a + b - c)`: the selector times a linear expression
+    /// assert_eq!(degrees.gates, [2]);
+    /// assert_eq!(degrees.max(), 2);
+    /// assert!(degrees.system >= degrees.max());
+    /// ```
+    pub fn of<const ARITY: usize, F: PrimeField, SC: StepCircuit<ARITY, F>>() -> Self {
+        let mut cs = ConstraintSystem::<F>::default();
+        SC::configure(&mut cs);
+        Self::from_constraint_system(&cs)
+    }
+
+    /// Degree of the gates of a configured constraint system
+    pub fn from_constraint_system<F: PrimeField>(cs: &ConstraintSystem<F>) -> Self {
+        Self {
+            gates: cs
+                .gates()
+                .iter()
+                .map(|gate| {
+                    gate.polynomials()
+                        .iter()
+                        .map(|polynomial| polynomial.degree())
+                        .max()
+                        .unwrap_or(0)
+                })
+                .collect(),
+            system: cs.degree(),
+        }
+    }
+
+    /// Highest degree of the gates, `0` without any
+    pub fn max(&self) -> usize {
+        self.gates.iter().copied().max().unwrap_or(0)
+    }
+}
+
+impl fmt::Display for GateDegrees {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "gate degrees:")?;
+        for (index, degree) in self.gates.iter().enumerate() {
+            writeln!(f, "  gate {index}: {degree}")?;
+        }
+        writeln!(
+            f,
+            "max gate degree {}, constraint system degree {} with the permutation and lookups",
+            self.max(),
+            self.system
+        )
+    }
+}
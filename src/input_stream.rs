@@ -0,0 +1,38 @@
+//! Per-step inputs read line by line, e.g. streamed by another process through stdin
+
+use std::io::BufRead;
+
+use crate::error::ExampleError;
+
+/// One input per non-blank line of `reader`, as a decimal `u64`
+///
+/// Lines are read lazily, so each input blocks until its line arrives, and the inputs end with
+/// `reader`
+///
+/// ```
+/// # use sirius_fibonacci_example::{error::ExampleError, input_stream::read_inputs};
+/// let inputs: Vec<_> = read_inputs(&b"3\n\n  \n 5 \r\n8"[..])
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(inputs, [3, 5, 8]);
+///
+/// // A malformed line fails on its own, the lines around it still read
+/// let mut inputs = read_inputs(std::io::Cursor::new("1\nx2\n-3\n4\n"));
+/// assert_eq!(inputs.next().unwrap().unwrap(), 1);
+/// let err = inputs.next().unwrap().unwrap_err();
+/// assert!(matches!(&err, ExampleError::InputStream(msg) if msg.starts_with("\"x2\"")), "{err}");
+/// assert!(inputs.next().unwrap().is_err());
+/// assert_eq!(inputs.next().unwrap().unwrap(), 4);
+/// assert!(inputs.next().is_none());
+/// ```
+pub fn read_inputs<R: BufRead>(reader: R) -> impl Iterator<Item = Result<u64, ExampleError>> {
+    reader
+        .lines()
+        .filter(|line| !matches!(line, Ok(line) if line.trim().is_empty()))
+        .map(|line| {
+            let line = line.map_err(|err| ExampleError::InputStream(err.to_string()))?;
+            line.trim()
+                .parse()
+                .map_err(|err| ExampleError::InputStream(format!("{line:?}: {err}")))
+        })
+}
@@ -0,0 +1,99 @@
+//! Finding the smallest primary commitment key that works for a circuit
+
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    config::RunConfig, error::ExampleError, progress::StepEvent, run_circuit, CircuitSpec,
+};
+
+/// Outcome of the short run with one primary commitment key size
+#[derive(Debug)]
+pub struct KeySizeTrial {
+    pub size: usize,
+    /// Time from the start of the setup to the created IVC, `None` if the setup didn't finish
+    pub setup: Option<Duration>,
+    pub result: Result<(), ExampleError>,
+}
+
+/// Every tried size with its trial, in the order tried
+pub struct KeySizeReport {
+    pub trials: Vec<KeySizeTrial>,
+}
+
+impl KeySizeReport {
+    pub fn smallest_working(&self) -> Option<usize> {
+        self.trials
+            .iter()
+            .filter(|trial| trial.result.is_ok())
+            .map(|trial| trial.size)
+            .min()
+    }
+}
+
+impl fmt::Display for KeySizeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:>4} | {:>5} | {:>10}", "size", "works", "setup_ms")?;
+        for trial in &self.trials {
+            let setup_ms = trial
+                .setup
+                .map_or_else(|| "-".to_owned(), |setup| setup.as_millis().to_string());
+            writeln!(
+                f,
+                "{:>4} | {:>5} | {setup_ms:>10}",
+                trial.size,
+                trial.result.is_ok()
+            )?;
+        }
+
+        match self.smallest_working() {
+            Some(size) => writeln!(f, "smallest working primary commitment key size: {size}"),
+            None => writeln!(f, "none of the tried sizes works"),
+        }
+    }
+}
+
+/// Run `circuit` with each of `sizes` as the primary commitment key size
+///
+/// Each run is the shortest pipeline: the base step and its verification. Keys already in the
+/// cache dir are loaded instead of generated, but every size is a separate cache entry: sirius
+/// v0.1.1 can't cut a smaller key out of a larger one.
+pub fn compare_key_sizes(
+    circuit: &CircuitSpec,
+    config: &RunConfig,
+    sizes: &[usize],
+) -> KeySizeReport {
+    let trials = sizes
+        .iter()
+        .map(|&size| {
+            let config = RunConfig {
+                primary_commitment_key_size: size,
+                fold_step_count: 1,
+                ..config.clone()
+            };
+
+            let mut setup_start = None;
+            let mut setup = None;
+            let result = run_circuit(
+                circuit,
+                &config,
+                Some(&mut |event: StepEvent| match event {
+                    StepEvent::SetupStarted => setup_start = Some(Instant::now()),
+                    StepEvent::SetupFinished => setup = setup_start.map(|start| start.elapsed()),
+                    StepEvent::StepFolded { .. } | StepEvent::FoldingFinished { .. } => {}
+                }),
+            )
+            .map(drop);
+
+            KeySizeTrial {
+                size,
+                setup,
+                result,
+            }
+        })
+        .collect();
+
+    KeySizeReport { trials }
+}
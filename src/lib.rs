@@ -0,0 +1,1624 @@
+//! Fibonacci (and other) step circuits folded with Sirius, with the harness that sets up, folds
+//! and verifies their IVC
+//!
+//! The `sirius-fibonacci-example` binary is the command line front end of this library.
+
+use std::{array, ops::Add, time::Instant};
+
+use serde::{Deserialize, Serialize};
+use sirius::{
+    ff::Field,
+    halo2_proofs::{
+        plonk::{Advice, Column, Selector},
+        poly::Rotation,
+    },
+    ivc::{
+        step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+        SynthesisError,
+    },
+    prelude::{
+        bn256::{C1Scalar, C2Scalar},
+        PrimeField, StepCircuit,
+    },
+};
+
+pub mod batch;
+pub mod block_sizes;
+pub mod build_info;
+pub mod bundle;
+pub mod bundle_diff;
+mod cache;
+pub mod circuits;
+pub mod compare_curves;
+pub mod config;
+pub mod constraint_graph;
+pub mod curve;
+mod describe;
+pub mod diagnostic;
+pub mod env_info;
+pub mod error;
+pub mod estimate;
+pub mod field;
+pub mod gate_degree;
+pub mod input_stream;
+pub mod key_sizes;
+pub mod metrics;
+pub mod mock;
+pub mod output;
+pub mod pp_digest;
+#[cfg(feature = "profiling")]
+pub mod profiling;
+pub mod progress;
+pub mod remedy;
+pub mod repeat;
+pub mod runner;
+pub mod secondary;
+pub mod seed;
+pub mod snapshot;
+#[doc(hidden)]
+pub mod testing;
+pub mod transcript;
+pub mod verification;
+pub mod verify_cache;
+pub mod warning;
+
+use circuits::ReferenceStep;
+use config::{Check, RunConfig};
+use describe::{IvcDescription, SideDescription, StepCircuitShape};
+use diagnostic::FailureDiagnostic;
+use error::ExampleError;
+use gate_degree::GateDegrees;
+use metrics::METRICS;
+use output::Phase;
+use progress::StepEvent;
+use runner::{CommitmentKeys, FoldRunner};
+use verification::VerificationReport;
+
+/// Number of folding steps
+///
+/// Includes the base step made by `IVC::new`, so `1` proves just `z_1 = F(z_0)`:
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     config::RunConfig, run_circuit, testing::test_cache_dir, CircuitSpec,
+/// # };
+/// let config = RunConfig {
+///     fold_step_count: 1,
+///     cache_dir: test_cache_dir(),
+///     ..RunConfig::default()
+/// };
+/// let summary = run_circuit(&CircuitSpec::Fibonacci, &config, None).unwrap();
+///
+/// // No fold step, the IVC of the base step alone is verified
+/// assert_eq!(summary.steps, 1);
+/// assert!(summary.verification.is_some());
+/// // `z_0 = [0, 1]` advanced by exactly one block of `ELEMENTS_NUM` elements, to #10 and #11
+/// assert_eq!(summary.z_out, [55, 89].map(C1Scalar::from));
+/// ```
+pub const FOLD_STEP_COUNT: usize = 5;
+
+/// Number of Fibonacci sequence elements proven by one step of the primary circuit
+pub const ELEMENTS_NUM: usize = 10;
+
+// An empty block would only copy `z_i` to `z_out`, with no gate enforced
+const _: () = assert!(ELEMENTS_NUM >= 1, "ELEMENTS_NUM must be at least 1");
+
+/// Largest block that `--prove-length` puts in one step
+///
+/// With the minimal primary table size this still leaves plenty of rows to the folding part of the
+/// step circuit
+pub const MAX_ELEMENTS_PER_STEP: usize = 1000;
+
+// === PRIMARY ===
+
+/// Arity : Input/output size per fold-step for primary step-circuit
+pub const A1: usize = 2;
+
+/// Key size for Primary Circuit
+///
+/// This is the minimum value, for your circuit you may get the output that the key size is
+/// insufficient, then increase this constant
+pub const PRIMARY_COMMITMENT_KEY_SIZE: usize = 20;
+
+/// Table size for Primary Circuit
+///
+/// Requires at least 17, for service purposes, but if the primary requires more, increase the
+/// constant
+pub const PRIMARY_CIRCUIT_TABLE_SIZE: usize = 17;
+
+// === SECONDARY ===
+
+/// Arity : Input/output size per fold-step for secondary step-circuit
+/// For tivial case it can be any number
+///
+/// Everything on the secondary side follows this constant, including the default
+/// `--secondary-z0`, so changing it takes no other edit
+pub const A2: usize = 1;
+
+/// Default input to be passed on the zero step to the secondary circuit
+pub const SECONDARY_Z_0: [C2Scalar; A2] = [C2Scalar::ZERO; A2];
+
+/// Table size for Primary Circuit
+///
+/// Requires at least 17, for service purposes, but if the primary requires more, increase the
+/// constant
+pub const SECONDARY_CIRCUIT_TABLE_SIZE: usize = 17;
+
+/// Key size for Secondary Circuit
+///
+/// This is the minimum value, for your circuit you may get the output that the key size is
+/// insufficient, then increase this constant
+pub const SECONDARY_COMMITMENT_KEY_SIZE: usize = 20;
+
+/// Iterator for generating Fibonacci sequence values.
+///
+/// Given two initial values, it produces subsequent Fibonacci numbers by
+/// summing the two preceding numbers.
+///
+/// # Example
+/// ```
+/// # use sirius_fibonacci_example::FibonacciIter;
+/// const EXPECTED: [u64; 20] = [
+///     0, 1, 1, 2, 3, 5, 8, 13, 21, 34,
+///     55, 89, 144, 233, 377, 610, 987,
+///     1597, 2584, 4181,
+/// ];
+/// let actual = FibonacciIter(0, 1).take(20).collect::<Vec<_>>();
+/// assert_eq!(&actual, &EXPECTED);
+/// ```
+pub struct FibonacciIter<F>(pub F, pub F);
+
+impl<F: Copy> FibonacciIter<F> {
+    /// Restart the sequence from `a, b`, to reuse the iterator
+    ///
+    /// ```
+    /// # use sirius_fibonacci_example::FibonacciIter;
+    /// let mut iter = FibonacciIter(0, 1);
+    /// assert_eq!(iter.by_ref().take(5).collect::<Vec<_>>(), [0, 1, 1, 2, 3]);
+    ///
+    /// iter.reset(2, 1);
+    /// assert_eq!(iter.by_ref().take(5).collect::<Vec<_>>(), [2, 1, 3, 4, 7]);
+    /// ```
+    pub fn reset(&mut self, a: F, b: F) {
+        self.0 = a;
+        self.1 = b;
+    }
+
+    /// The next two values, without advancing
+    ///
+    /// ```
+    /// # use sirius_fibonacci_example::FibonacciIter;
+    /// let mut iter = FibonacciIter(0, 1);
+    /// iter.nth(9);
+    /// assert_eq!(iter.peek(), (55, 89));
+    /// assert_eq!(iter.next(), Some(55));
+    /// ```
+    pub fn peek(&self) -> (F, F) {
+        (self.0, self.1)
+    }
+}
+
+impl<F: Add<Output = F> + Copy> Iterator for FibonacciIter<F> {
+    type Item = F;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cur = self.0;
+
+        self.0 = self.1;
+        self.1 = cur + self.1;
+
+        Some(cur)
+    }
+}
+
+/// Configuration for the Fibonacci circuit, which includes the selectors and the advice columns
+/// to hold intermediate values of the Fibonacci sequence.
+///
+/// The block of a step is laid out row-major across the columns: element `i` of the block is in
+/// the column `i % n`, on the row `i / n` of the region.
+#[derive(Debug, Clone)]
+pub struct FibonacciConfig {
+    /// Selectors used to activate the gate that enforces the Fibonacci relation, one per column.
+    s: Vec<Selector>,
+    /// Advice columns to store the current and previous Fibonacci numbers.
+    e: Vec<Column<Advice>>,
+}
+
+impl FibonacciConfig {
+    /// Configure the block across `n` advice columns, so that it takes about `n` times fewer rows
+    ///
+    /// The gate of each column references the two previous elements of the sequence, which are
+    /// the cells to its left on the same row, wrapping around to the end of the row above. One
+    /// column is the layout of [`FibonacciCircuit`], more are that of [`WideFibonacciCircuit`], with
+    /// the same block in fewer rows:
+    ///
+    /// ```
+    /// # use sirius::{ivc::step_circuit::ConstraintSystem, prelude::bn256::C1Scalar};
+    /// # use sirius_fibonacci_example::FibonacciConfig;
+    /// let rows = [1, 2, 4].map(|n| {
+    ///     let mut cs = ConstraintSystem::<C1Scalar>::default();
+    ///     FibonacciConfig::with_columns(&mut cs, n).rows(10)
+    /// });
+    /// // The 10 elements and the 2 of `z_i`
+    /// assert_eq!(rows, [12, 6, 3]);
+    /// ```
+    ///
+    /// Every column of the block takes part in the copy constraints, see the comment on
+    /// `enable_equality` below. Without it, copying `z_i` into the block doesn't synthesize:
+    ///
+    /// ```
+    /// # use sirius::{
+    /// #     halo2_proofs::plonk::{Advice, Column},
+    /// #     ivc::{
+    /// #         step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+    /// #         SynthesisError,
+    /// #     },
+    /// #     prelude::{bn256::C1Scalar, StepCircuit},
+    /// # };
+    /// # use sirius_fibonacci_example::{
+    /// #     circuits::ReferenceStep, mock, FibonacciCircuit, FibonacciConfig,
+    /// # };
+    /// let mut cs = ConstraintSystem::<C1Scalar>::default();
+    /// FibonacciConfig::with_columns(&mut cs, 2);
+    /// assert_eq!(cs.permutation().get_columns().len(), 2);
+    ///
+    /// let z_0 = [0, 1].map(C1Scalar::from);
+    /// mock::check_step(5, &FibonacciCircuit::new(3), z_0).unwrap();
+    ///
+    /// // The first two cells of a block, in a column without equality
+    /// struct Unbound;
+    ///
+    /// impl StepCircuit<2, C1Scalar> for Unbound {
+    ///     type Config = Column<Advice>;
+    ///
+    ///     fn configure(cs: &mut ConstraintSystem<C1Scalar>) -> Self::Config {
+    ///         cs.advice_column()
+    ///     }
+    ///
+    ///     fn synthesize_step(
+    ///         &self,
+    ///         e: Self::Config,
+    ///         layouter: &mut impl Layouter<C1Scalar>,
+    ///         z_i: &[AssignedCell<C1Scalar, C1Scalar>; 2],
+    ///     ) -> Result<[AssignedCell<C1Scalar, C1Scalar>; 2], SynthesisError> {
+    ///         layouter.assign_region(
+    ///             || "unbound",
+    ///             |mut region| {
+    ///                 let [a, b] = z_i;
+    ///                 Ok([
+    ///                     a.copy_advice(|| "a", &mut region, e, 0)?,
+    ///                     b.copy_advice(|| "b", &mut region, e, 1)?,
+    ///                 ])
+    ///             },
+    ///         )
+    ///     }
+    /// }
+    ///
+    /// impl ReferenceStep<2, C1Scalar> for Unbound {
+    ///     fn reference_step(&self, z_i: &[C1Scalar; 2]) -> [C1Scalar; 2] {
+    ///         *z_i
+    ///     }
+    /// }
+    ///
+    /// mock::check_step(5, &Unbound, z_0).unwrap_err();
+    /// ```
+    ///
+    /// # Panics
+    /// If `n` is zero
+    pub fn with_columns<F: PrimeField>(cs: &mut ConstraintSystem<F>, n: usize) -> Self {
+        assert!(n >= 1, "the block needs at least one column");
+
+        let config = Self {
+            s: (0..n).map(|_| cs.selector()).collect(),
+            e: (0..n).map(|_| cs.advice_column()).collect(),
+        };
+
+        // Required: the first two cells of the block are bound to `z_i`, and the last two are
+        // returned as `z_out` and bound to the next step's input, both with copy constraints
+        // (`constrain_equal`). Without equality enabled on `e`, the block would not be connected
+        // to the folded state at all, and any two numbers could be "continued" by the gate.
+        for column in &config.e {
+            cs.enable_equality(*column);
+        }
+
+        cs.create_gate("fibo-block", |meta| {
+            (0..n)
+                .map(|column| {
+                    let s = meta.query_selector(config.s[column]);
+
+                    // The element `back` positions before the one in `column`, on this row or
+                    // one of the rows above
+                    let mut query = |back: usize| {
+                        let position = column as i32 - back as i32;
+                        meta.query_advice(
+                            config.e[position.rem_euclid(n as i32) as usize],
+                            Rotation(position.div_euclid(n as i32)),
+                        )
+                    };
+                    let e1 = query(2);
+                    let e2 = query(1);
+                    let e3 = query(0);
+
+                    s * (e1 + e2 - e3)
+                })
+                .collect::<Vec<_>>()
+        });
+
+        config
+    }
+
+    /// Rows taken by the block of `elements_num` elements
+    pub fn rows(&self, elements_num: usize) -> usize {
+        (elements_num + A1).div_ceil(self.e.len())
+    }
+
+    /// Assign the block of `elements_num` elements from `z_i` and return its last two cells
+    fn assign_block<F: PrimeField>(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; A1],
+        elements_num: usize,
+    ) -> Result<[AssignedCell<F, F>; A1], SynthesisError> {
+        let n = self.e.len();
+
+        let z_out = layouter.assign_region(
+            || "main",
+            |mut region| {
+                let [a, b] = z_i;
+
+                FibonacciIter(a.value().copied(), b.value().copied())
+                    .enumerate()
+                    .map(|(offset, value)| {
+                        let (row, column) = (offset / n, offset % n);
+                        let assigned = region.assign_advice(
+                            || "element of sequence",
+                            self.e[column],
+                            row,
+                            || value,
+                        )?;
+
+                        // Enforce equality constraints on the first two elements.
+                        //
+                        // For all other - enable gate with check. Note that the gate starts work
+                        // at index 2, because the gate references the cell two elements back
+                        match offset {
+                            0 => {
+                                region.constrain_equal(a.cell(), assigned.cell())?;
+                            }
+                            1 => {
+                                region.constrain_equal(b.cell(), assigned.cell())?;
+                            }
+                            _ => {
+                                self.s[column].enable(&mut region, row)?;
+                            }
+                        }
+
+                        Ok(assigned)
+                    })
+                    .take(elements_num + A1)
+                    .skip(elements_num) // We only need the last two elements (A1 := 2)
+                    .collect::<Result<Vec<_>, _>>()
+            },
+        )?;
+
+        Ok(z_out.try_into().unwrap())
+    }
+}
+
+/// The pair `elements_num` indices after `a, b`, the output of a step of either Fibonacci circuit
+fn fibonacci_step<F: PrimeField>([a, b]: &[F; A1], elements_num: usize) -> [F; A1] {
+    let mut iter = FibonacciIter(*a, *b).skip(elements_num);
+    [iter.next().unwrap(), iter.next().unwrap()]
+}
+
+/// Circuit that generates Fibonacci numbers over multiple folding steps.
+///
+/// The circuit is configured to prove a specified number of Fibonacci sequence elements in each
+/// step, defined by `elements_num`.
+#[derive(Clone)]
+pub struct FibonacciCircuit {
+    elements_num: usize,
+}
+
+impl FibonacciCircuit {
+    /// # Panics
+    /// If `elements_num` is zero: the step would only copy `z_i` to `z_out`, with no gate enforced
+    pub fn new(elements_num: usize) -> Self {
+        assert!(elements_num >= 1, "elements_num must be at least 1");
+
+        Self { elements_num }
+    }
+
+    /// Every value `synthesize_step` assigns to the column `e` for the input `z_i`, by offset
+    ///
+    /// The block is `elements_num + A1` long: two cells bound to `z_i`, then one gated cell per
+    /// proven element, the last two being `z_out`. For debugging, it is computed out of circuit
+    /// with the same iterator as the synthesis.
+    pub fn witness<F: PrimeField>(&self, [a, b]: &[F; A1]) -> Vec<F> {
+        FibonacciIter(*a, *b).take(self.elements_num + A1).collect()
+    }
+
+    /// Every element of the sequence from `z_0` up to the `z_out` of `steps` steps, the
+    /// `steps * elements_num` proven ones after the two of `z_0`
+    ///
+    /// Computed out of circuit with the same iterator as the synthesis, so its last two are the
+    /// cells the circuit assigns to `z_out` on the last step, and the `z_out` of the run:
+    ///
+    /// ```
+    /// # use sirius::prelude::bn256::C1Scalar;
+    /// # use sirius_fibonacci_example::{
+    /// #     config::RunConfig, mock, run_circuit, testing::test_cache_dir, CircuitSpec,
+    /// #     FibonacciCircuit, A1,
+    /// # };
+    /// let config = RunConfig {
+    ///     fold_step_count: 3,
+    ///     elements_num: 4,
+    ///     cache_dir: test_cache_dir(),
+    ///     ..RunConfig::default()
+    /// };
+    /// let circuit = FibonacciCircuit::new(config.elements_num);
+    /// let z_0 = [0, 1].map(C1Scalar::from);
+    ///
+    /// let sequence = circuit.sequence(&z_0, config.fold_step_count);
+    /// assert_eq!(sequence.len(), 3 * 4 + A1);
+    /// assert_eq!(sequence[..5], [0, 1, 1, 2, 3].map(C1Scalar::from));
+    ///
+    /// let summary = run_circuit(&CircuitSpec::Fibonacci, &config, None).unwrap();
+    /// assert!(summary.verification.is_some());
+    /// assert_eq!(sequence[sequence.len() - A1..], summary.z_out[..]);
+    ///
+    /// let mut z = z_0;
+    /// for _ in 0..config.fold_step_count {
+    ///     z = mock::check_step(5, &circuit, z).unwrap();
+    /// }
+    /// assert_eq!(sequence[sequence.len() - A1..], z);
+    /// assert_eq!(z, [144, 233].map(C1Scalar::from));
+    /// ```
+    pub fn sequence<F: PrimeField>(&self, [a, b]: &[F; A1], steps: usize) -> Vec<F> {
+        FibonacciIter(*a, *b)
+            .take(steps * self.elements_num + A1)
+            .collect()
+    }
+}
+
+impl<F: PrimeField> ReferenceStep<A1, F> for FibonacciCircuit {
+    /// Each step advances the pair by `elements_num` indices of the sequence
+    ///
+    /// The step hands its last two cells over as the next step's `z_i`, so `k` steps of
+    /// `elements_num` elements must reach the same pair as one block of `k * elements_num`. The
+    /// handoff across the `.skip` boundary is pinned on the cells the circuit assigns, captured by
+    /// the mock prover, the `z_out` of the first step being the `z_i` of the second:
+    ///
+    /// ```
+    /// # use sirius::prelude::bn256::C1Scalar;
+    /// # use sirius_fibonacci_example::{
+    /// #     mock, testing::prove_and_verify, FibonacciCircuit, FibonacciIter,
+    /// # };
+    /// let z_0 = [0, 1].map(C1Scalar::from);
+    /// let pair = |index| {
+    ///     let mut iter = FibonacciIter(0u64, 1).skip(index);
+    ///     [iter.next().unwrap(), iter.next().unwrap()].map(C1Scalar::from)
+    /// };
+    ///
+    /// // Two steps of 5 elements, the second one from the cells the first assigned
+    /// let half = FibonacciCircuit::new(5);
+    /// let first = mock::check_step(5, &half, z_0).unwrap();
+    /// assert_eq!(first, pair(5));
+    /// let handed_over = mock::check_step(5, &half, first).unwrap();
+    ///
+    /// let unbroken = mock::check_step(5, &FibonacciCircuit::new(10), z_0).unwrap();
+    /// assert_eq!(handed_over, unbroken);
+    /// assert_eq!(handed_over, pair(10));
+    ///
+    /// // Folded, the two steps verify and end at the same cells
+    /// assert_eq!(prove_and_verify(&half, z_0, 2), handed_over);
+    /// ```
+    fn reference_step(&self, z_i: &[F; A1]) -> [F; A1] {
+        fibonacci_step(z_i, self.elements_num)
+    }
+}
+
+impl<F: PrimeField> StepCircuit<A1, F> for FibonacciCircuit {
+    /// This is a configuration object that stores things like columns.
+    type Config = FibonacciConfig;
+
+    /// Configure the step circuit. This method initializes necessary
+    /// fixed columns and advice columns
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        FibonacciConfig::with_columns(cs, 1)
+    }
+
+    /// Sythesize the circuit for a computation step and return variable
+    /// that corresponds to the output of the step z_{i+1}
+    /// this method will be called when we synthesize the IVC_Circuit
+    ///
+    /// Return `z_out` result
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; 2],
+    ) -> Result<[AssignedCell<F, F>; 2], SynthesisError> {
+        config.assign_block(layouter, z_i, self.elements_num)
+    }
+}
+
+/// [`FibonacciCircuit`] with its block spread across `COLUMNS` advice columns, see
+/// [`FibonacciConfig::with_columns`]
+///
+/// The same step in about `COLUMNS` times fewer rows, so a table of a given size holds a block
+/// about `COLUMNS` times larger, for the price of more columns to commit to:
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     circuits::ReferenceStep, mock, testing::prove_and_verify, FibonacciCircuit,
+/// #     WideFibonacciCircuit,
+/// # };
+/// let z_0 = [0, 1].map(C1Scalar::from);
+/// let narrow = FibonacciCircuit::new(10);
+/// let wide = WideFibonacciCircuit::<4>::new(10);
+///
+/// // The wide layout is satisfiable and proves the same pair
+/// mock::check_step(5, &wide, z_0).unwrap();
+/// assert_eq!(wide.reference_step(&z_0), narrow.reference_step(&z_0));
+///
+/// // Folded too
+/// let z_out = prove_and_verify(&wide, z_0, 2);
+/// assert_eq!(z_out, narrow.reference_step(&narrow.reference_step(&z_0)));
+/// ```
+#[derive(Clone)]
+pub struct WideFibonacciCircuit<const COLUMNS: usize> {
+    elements_num: usize,
+}
+
+impl<const COLUMNS: usize> WideFibonacciCircuit<COLUMNS> {
+    /// # Panics
+    /// If `elements_num` is zero, like [`FibonacciCircuit::new`]
+    pub fn new(elements_num: usize) -> Self {
+        assert!(elements_num >= 1, "elements_num must be at least 1");
+
+        Self { elements_num }
+    }
+}
+
+impl<const COLUMNS: usize, F: PrimeField> ReferenceStep<A1, F> for WideFibonacciCircuit<COLUMNS> {
+    fn reference_step(&self, z_i: &[F; A1]) -> [F; A1] {
+        fibonacci_step(z_i, self.elements_num)
+    }
+}
+
+impl<const COLUMNS: usize, F: PrimeField> StepCircuit<A1, F> for WideFibonacciCircuit<COLUMNS> {
+    type Config = FibonacciConfig;
+
+    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
+        FibonacciConfig::with_columns(cs, COLUMNS)
+    }
+
+    fn synthesize_step(
+        &self,
+        config: Self::Config,
+        layouter: &mut impl Layouter<F>,
+        z_i: &[AssignedCell<F, F>; A1],
+    ) -> Result<[AssignedCell<F, F>; A1], SynthesisError> {
+        config.assign_block(layouter, z_i, self.elements_num)
+    }
+}
+
+/// Primary step circuit together with its parameters, enough to rebuild it
+///
+/// The circuit data that depends on the run (e.g. the Fibonacci block size) stays in
+/// [`RunConfig`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum CircuitSpec {
+    Fibonacci,
+    ModCounter {
+        modulus: u64,
+    },
+    DigitRoot {
+        base: u64,
+    },
+    BitAccum {
+        bit_width: usize,
+        xor_input: u64,
+    },
+    Sum {
+        addends: Vec<u64>,
+    },
+    SaturatingSum {
+        addends: Vec<u64>,
+        cap: u64,
+    },
+    RangeGuard {
+        readings: Vec<u64>,
+        lo: u64,
+        hi: u64,
+    },
+    FiboMod {
+        p: u64,
+    },
+    ChallengeAccum {
+        claims: Vec<u64>,
+    },
+    InputCommitment {
+        inputs: Vec<u64>,
+    },
+    CoeffRecurrence {
+        coefficients: Vec<u64>,
+    },
+    OpcodeLookup {
+        opcodes: Vec<u64>,
+    },
+    SortedPair {
+        values: Vec<u64>,
+    },
+    Average {
+        values: Vec<u64>,
+    },
+    Checksum {
+        #[serde(with = "field::serde_vec")]
+        words: Vec<C1Scalar>,
+    },
+    /// [`circuits::DenseLayerCircuit`] of width [`DENSE_LAYER_WIDTH`]
+    DenseLayer {
+        /// Weight matrix, row-major
+        weights: Vec<u64>,
+        bias: Vec<u64>,
+    },
+    IndexedFibonacci,
+    BoundedFibonacci {
+        bound: u64,
+    },
+    /// [`circuits::PidCircuit`] with the fixed-point gains `kp`, `ki` & `kd`
+    Pid {
+        errors: Vec<i64>,
+        kp: i64,
+        ki: i64,
+        kd: i64,
+    },
+    /// [`WideFibonacciCircuit`] with `columns` of [`WIDE_FIBONACCI_COLUMNS`]
+    WideFibonacci {
+        columns: usize,
+    },
+    /// [`circuits::SetAccumulatorCircuit`], one of `ops` per step
+    SetAccumulator {
+        ops: Vec<circuits::SetOp>,
+    },
+    /// [`circuits::DigitCountFiboCircuit`] with the brackets up to `max_digits` digits
+    DigitCountFibonacci {
+        max_digits: u32,
+    },
+    /// [`circuits::LongDivisionCircuit`] of the dividend `digits`, most significant first, by
+    /// `divisor`
+    LongDivision {
+        digits: Vec<u64>,
+        divisor: u64,
+    },
+    /// [`circuits::WindowAverageCircuit`] of width [`WINDOW_AVERAGE_WIDTH`], shifting in one of
+    /// `values` per step
+    WindowAverage {
+        values: Vec<u64>,
+        /// Weight of each window position, oldest first
+        weights: Vec<u32>,
+    },
+    /// [`circuits::StackMachineCircuit`] of depth [`STACK_MACHINE_DEPTH`], executing one of
+    /// `program` per step
+    StackMachine {
+        program: Vec<circuits::StackOp>,
+    },
+}
+
+/// Column counts of the [`CircuitSpec::WideFibonacci`] circuit, each is its own type
+pub const WIDE_FIBONACCI_COLUMNS: [usize; 4] = [1, 2, 4, 8];
+
+/// State size of the [`CircuitSpec::DenseLayer`] circuit
+pub const DENSE_LAYER_WIDTH: usize = 2;
+
+/// Window, and so state, size of the [`CircuitSpec::WindowAverage`] circuit
+pub const WINDOW_AVERAGE_WIDTH: usize = 4;
+
+/// Depth of the stack, and so state size, of the [`CircuitSpec::StackMachine`] circuit
+pub const STACK_MACHINE_DEPTH: usize = 4;
+
+impl CircuitSpec {
+    /// The same circuit with `inputs` as its per-step data, e.g. to record the inputs of a
+    /// [`run_circuit_streamed`] run, `None` if the circuit has no per-step data
+    pub fn with_step_inputs(&self, inputs: Vec<u64>) -> Option<Self> {
+        Some(match self {
+            Self::Sum { .. } => Self::Sum { addends: inputs },
+            Self::SaturatingSum { cap, .. } => Self::SaturatingSum {
+                addends: inputs,
+                cap: *cap,
+            },
+            Self::RangeGuard { lo, hi, .. } => Self::RangeGuard {
+                readings: inputs,
+                lo: *lo,
+                hi: *hi,
+            },
+            Self::ChallengeAccum { .. } => Self::ChallengeAccum { claims: inputs },
+            Self::InputCommitment { .. } => Self::InputCommitment { inputs },
+            Self::CoeffRecurrence { .. } => Self::CoeffRecurrence {
+                coefficients: inputs,
+            },
+            Self::OpcodeLookup { .. } => Self::OpcodeLookup { opcodes: inputs },
+            Self::SortedPair { .. } => Self::SortedPair { values: inputs },
+            Self::Average { .. } => Self::Average { values: inputs },
+            Self::Checksum { .. } => Self::Checksum {
+                words: inputs.into_iter().map(C1Scalar::from).collect(),
+            },
+            // Two's complement, as `run_circuit_streamed` reads them
+            Self::Pid { kp, ki, kd, .. } => Self::Pid {
+                errors: inputs.into_iter().map(|input| input as i64).collect(),
+                kp: *kp,
+                ki: *ki,
+                kd: *kd,
+            },
+            Self::LongDivision { divisor, .. } => Self::LongDivision {
+                digits: inputs,
+                divisor: *divisor,
+            },
+            Self::WindowAverage { weights, .. } => Self::WindowAverage {
+                values: inputs,
+                weights: weights.clone(),
+            },
+            Self::Fibonacci
+            | Self::ModCounter { .. }
+            | Self::DigitRoot { .. }
+            | Self::BitAccum { .. }
+            | Self::FiboMod { .. }
+            | Self::DenseLayer { .. }
+            | Self::IndexedFibonacci
+            | Self::BoundedFibonacci { .. }
+            | Self::SetAccumulator { .. }
+            | Self::WideFibonacci { .. }
+            | Self::DigitCountFibonacci { .. }
+            | Self::StackMachine { .. } => return None,
+        })
+    }
+
+    /// Reject an empty list of per-step data, which [`run_circuit`] has nothing to cycle through
+    ///
+    /// ```
+    /// # use sirius::prelude::bn256::C1Scalar;
+    /// # use sirius_fibonacci_example::{
+    /// #     config::RunConfig, error::ExampleError, run_circuit, testing::test_cache_dir,
+    /// #     CircuitSpec,
+    /// # };
+    /// let config = RunConfig {
+    ///     primary_z_0: vec![C1Scalar::from(0)],
+    ///     cache_dir: test_cache_dir(),
+    ///     ..RunConfig::default()
+    /// };
+    /// let empty = CircuitSpec::Sum { addends: vec![] };
+    /// let err = run_circuit(&empty, &config, None).err().unwrap();
+    /// assert!(matches!(err, ExampleError::InvalidConfig(_)), "{err}");
+    ///
+    /// assert!(CircuitSpec::Sum { addends: vec![3] }.ensure_step_inputs().is_ok());
+    /// assert!(CircuitSpec::Fibonacci.ensure_step_inputs().is_ok());
+    /// ```
+    pub fn ensure_step_inputs(&self) -> Result<(), ExampleError> {
+        let (name, len) = match self {
+            Self::Sum { addends } | Self::SaturatingSum { addends, .. } => {
+                ("addends", addends.len())
+            }
+            Self::RangeGuard { readings, .. } => ("readings", readings.len()),
+            Self::ChallengeAccum { claims } => ("claims", claims.len()),
+            Self::InputCommitment { inputs } => ("inputs", inputs.len()),
+            Self::CoeffRecurrence { coefficients } => ("coefficients", coefficients.len()),
+            Self::OpcodeLookup { opcodes } => ("opcodes", opcodes.len()),
+            Self::SortedPair { values }
+            | Self::Average { values }
+            | Self::WindowAverage { values, .. } => ("values", values.len()),
+            Self::Checksum { words } => ("words", words.len()),
+            Self::Pid { errors, .. } => ("errors", errors.len()),
+            Self::SetAccumulator { ops } => ("ops", ops.len()),
+            Self::LongDivision { digits, .. } => ("digits", digits.len()),
+            Self::StackMachine { program } => ("program", program.len()),
+            Self::Fibonacci
+            | Self::ModCounter { .. }
+            | Self::DigitRoot { .. }
+            | Self::BitAccum { .. }
+            | Self::FiboMod { .. }
+            | Self::DenseLayer { .. }
+            | Self::IndexedFibonacci
+            | Self::BoundedFibonacci { .. }
+            | Self::WideFibonacci { .. }
+            | Self::DigitCountFibonacci { .. } => return Ok(()),
+        };
+        if len == 0 {
+            return Err(ExampleError::InvalidConfig(format!(
+                "the per-step `{name}` of the circuit are empty, at least one is required"
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn arity(&self) -> usize {
+        match self {
+            Self::Fibonacci | Self::WideFibonacci { .. } => A1,
+            Self::ChallengeAccum { .. }
+            | Self::FiboMod { .. }
+            | Self::CoeffRecurrence { .. }
+            | Self::SortedPair { .. }
+            | Self::Average { .. }
+            | Self::LongDivision { .. } => 2,
+            Self::DenseLayer { .. } => DENSE_LAYER_WIDTH,
+            Self::WindowAverage { .. } => WINDOW_AVERAGE_WIDTH,
+            Self::StackMachine { .. } => STACK_MACHINE_DEPTH,
+            Self::IndexedFibonacci => 4,
+            Self::BoundedFibonacci { .. } | Self::Pid { .. } | Self::DigitCountFibonacci { .. } => {
+                3
+            }
+            Self::ModCounter { .. }
+            | Self::DigitRoot { .. }
+            | Self::BitAccum { .. }
+            | Self::Sum { .. }
+            | Self::SaturatingSum { .. }
+            | Self::RangeGuard { .. }
+            | Self::InputCommitment { .. }
+            | Self::OpcodeLookup { .. }
+            | Self::Checksum { .. }
+            | Self::SetAccumulator { .. } => 1,
+        }
+    }
+
+    /// Constraint system the step circuit configures, which only depends on its type, not on its
+    /// data
+    pub fn constraint_system(&self) -> Result<ConstraintSystem<C1Scalar>, ExampleError> {
+        use circuits::*;
+
+        fn configured<const ARITY: usize, SC: StepCircuit<ARITY, C1Scalar>>(
+        ) -> ConstraintSystem<C1Scalar> {
+            let mut cs = ConstraintSystem::default();
+            SC::configure(&mut cs);
+            cs
+        }
+
+        Ok(match self {
+            Self::Fibonacci => configured::<A1, FibonacciCircuit>(),
+            Self::ModCounter { .. } => configured::<1, ModCounterCircuit>(),
+            Self::DigitRoot { .. } => configured::<1, DigitRootCircuit>(),
+            Self::BitAccum { .. } => configured::<1, BitAccumCircuit>(),
+            Self::Sum { .. } => configured::<1, SumCircuit>(),
+            Self::FiboMod { .. } => configured::<2, FiboModCircuit>(),
+            Self::RangeGuard { .. } => configured::<1, RangeGuardCircuit>(),
+            Self::SaturatingSum { .. } => configured::<1, SaturatingSumCircuit>(),
+            Self::ChallengeAccum { .. } => configured::<2, ChallengeAccumCircuit>(),
+            Self::InputCommitment { .. } => configured::<1, InputCommitmentCircuit>(),
+            Self::CoeffRecurrence { .. } => configured::<2, CoeffRecurrenceCircuit>(),
+            Self::OpcodeLookup { .. } => configured::<1, OpcodeLookupCircuit>(),
+            Self::SortedPair { .. } => configured::<2, SortedPairCircuit>(),
+            Self::Average { .. } => configured::<2, AverageCircuit>(),
+            Self::Checksum { .. } => configured::<1, ChecksumCircuit<C1Scalar>>(),
+            Self::DenseLayer { .. } => {
+                configured::<DENSE_LAYER_WIDTH, DenseLayerCircuit<DENSE_LAYER_WIDTH, C1Scalar>>()
+            }
+            Self::IndexedFibonacci => configured::<4, IndexedFiboCircuit>(),
+            Self::BoundedFibonacci { .. } => configured::<3, BoundedFiboCircuit>(),
+            Self::WideFibonacci { columns } => match columns {
+                1 => configured::<A1, FibonacciCircuit>(),
+                2 => configured::<A1, WideFibonacciCircuit<2>>(),
+                4 => configured::<A1, WideFibonacciCircuit<4>>(),
+                8 => configured::<A1, WideFibonacciCircuit<8>>(),
+                _ => {
+                    return Err(ExampleError::InvalidConfig(format!(
+                        "the wide fibonacci circuit takes {WIDE_FIBONACCI_COLUMNS:?} columns, got \
+                         {columns}"
+                    )))
+                }
+            },
+            Self::SetAccumulator { .. } => configured::<1, SetAccumulatorCircuit>(),
+            Self::Pid { .. } => configured::<3, PidCircuit>(),
+            Self::DigitCountFibonacci { .. } => configured::<3, DigitCountFiboCircuit>(),
+            Self::LongDivision { .. } => configured::<2, LongDivisionCircuit>(),
+            Self::WindowAverage { .. } => {
+                configured::<WINDOW_AVERAGE_WIDTH, WindowAverageCircuit<WINDOW_AVERAGE_WIDTH>>()
+            }
+            Self::StackMachine { .. } => {
+                configured::<STACK_MACHINE_DEPTH, StackMachineCircuit<STACK_MACHINE_DEPTH>>()
+            }
+        })
+    }
+
+    /// Degree of the gates of the step circuit, see [`CircuitSpec::constraint_system`]
+    pub fn gate_degrees(&self) -> Result<GateDegrees, ExampleError> {
+        Ok(GateDegrees::from_constraint_system(
+            &self.constraint_system()?,
+        ))
+    }
+}
+
+/// Pick the block size and step count that prove the first `len` elements of the sequence
+///
+/// Uses the fewest steps of at most [`MAX_ELEMENTS_PER_STEP`] elements, spread evenly, so that
+/// `steps * elements_num >= len`. Returns `(elements_num, steps)`.
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{plan_sequence, testing::prove_and_verify, FibonacciCircuit};
+/// assert_eq!(plan_sequence(1), (1, 1));
+/// assert_eq!(plan_sequence(1000), (1000, 1));
+/// // Rounded up to whole steps: the pair proven is #1002 and #1003
+/// assert_eq!(plan_sequence(1001), (501, 2));
+/// assert_eq!(plan_sequence(2500), (834, 3));
+///
+/// let (elements_num, steps) = plan_sequence(1000);
+/// let z_out = prove_and_verify(
+///     &FibonacciCircuit::new(elements_num),
+///     [C1Scalar::from(0), C1Scalar::from(1)],
+///     steps,
+/// );
+///
+/// // Elements #1000 and #1001 of the sequence, computed in the field
+/// let (mut a, mut b) = (C1Scalar::from(0), C1Scalar::from(1));
+/// for _ in 0..1000 {
+///     (a, b) = (b, a + b);
+/// }
+/// assert_eq!(z_out, [a, b]);
+/// ```
+pub fn plan_sequence(len: usize) -> (usize, usize) {
+    let steps = len.div_ceil(MAX_ELEMENTS_PER_STEP).max(1);
+    (len.div_ceil(steps).max(1), steps)
+}
+
+/// Result of a successful [`run_ivc`]
+pub struct RunSummary {
+    /// Steps actually folded, including the base step
+    pub steps: usize,
+    /// Output of the last step, computed with [`ReferenceStep`], has the circuit arity elements
+    pub z_out: Vec<C1Scalar>,
+    /// Checks of the final verification, all passed; `None` if it was skipped with
+    /// [`RunConfig::no_verify`], the IVC is then unverified
+    ///
+    /// ```
+    /// # use sirius_fibonacci_example::{
+    /// #     config::RunConfig, metrics::METRICS, run_circuit, CircuitSpec,
+    /// # };
+    /// let cache = tempfile::tempdir().unwrap();
+    /// let config = RunConfig {
+    ///     no_verify: true,
+    ///     cache_dir: cache.path().to_path_buf(),
+    ///     ..RunConfig::default()
+    /// };
+    ///
+    /// let summary = run_circuit(&CircuitSpec::Fibonacci, &config, None).unwrap();
+    /// // Every step is folded, but `IVC::verify` is never called
+    /// assert_eq!(summary.steps, config.fold_step_count);
+    /// assert!(summary.verification.is_none());
+    /// assert_eq!(METRICS.verifications_succeeded_total.get(), 0);
+    /// ```
+    pub verification: Option<VerificationReport>,
+    /// Steps counted by the secondary circuit, `None` if it doesn't count them, see
+    /// [`secondary::SecondaryCircuit::counted_steps`]
+    ///
+    /// Counted from the state [`runner::FoldRunner::secondary_z_out`] follows with the reference
+    /// step of the secondary circuit, alongside the fold: sirius v0.1.1 doesn't expose the state
+    /// of the IVC, so this is not read from the proof.
+    ///
+    /// ```
+    /// # use sirius_fibonacci_example::{
+    /// #     config::RunConfig, run_circuit, secondary::SecondaryCircuit, CircuitSpec,
+    /// # };
+    /// let cache = tempfile::tempdir().unwrap();
+    /// let config = RunConfig {
+    ///     fold_step_count: 5,
+    ///     secondary_circuit: SecondaryCircuit::Counter,
+    ///     cache_dir: cache.path().to_path_buf(),
+    ///     ..RunConfig::default()
+    /// };
+    ///
+    /// let summary = run_circuit(&CircuitSpec::Fibonacci, &config, None).unwrap();
+    /// // The base step and 4 folds
+    /// assert_eq!(summary.recorded_steps, Some(5));
+    /// assert_eq!(summary.recorded_steps, Some(summary.steps));
+    /// ```
+    pub recorded_steps: Option<usize>,
+}
+
+/// Step circuit that [`run_fibonacci_ivc`] can prove: a pair of the sequence in, the pair
+/// `elements_num` indices later out
+///
+/// Fixes the arity to [`A1`] at the type level, so a circuit of another arity is rejected with
+/// an error naming this trait, instead of a const-generic mismatch inside [`run_ivc`]:
+///
+/// ```compile_fail,E0277
+/// # use sirius_fibonacci_example::{circuits::SumCircuit, config::RunConfig, run_fibonacci_ivc};
+/// // `SumCircuit` has arity 1: "the trait bound `SumCircuit: FibStepCircuit` is not satisfied"
+/// run_fibonacci_ivc(&RunConfig::default(), &SumCircuit::new(3), None);
+/// ```
+pub trait FibStepCircuit: StepCircuit<A1, C1Scalar> + ReferenceStep<A1, C1Scalar> + Clone {}
+
+impl FibStepCircuit for FibonacciCircuit {}
+
+impl<const COLUMNS: usize> FibStepCircuit for WideFibonacciCircuit<COLUMNS> {}
+
+/// Setup, fold and verify the IVC of the Fibonacci step circuit `sc1` described by `config`
+///
+/// `progress` is called on every phase of the run, see [`run_ivc_per_step`]
+pub fn run_fibonacci_ivc(
+    config: &RunConfig,
+    sc1: &impl FibStepCircuit,
+    progress: Option<&mut dyn FnMut(StepEvent)>,
+) -> Result<RunSummary, ExampleError> {
+    let summary = run_ivc::<A1, _>(config, sc1, progress)?;
+
+    let [a, b] = summary.z_out[..] else {
+        unreachable!("fibonacci arity is {A1}")
+    };
+    let index = summary.steps * config.elements_num;
+    output::info(
+        Phase::Fold,
+        format_args!(
+            "proven elements #{index} = {} and #{} = {}",
+            config.field_format.display(&a),
+            index + 1,
+            config.field_format.display(&b)
+        ),
+    );
+
+    Ok(summary)
+}
+
+/// Setup, fold and verify the IVC of the primary step circuit `sc1` described by `config`, with
+/// the same circuit data on every step
+pub fn run_ivc<const ARITY: usize, SC>(
+    config: &RunConfig,
+    sc1: &SC,
+    progress: Option<&mut dyn FnMut(StepEvent)>,
+) -> Result<RunSummary, ExampleError>
+where
+    SC: StepCircuit<ARITY, C1Scalar> + ReferenceStep<ARITY, C1Scalar> + Clone,
+{
+    run_ivc_per_step(config, |_step| sc1.clone(), progress)
+}
+
+/// Setup, fold and verify the IVC described by `config`, where `circuit_for_step` builds the
+/// primary step circuit of each step, `0` being the base step
+///
+/// This is how per-step data gets into the circuit: `sirius` takes the circuit on every
+/// `fold_step`, so each step can use a different instance of the same type. Only the data may
+/// vary between steps, the shape of the circuit must stay the same, as the public parameters are
+/// built from the base step instance.
+///
+/// `progress`, if given, is called before and after the setup and after every fold step.
+pub fn run_ivc_per_step<const ARITY: usize, SC>(
+    config: &RunConfig,
+    mut circuit_for_step: impl FnMut(usize) -> SC,
+    progress: Option<&mut dyn FnMut(StepEvent)>,
+) -> Result<RunSummary, ExampleError>
+where
+    SC: StepCircuit<ARITY, C1Scalar> + ReferenceStep<ARITY, C1Scalar> + Clone,
+{
+    run_ivc_stream(config, |step| Ok(Some(circuit_for_step(step))), progress)
+}
+
+/// Same as [`run_ivc_per_step`], but the circuit data may run out: folding stops at the first
+/// step `next_circuit` has no circuit for, and the steps folded so far are verified
+///
+/// `next_circuit` is called right before each step is folded, so it may block, e.g. waiting for
+/// the next input of a stream. The base step must have a circuit.
+pub fn run_ivc_stream<const ARITY: usize, SC>(
+    config: &RunConfig,
+    mut next_circuit: impl FnMut(usize) -> Result<Option<SC>, ExampleError>,
+    mut progress: Option<&mut dyn FnMut(StepEvent)>,
+) -> Result<RunSummary, ExampleError>
+where
+    SC: StepCircuit<ARITY, C1Scalar> + ReferenceStep<ARITY, C1Scalar> + Clone,
+{
+    let mut emit = |event: StepEvent| {
+        if let Some(on_event) = progress.as_mut() {
+            on_event(event);
+        }
+    };
+
+    // Fail on an invalid config before the (possibly long) key setup
+    config.ensure_valid(ARITY, A2)?;
+
+    emit(StepEvent::SetupStarted);
+
+    let keys = CommitmentKeys::setup(config)?;
+    let base = next_circuit(0)?
+        .ok_or_else(|| ExampleError::InputStream("no input for the base step".to_owned()))?;
+    let mut runner = FoldRunner::new(&keys, config, &base)?;
+    emit(StepEvent::SetupFinished);
+
+    if config.describe_ivc {
+        let description = IvcDescription {
+            primary: SideDescription {
+                arity: ARITY,
+                table_size: config.primary_circuit_table_size,
+                commitment_key_size: config.primary_commitment_key_size,
+                shape: StepCircuitShape::of::<ARITY, C1Scalar, SC>(),
+            },
+            secondary: SideDescription {
+                arity: A2,
+                table_size: config.secondary_circuit_table_size,
+                commitment_key_size: config.secondary_commitment_key_size,
+                shape: config.secondary_circuit.shape(),
+            },
+        };
+        output::info(Phase::Setup, description.to_string().trim_end());
+    }
+
+    // With `fold_step_count == 1` the loop is empty and the IVC of the single base step made by
+    // `FoldRunner::new` is verified
+    let fold_start = Instant::now();
+    let mut folded = 0;
+    for step in 1..config.fold_step_count {
+        let steps = runner.current_index();
+
+        if let Some(max_time) = config
+            .max_time
+            .filter(|max_time| fold_start.elapsed().as_secs_f64() >= *max_time)
+        {
+            // In strict mode the partial run fails, with an error of its own for scripts to tell;
+            // going over the warning budget stays a budget failure
+            warning::warn(format_args!(
+                "folding time budget of {max_time}s exceeded after {steps} steps, stop folding"
+            ))
+            .map_err(|err| match err {
+                ExampleError::Strict(_) => ExampleError::Timeout { steps, max_time },
+                err => err,
+            })?;
+            break;
+        }
+
+        // This step's circuit data
+        let Some(sc1) = next_circuit(step)? else {
+            output::info(
+                Phase::Fold,
+                format_args!("input stream ended after {steps} steps, stop folding"),
+            );
+            break;
+        };
+
+        if config.halt_on_fixpoint && sc1.reference_step(runner.z_out()) == *runner.z_out() {
+            output::info(
+                Phase::Fold,
+                format_args!("fixpoint reached after {steps} steps, stop folding"),
+            );
+            break;
+        }
+
+        let step_start = Instant::now();
+
+        if let Err(err) = runner.step(&sc1) {
+            if !config.keep_going_on_verify_fail {
+                return Err(err);
+            }
+            // The steps before the failed one, to tell a broken step from an IVC already unsound
+            let verified = runner.assert_sound();
+            let checks = vec![
+                Check {
+                    name: "fold step",
+                    result: Err(err.to_string()),
+                },
+                Check {
+                    name: "ivc verify",
+                    result: verified.map_err(|err| err.to_string()),
+                },
+            ];
+            return Err(diagnose(config, &runner, checks, err));
+        }
+        folded += 1;
+
+        METRICS.folds_total.inc();
+        METRICS
+            .last_step_seconds
+            .set(step_start.elapsed().as_secs_f64());
+        emit(StepEvent::StepFolded {
+            step,
+            steps: config.fold_step_count,
+        });
+    }
+
+    emit(StepEvent::FoldingFinished {
+        steps: runner.current_index(),
+    });
+
+    let steps = runner.current_index();
+    // Length checked by `ensure_valid`
+    let secondary_z_0: [C2Scalar; A2] = config.secondary_z_0.clone().try_into().unwrap();
+    let recorded_steps = config
+        .secondary_circuit
+        .counted_steps(&secondary_z_0, runner.secondary_z_out());
+    let verification = if config.no_verify {
+        output::info(Phase::Fold, "verification skipped, the IVC is unverified");
+        None
+    } else {
+        let verify_start = Instant::now();
+        let verified = runner.assert_sound();
+        let verification = VerificationReport {
+            checks: vec![
+                Check {
+                    name: "ivc verify",
+                    result: verified.as_ref().map(drop).map_err(ToString::to_string),
+                },
+                config::check("step count", steps == folded + 1, || {
+                    format!(
+                        "the IVC is at step {steps}, but the base step and {folded} more were \
+                         folded"
+                    )
+                }),
+            ],
+            duration: verify_start.elapsed(),
+        };
+        // The sirius error carries the diagnostics of the failed side
+        if let Err(err) = verified.and_then(|()| verification.ensure_passed()) {
+            return Err(diagnose(config, &runner, verification.checks, err));
+        }
+        METRICS.verifications_succeeded_total.inc();
+        output::info(Phase::Fold, &verification);
+        Some(verification)
+    };
+
+    Ok(RunSummary {
+        steps,
+        z_out: runner.z_out().to_vec(),
+        verification,
+        recorded_steps,
+    })
+}
+
+/// `err` with a [`FailureDiagnostic`] of `runner` and `checks` if
+/// [`RunConfig::keep_going_on_verify_fail`] is set, `err` as is otherwise
+fn diagnose<'key, const ARITY: usize, SC>(
+    config: &RunConfig,
+    runner: &FoldRunner<'key, ARITY, SC>,
+    checks: Vec<Check>,
+    err: ExampleError,
+) -> ExampleError
+where
+    SC: StepCircuit<ARITY, C1Scalar> + ReferenceStep<ARITY, C1Scalar> + Clone + 'key,
+{
+    if !config.keep_going_on_verify_fail {
+        return err;
+    }
+
+    let diagnostic = FailureDiagnostic::new(
+        config,
+        runner.current_index(),
+        runner.z_out(),
+        runner.secondary_z_out(),
+        checks,
+    );
+    ExampleError::Diagnosed {
+        err: Box::new(err),
+        diagnostic: Box::new(diagnostic),
+    }
+}
+
+/// Run the circuit described by `circuit` with `config`
+///
+/// `progress` is called on every phase of the run, see [`run_ivc_per_step`]
+pub fn run_circuit(
+    circuit: &CircuitSpec,
+    config: &RunConfig,
+    progress: Option<&mut dyn FnMut(StepEvent)>,
+) -> Result<RunSummary, ExampleError> {
+    // Before building the circuit, which may reject parameters the config checks report better
+    config.ensure_valid(circuit.arity(), A2)?;
+    circuit.ensure_step_inputs()?;
+
+    match circuit {
+        CircuitSpec::Fibonacci => run_fibonacci_ivc(
+            config,
+            &FibonacciCircuit::new(config.elements_num),
+            progress,
+        ),
+        CircuitSpec::ModCounter { modulus } => run_ivc::<1, _>(
+            config,
+            &circuits::ModCounterCircuit::new(*modulus),
+            progress,
+        ),
+        CircuitSpec::DigitRoot { base } => {
+            run_ivc::<1, _>(config, &circuits::DigitRootCircuit::new(*base), progress)
+        }
+        CircuitSpec::BitAccum {
+            bit_width,
+            xor_input,
+        } => run_ivc::<1, _>(
+            config,
+            &circuits::BitAccumCircuit::new(*bit_width, *xor_input),
+            progress,
+        ),
+        CircuitSpec::Sum { addends } => run_ivc_per_step::<1, _>(
+            config,
+            |step| circuits::SumCircuit::new(addends[step % addends.len()]),
+            progress,
+        ),
+        CircuitSpec::FiboMod { p } => {
+            run_ivc::<2, _>(config, &circuits::FiboModCircuit::new(*p), progress)
+        }
+        CircuitSpec::RangeGuard { readings, lo, hi } => run_ivc_per_step::<1, _>(
+            config,
+            |step| circuits::RangeGuardCircuit::new(readings[step % readings.len()], *lo, *hi),
+            progress,
+        ),
+        CircuitSpec::SaturatingSum { addends, cap } => run_ivc_per_step::<1, _>(
+            config,
+            |step| circuits::SaturatingSumCircuit::new(addends[step % addends.len()], *cap),
+            progress,
+        ),
+        CircuitSpec::ChallengeAccum { claims } => run_ivc_per_step::<2, _>(
+            config,
+            |step| circuits::ChallengeAccumCircuit::new(claims[step % claims.len()]),
+            progress,
+        ),
+        CircuitSpec::InputCommitment { inputs } => run_ivc_per_step::<1, _>(
+            config,
+            |step| circuits::InputCommitmentCircuit::new(inputs[step % inputs.len()]),
+            progress,
+        ),
+        CircuitSpec::CoeffRecurrence { coefficients } => run_ivc_per_step::<2, _>(
+            config,
+            |step| circuits::CoeffRecurrenceCircuit::new(coefficients[step % coefficients.len()]),
+            progress,
+        ),
+        CircuitSpec::OpcodeLookup { opcodes } => run_ivc_per_step::<1, _>(
+            config,
+            |step| circuits::OpcodeLookupCircuit::new(opcodes[step % opcodes.len()]),
+            progress,
+        ),
+        CircuitSpec::SortedPair { values } => run_ivc_per_step::<2, _>(
+            config,
+            |step| circuits::SortedPairCircuit::new(values[step % values.len()]),
+            progress,
+        ),
+        CircuitSpec::Average { values } => run_ivc_per_step::<2, _>(
+            config,
+            |step| circuits::AverageCircuit::new(values[step % values.len()]),
+            progress,
+        ),
+        CircuitSpec::Checksum { words } => run_ivc_per_step::<1, _>(
+            config,
+            |step| circuits::ChecksumCircuit::new(words[step % words.len()]),
+            progress,
+        ),
+        CircuitSpec::DenseLayer { weights, bias } => {
+            let invalid = |what: &str, len: usize, expected: usize| {
+                ExampleError::InvalidConfig(format!(
+                    "the dense layer takes {expected} {what}, got {len}"
+                ))
+            };
+            if weights.len() != DENSE_LAYER_WIDTH * DENSE_LAYER_WIDTH {
+                return Err(invalid(
+                    "weights",
+                    weights.len(),
+                    DENSE_LAYER_WIDTH * DENSE_LAYER_WIDTH,
+                ));
+            }
+            if bias.len() != DENSE_LAYER_WIDTH {
+                return Err(invalid("biases", bias.len(), DENSE_LAYER_WIDTH));
+            }
+
+            let weights = array::from_fn(|row| {
+                array::from_fn(|col| C1Scalar::from(weights[row * DENSE_LAYER_WIDTH + col]))
+            });
+            let bias = array::from_fn(|row| C1Scalar::from(bias[row]));
+            run_ivc::<DENSE_LAYER_WIDTH, _>(
+                config,
+                &circuits::DenseLayerCircuit::new(weights, bias),
+                progress,
+            )
+        }
+        CircuitSpec::IndexedFibonacci => {
+            run_ivc::<4, _>(config, &circuits::IndexedFiboCircuit, progress)
+        }
+        CircuitSpec::BoundedFibonacci { bound } => {
+            run_ivc::<3, _>(config, &circuits::BoundedFiboCircuit::new(*bound), progress)
+        }
+        CircuitSpec::DigitCountFibonacci { max_digits } => {
+            if !(1..=circuits::MAX_DECIMAL_DIGITS).contains(max_digits) {
+                return Err(ExampleError::InvalidConfig(format!(
+                    "the digit brackets have 1 to {} digits, got {max_digits}",
+                    circuits::MAX_DECIMAL_DIGITS
+                )));
+            }
+            run_ivc::<3, _>(
+                config,
+                &circuits::DigitCountFiboCircuit::new(*max_digits),
+                progress,
+            )
+        }
+        CircuitSpec::WideFibonacci { columns } => {
+            let n = config.elements_num;
+            match columns {
+                1 => run_fibonacci_ivc(config, &FibonacciCircuit::new(n), progress),
+                2 => run_fibonacci_ivc(config, &WideFibonacciCircuit::<2>::new(n), progress),
+                4 => run_fibonacci_ivc(config, &WideFibonacciCircuit::<4>::new(n), progress),
+                8 => run_fibonacci_ivc(config, &WideFibonacciCircuit::<8>::new(n), progress),
+                _ => Err(ExampleError::InvalidConfig(format!(
+                    "the wide fibonacci circuit takes {WIDE_FIBONACCI_COLUMNS:?} columns, got \
+                     {columns}"
+                ))),
+            }
+        }
+        CircuitSpec::SetAccumulator { ops } => run_ivc_per_step::<1, _>(
+            config,
+            |step| circuits::SetAccumulatorCircuit::new(ops[step % ops.len()]),
+            progress,
+        ),
+        CircuitSpec::Pid { errors, kp, ki, kd } => {
+            let gains = circuits::PidGains {
+                kp: *kp,
+                ki: *ki,
+                kd: *kd,
+            };
+            run_ivc_per_step::<3, _>(
+                config,
+                |step| circuits::PidCircuit::new(errors[step % errors.len()], gains),
+                progress,
+            )
+        }
+        CircuitSpec::LongDivision { digits, divisor } => {
+            ensure_divisor(*divisor)?;
+            run_ivc_per_step::<2, _>(
+                config,
+                |step| circuits::LongDivisionCircuit::new(digits[step % digits.len()], *divisor),
+                progress,
+            )
+        }
+        CircuitSpec::WindowAverage { values, weights } => {
+            let weights = window_weights(weights)?;
+            run_ivc_per_step::<WINDOW_AVERAGE_WIDTH, _>(
+                config,
+                |step| circuits::WindowAverageCircuit::new(values[step % values.len()], weights),
+                progress,
+            )
+        }
+        CircuitSpec::StackMachine { program } => run_ivc_per_step::<STACK_MACHINE_DEPTH, _>(
+            config,
+            |step| circuits::StackMachineCircuit::new(program[step % program.len()]),
+            progress,
+        ),
+    }
+}
+
+/// Weights of the [`circuits::WindowAverageCircuit`], one per window position and not all zero
+fn window_weights(weights: &[u32]) -> Result<[u32; WINDOW_AVERAGE_WIDTH], ExampleError> {
+    let weights = <[u32; WINDOW_AVERAGE_WIDTH]>::try_from(weights).map_err(|_| {
+        ExampleError::InvalidConfig(format!(
+            "the window average takes {WINDOW_AVERAGE_WIDTH} weights, got {}",
+            weights.len()
+        ))
+    })?;
+    if weights.iter().all(|weight| *weight == 0) {
+        return Err(ExampleError::InvalidConfig(
+            "the window weights must not all be zero".to_owned(),
+        ));
+    }
+    Ok(weights)
+}
+
+/// The [`circuits::LongDivisionCircuit`] divides by at least `1`
+fn ensure_divisor(divisor: u64) -> Result<(), ExampleError> {
+    if divisor == 0 {
+        return Err(ExampleError::InvalidConfig(
+            "the long division divisor must be at least 1".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// Run the circuit described by `circuit` with `config`, taking the per-step data from `inputs`
+/// instead of the list in `circuit`
+///
+/// Each input is pulled right before its step is folded. Folding stops when `inputs` ends, after
+/// at most `config.fold_step_count` steps, and the steps folded so far are verified. Only the
+/// circuits with per-step data take inputs, see [`CircuitSpec::with_step_inputs`].
+pub fn run_circuit_streamed(
+    circuit: &CircuitSpec,
+    config: &RunConfig,
+    inputs: &mut dyn Iterator<Item = Result<u64, ExampleError>>,
+    progress: Option<&mut dyn FnMut(StepEvent)>,
+) -> Result<RunSummary, ExampleError> {
+    config.ensure_valid(circuit.arity(), A2)?;
+
+    let mut next = || inputs.next().transpose();
+    match circuit {
+        CircuitSpec::Sum { .. } => run_ivc_stream::<1, _>(
+            config,
+            |_step| Ok(next()?.map(circuits::SumCircuit::new)),
+            progress,
+        ),
+        CircuitSpec::SaturatingSum { cap, .. } => run_ivc_stream::<1, _>(
+            config,
+            |_step| Ok(next()?.map(|addend| circuits::SaturatingSumCircuit::new(addend, *cap))),
+            progress,
+        ),
+        CircuitSpec::RangeGuard { lo, hi, .. } => run_ivc_stream::<1, _>(
+            config,
+            |_step| Ok(next()?.map(|reading| circuits::RangeGuardCircuit::new(reading, *lo, *hi))),
+            progress,
+        ),
+        CircuitSpec::ChallengeAccum { .. } => run_ivc_stream::<2, _>(
+            config,
+            |_step| Ok(next()?.map(circuits::ChallengeAccumCircuit::new)),
+            progress,
+        ),
+        CircuitSpec::InputCommitment { .. } => run_ivc_stream::<1, _>(
+            config,
+            |_step| Ok(next()?.map(circuits::InputCommitmentCircuit::new)),
+            progress,
+        ),
+        CircuitSpec::CoeffRecurrence { .. } => run_ivc_stream::<2, _>(
+            config,
+            |_step| Ok(next()?.map(circuits::CoeffRecurrenceCircuit::new)),
+            progress,
+        ),
+        CircuitSpec::OpcodeLookup { .. } => run_ivc_stream::<1, _>(
+            config,
+            |_step| Ok(next()?.map(circuits::OpcodeLookupCircuit::new)),
+            progress,
+        ),
+        CircuitSpec::SortedPair { .. } => run_ivc_stream::<2, _>(
+            config,
+            |_step| Ok(next()?.map(circuits::SortedPairCircuit::new)),
+            progress,
+        ),
+        CircuitSpec::Average { .. } => run_ivc_stream::<2, _>(
+            config,
+            |_step| Ok(next()?.map(circuits::AverageCircuit::new)),
+            progress,
+        ),
+        CircuitSpec::Checksum { .. } => run_ivc_stream::<1, _>(
+            config,
+            |_step| Ok(next()?.map(|word| circuits::ChecksumCircuit::new(C1Scalar::from(word)))),
+            progress,
+        ),
+        CircuitSpec::Pid { kp, ki, kd, .. } => {
+            let gains = circuits::PidGains {
+                kp: *kp,
+                ki: *ki,
+                kd: *kd,
+            };
+            // Inputs are `u64`, read as two's complement to allow negative errors
+            run_ivc_stream::<3, _>(
+                config,
+                |_step| Ok(next()?.map(|error| circuits::PidCircuit::new(error as i64, gains))),
+                progress,
+            )
+        }
+        CircuitSpec::LongDivision { divisor, .. } => {
+            ensure_divisor(*divisor)?;
+            run_ivc_stream::<2, _>(
+                config,
+                |_step| Ok(next()?.map(|digit| circuits::LongDivisionCircuit::new(digit, *divisor))),
+                progress,
+            )
+        }
+        CircuitSpec::WindowAverage { weights, .. } => {
+            let weights = window_weights(weights)?;
+            run_ivc_stream::<WINDOW_AVERAGE_WIDTH, _>(
+                config,
+                |_step| Ok(next()?.map(|value| circuits::WindowAverageCircuit::new(value, weights))),
+                progress,
+            )
+        }
+        CircuitSpec::Fibonacci
+        | CircuitSpec::ModCounter { .. }
+        | CircuitSpec::DigitRoot { .. }
+        | CircuitSpec::BitAccum { .. }
+        | CircuitSpec::FiboMod { .. }
+        | CircuitSpec::DenseLayer { .. }
+        | CircuitSpec::IndexedFibonacci
+        | CircuitSpec::BoundedFibonacci { .. }
+        | CircuitSpec::WideFibonacci { .. }
+        | CircuitSpec::DigitCountFibonacci { .. } => Err(ExampleError::InvalidConfig(
+            "the circuit takes no per-step input".to_owned(),
+        )),
+        CircuitSpec::SetAccumulator { .. } => Err(ExampleError::InvalidConfig(
+            "the set operations of the set accumulator are not plain integers, pass them with \
+             `--set-ops`"
+                .to_owned(),
+        )),
+        CircuitSpec::StackMachine { .. } => Err(ExampleError::InvalidConfig(
+            "the instructions of the stack machine are not plain integers, pass them with \
+             `--program`"
+                .to_owned(),
+        )),
+    }
+}
@@ -1,249 +1,1670 @@
-use std::{ops::Add, path::Path};
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufRead, BufReader},
+    num::NonZeroUsize,
+    path::{Path, PathBuf},
+    process::{Command, ExitCode},
+};
+
+use clap::{
+    builder::{PossibleValue, PossibleValuesParser},
+    Parser, ValueEnum,
+};
+use sirius::prelude::bn256::{C1Scalar, C2Scalar};
+#[cfg(feature = "env-dump")]
+use sirius_fibonacci_example::env_info::EnvInfo;
+#[cfg(feature = "profiling")]
+use sirius_fibonacci_example::profiling::FoldProfiler;
+use sirius_fibonacci_example::{
+    batch::{self, BatchPolicy},
+    block_sizes,
+    build_info::{self, BuildInfo},
+    bundle::ProofBundle,
+    bundle_diff::BundleDiff,
+    circuits::{self, ReferenceStep},
+    compare_curves,
+    config::RunConfig,
+    constraint_graph::ConstraintGraph,
+    curve::{self, Cycle},
+    error::ExampleError,
+    estimate::CostEstimate,
+    field::{self, FieldFormat, ValueEncoding},
+    input_stream, key_sizes, metrics, mock, output, plan_sequence, pp_digest,
+    progress::{self, StepEvent},
+    repeat, run_circuit, run_circuit_streamed,
+    secondary::SecondaryCircuit,
+    seed::Seed,
+    snapshot::Snapshot,
+    transcript::PublicTranscript,
+    verify_cache::VerifyCache,
+    warning, CircuitSpec, FibonacciCircuit, RunSummary, A1, A2, ELEMENTS_NUM, FOLD_STEP_COUNT,
+    PRIMARY_CIRCUIT_TABLE_SIZE, PRIMARY_COMMITMENT_KEY_SIZE, SECONDARY_CIRCUIT_TABLE_SIZE,
+    SECONDARY_COMMITMENT_KEY_SIZE, SECONDARY_Z_0, WINDOW_AVERAGE_WIDTH,
+};
+use tracing_chrome::ChromeLayerBuilder;
+use tracing_subscriber::prelude::*;
+
+/// Primary step circuit of `--circuit`, registered once in [`CIRCUITS`]
+///
+/// `StepCircuit` has generic methods and an associated config, so it can't be a trait object.
+/// Instead the factory builds the [`CircuitSpec`] from the flags, which the library dispatches to
+/// the concrete circuit.
+struct CircuitEntry {
+    name: &'static str,
+    about: &'static str,
+    /// `z_0` used when `--z0` is not passed
+    default_z_0: &'static [u64],
+    /// What the gates of a step enforce, `--help-circuit` prints it
+    gate: &'static str,
+    /// Flag of the per-step inputs, `None` if every step is the same
+    step_inputs: Option<&'static str>,
+    /// Flags of an example run, after `--circuit`
+    example: &'static str,
+    /// The circuit with its parameter flags
+    spec: fn(&Args) -> CircuitSpec,
+}
 
-use sirius::{
-    ff::Field,
-    halo2_proofs::{
-        plonk::{Advice, Column, Selector},
-        poly::Rotation,
+/// Largest `--prime` whose Pisano period is reported before a `fibo-mod` run, it takes up to
+/// `6 p` reference steps
+const PISANO_PERIOD_MAX_PRIME: u64 = 1 << 20;
+
+/// Primary step circuits the example can fold, `--circuit` takes their names
+const CIRCUITS: &[CircuitEntry] = &[
+    CircuitEntry {
+        name: "fibonacci",
+        about: "`FibonacciCircuit`, `--elements-num` Fibonacci elements per step",
+        default_z_0: &[0, 1],
+        gate: "e(i) + e(i + 1) = e(i + 2) for every element of the block",
+        step_inputs: None,
+        example: "--elements-num 8 --fold-steps 3",
+        spec: |_| CircuitSpec::Fibonacci,
     },
-    ivc::{
-        step_circuit::{trivial, AssignedCell, ConstraintSystem, Layouter},
-        SynthesisError,
+    CircuitEntry {
+        name: "wide-fibonacci",
+        about: "`WideFibonacciCircuit`, the `fibonacci` block spread across `--columns` advice \
+                columns, in about `--columns` times fewer rows",
+        default_z_0: &[0, 1],
+        gate: "e(i) + e(i + 1) = e(i + 2) for every element of the block, across the columns",
+        step_inputs: None,
+        example: "--columns 4 --fold-steps 3",
+        spec: |args| CircuitSpec::WideFibonacci {
+            columns: args.columns,
+        },
     },
-    prelude::{
-        bn256::{new_default_pp, C1Affine, C1Scalar, C2Affine, C2Scalar},
-        CommitmentKey, PrimeField, StepCircuit, IVC,
+    CircuitEntry {
+        name: "mod-counter",
+        about: "`ModCounterCircuit`, counting modulo `--modulus`",
+        default_z_0: &[0],
+        gate: "z' = 0 if z + 1 = modulus, else z + 1",
+        step_inputs: None,
+        example: "--modulus 5 --fold-steps 7",
+        spec: |args| CircuitSpec::ModCounter {
+            modulus: args.modulus,
+        },
     },
-};
+    CircuitEntry {
+        name: "digit-root",
+        about: "`DigitRootCircuit`, converging to the digital root in base `--base`",
+        default_z_0: &[493193],
+        gate: "z = sum(digit_i base^i) and z' = sum(digit_i), with digit_i and base - 1 - digit_i range-checked",
+        step_inputs: None,
+        example: "--base 10 --z0 9875",
+        spec: |args| CircuitSpec::DigitRoot { base: args.base },
+    },
+    CircuitEntry {
+        name: "bit-accum",
+        about: "`BitAccumCircuit`, XOR-ing `--xor-input` into a `--bit-width`-bit state",
+        default_z_0: &[0b1100_1010],
+        gate: "z' = z XOR input, bit per bit: c = a + b - 2 a b with a, b binary",
+        step_inputs: None,
+        example: "--bit-width 8 --xor-input 255",
+        spec: |args| CircuitSpec::BitAccum {
+            bit_width: args.bit_width,
+            xor_input: args.xor_input,
+        },
+    },
+    CircuitEntry {
+        name: "sum",
+        about: "`SumCircuit`, adding a different one of `--addends` every step",
+        default_z_0: &[0],
+        gate: "z' = z + addend",
+        step_inputs: Some("--addends"),
+        example: "--addends 3,1,4 --fold-steps 3",
+        spec: |args| CircuitSpec::Sum {
+            addends: args.addends.clone(),
+        },
+    },
+    CircuitEntry {
+        name: "saturating-sum",
+        about: "`SaturatingSumCircuit`, adding `--addends` like `sum`, capped at `--cap`",
+        default_z_0: &[0],
+        gate: "z' = min(z + addend, cap), the branch picked by a range-checked difference",
+        step_inputs: Some("--addends"),
+        example: "--addends 4,4,4 --cap 10",
+        spec: |args| CircuitSpec::SaturatingSum {
+            addends: args.addends.clone(),
+            cap: args.cap,
+        },
+    },
+    CircuitEntry {
+        name: "fibo-mod",
+        about: "`FiboModCircuit`, the Fibonacci sequence modulo `--prime`, one element per step",
+        default_z_0: &[0, 1],
+        gate: "[a, b]' = [b, r] with a + b = p q + r, q binary and r < p",
+        step_inputs: None,
+        example: "--prime 11 --fold-steps 10",
+        spec: |args| CircuitSpec::FiboMod { p: args.prime },
+    },
+    CircuitEntry {
+        name: "range-guard",
+        about: "`RangeGuardCircuit`, checking a different one of `--readings` is within \
+                `--lo..=--hi` every step",
+        default_z_0: &[0],
+        gate: "reading - lo and hi - reading are range-checked, z' = z",
+        step_inputs: Some("--readings"),
+        example: "--readings 5,50,95 --lo 0 --hi 100",
+        spec: |args| CircuitSpec::RangeGuard {
+            readings: args.readings.clone(),
+            lo: args.lo,
+            hi: args.hi,
+        },
+    },
+    CircuitEntry {
+        name: "challenge-accum",
+        about: "`ChallengeAccumCircuit`, accumulating a different one of `--claims` every step",
+        default_z_0: &[0, 1],
+        gate: "challenge' = (challenge + claim)^5, acc' = acc + challenge' claim",
+        step_inputs: Some("--claims"),
+        example: "--claims 2,7,1",
+        spec: |args| CircuitSpec::ChallengeAccum {
+            claims: args.claims.clone(),
+        },
+    },
+    CircuitEntry {
+        name: "input-commitment",
+        about: "`InputCommitmentCircuit`, hashing a different one of `--inputs` into the state \
+                every step",
+        default_z_0: &[0],
+        gate: "h = (h + input + c_r)^5 for every round constant c_r from h = z, z' = h",
+        step_inputs: Some("--inputs"),
+        example: "--inputs 1,2,3",
+        spec: |args| CircuitSpec::InputCommitment {
+            inputs: args.inputs.clone(),
+        },
+    },
+    CircuitEntry {
+        name: "coeff-recurrence",
+        about: "`CoeffRecurrenceCircuit`, `e(n) = c_n * e(n - 1) + e(n - 2)` with a different one \
+                of `--coefficients` every step",
+        default_z_0: &[0, 1],
+        gate: "[a, b]' = [b, c b + a]",
+        step_inputs: Some("--coefficients"),
+        example: "--coefficients 1,2,2,2",
+        spec: |args| CircuitSpec::CoeffRecurrence {
+            coefficients: args.coefficients.clone(),
+        },
+    },
+    CircuitEntry {
+        name: "opcode-lookup",
+        about: "`OpcodeLookupCircuit`, appending a different one of `--opcodes` to a trace every \
+                step, each looked up in a fixed table of valid opcodes",
+        default_z_0: &[0],
+        gate: "trace' = trace * base + opcode, opcode looked up in the table of valid opcodes",
+        step_inputs: Some("--opcodes"),
+        example: "--opcodes 1,2,3",
+        spec: |args| CircuitSpec::OpcodeLookup {
+            opcodes: args.opcodes.clone(),
+        },
+    },
+    CircuitEntry {
+        name: "sorted-pair",
+        about: "`SortedPairCircuit`, proving a different one of `--values` is not below the \
+                previous one every step",
+        default_z_0: &[0, 0],
+        gate: "value - prev is range-checked, [prev, count]' = [value, count + 1]",
+        step_inputs: Some("--values"),
+        example: "--values 1,4,9,16",
+        spec: |args| CircuitSpec::SortedPair {
+            values: args.values.clone(),
+        },
+    },
+    CircuitEntry {
+        name: "average",
+        about: "`AverageCircuit`, averaging a different one of `--values` into the state every \
+                step, with a fixed-point average proven by multiplication",
+        default_z_0: &[0, 0],
+        gate: "[sum, count]' = [sum + value, count + 1], sum' 2^16 = average count' + rem with rem < count'",
+        step_inputs: Some("--values"),
+        example: "--values 10,20,30",
+        spec: |args| CircuitSpec::Average {
+            values: args.values.clone(),
+        },
+    },
+    CircuitEntry {
+        name: "checksum",
+        about:
+            "`ChecksumCircuit`, adding a different one of `--words` to a running checksum every \
+                step, checked with `--assert-checksum`",
+        default_z_0: &[0],
+        gate: "z' = z + word",
+        step_inputs: Some("--words"),
+        example: "--words 0x01,0x02 --assert-checksum 3 --fold-steps 2",
+        spec: |args| CircuitSpec::Checksum {
+            words: args.words.clone(),
+        },
+    },
+    CircuitEntry {
+        name: "dense-layer",
+        about: "`DenseLayerCircuit`, applying the linear layer `--weights`, `--bias` to the state \
+                every step",
+        default_z_0: &[1, 0],
+        gate: "z'_j = sum_k W_jk z_k + b_j for every output j",
+        step_inputs: None,
+        example: "--weights 2,0,0,3 --bias 1,1",
+        spec: |args| CircuitSpec::DenseLayer {
+            weights: args.weights.clone(),
+            bias: args.bias.clone(),
+        },
+    },
+    CircuitEntry {
+        name: "indexed-fibonacci",
+        about: "`IndexedFiboCircuit`, the Fibonacci sequence one element per step, with the index \
+                and its parity carried in the state",
+        default_z_0: &[0, 1, 0, 0],
+        gate: "[a, b, index, parity]' = [b, a + b, index + 1, 1 - parity]",
+        step_inputs: None,
+        example: "--fold-steps 8",
+        spec: |_| CircuitSpec::IndexedFibonacci,
+    },
+    CircuitEntry {
+        name: "bounded-fibonacci",
+        about: "`BoundedFiboCircuit`, the Fibonacci sequence one element per step, frozen once a \
+                value exceeds `--bound`",
+        default_z_0: &[0, 1, 0],
+        gate: "[a, b, halted]' = [b, a + b, over] while running, the state kept once halted, over from a range-checked comparison with the bound",
+        step_inputs: None,
+        example: "--bound 20 --fold-steps 10",
+        spec: |args| CircuitSpec::BoundedFibonacci { bound: args.bound },
+    },
+    CircuitEntry {
+        name: "digit-count-fibonacci",
+        about: "`DigitCountFiboCircuit`, the Fibonacci sequence one element per step, with the \
+                proven decimal digit count of the last element, up to `--max-digits`",
+        default_z_0: &[0, 1, 1],
+        gate: "[a, b, digits]' = [b, a + b, d] with (d, lo, hi) looked up and lo <= a + b < hi range-checked",
+        step_inputs: None,
+        example: "--max-digits 6 --fold-steps 20",
+        spec: |args| CircuitSpec::DigitCountFibonacci {
+            max_digits: args.max_digits,
+        },
+    },
+    CircuitEntry {
+        name: "pid",
+        about: "`PidCircuit`, a PID controller update with the gains `--kp`, `--ki`, `--kd` and a \
+                different one of `--errors` every step",
+        default_z_0: &[0, 0, 0],
+        gate: "[integral, prev_error, output]' = [integral + e, e, kp e + ki integral' + kd (e - prev_error)]",
+        step_inputs: Some("--errors"),
+        example: "--errors 5,3,-1 --kp 0.5 --ki 0.1 --kd 0",
+        spec: |args| {
+            let gains = circuits::PidGains::from_f64(args.kp, args.ki, args.kd);
+            CircuitSpec::Pid {
+                errors: args.errors.clone(),
+                kp: gains.kp,
+                ki: gains.ki,
+                kd: gains.kd,
+            }
+        },
+    },
+    CircuitEntry {
+        name: "set-accumulator",
+        about: "`SetAccumulatorCircuit`, adding an element to an accumulated set every step, a \
+                different one of `--set-ops`",
+        default_z_0: &[1],
+        gate: "acc' = acc (2^64 - x) to add x",
+        step_inputs: Some("--set-ops"),
+        example: "--set-ops add:3,add:5,add:8",
+        spec: |args| CircuitSpec::SetAccumulator {
+            ops: args.set_ops.clone(),
+        },
+    },
+    CircuitEntry {
+        name: "long-division",
+        about:
+            "`LongDivisionCircuit`, the long division by `--divisor`, bringing down a different \
+                one of `--dividend-digits` every step",
+        default_z_0: &[0, 0],
+        gate: "10 remainder + digit = divisor q + remainder', quotient' = 10 quotient + q, digit < 10 and remainder' < divisor range-checked",
+        step_inputs: Some("--dividend-digits"),
+        example: "--dividend-digits 1,2,3,4 --divisor 7 --fold-steps 4",
+        spec: |args| CircuitSpec::LongDivision {
+            digits: args.dividend_digits.clone(),
+            divisor: args.divisor,
+        },
+    },
+    CircuitEntry {
+        name: "window-average",
+        about:
+            "`WindowAverageCircuit`, shifting a different one of `--values` into a window of the \
+                last 4 every step, with their average weighted by `--window-weights`",
+        default_z_0: &[0, 0, 0, 0],
+        gate: "window' = window[1..] then value, sum(w_i window'_i) 2^16 = average sum(w_i) + rem with rem < sum(w_i)",
+        step_inputs: Some("--values"),
+        example: "--values 1,2,3,4,5 --window-weights 1,1,1,1",
+        spec: |args| CircuitSpec::WindowAverage {
+            values: args.values.clone(),
+            weights: args.window_weights.clone(),
+        },
+    },
+    CircuitEntry {
+        name: "stack-machine",
+        about: "`StackMachineCircuit`, executing a different one of the `--program` instructions on \
+                a stack 4 deep every step",
+        default_z_0: &[0, 0, 0, 0],
+        gate: "push, pop and add flags boolean with one set, stack' = push (value, stack[..3]) + pop (stack[1..], 0) + add (stack[0] + stack[1], stack[2..], 0)",
+        step_inputs: Some("--program"),
+        example: "--program push:2,push:3,add,push:4,add --fold-steps 5",
+        spec: |args| CircuitSpec::StackMachine {
+            program: args.program.clone(),
+        },
+    },
+];
 
-/// Number of folding steps
-const FOLD_STEP_COUNT: usize = 5;
+impl CircuitEntry {
+    /// Entry of `name`, which `--circuit` or `--help-circuit` already checked to be registered
+    fn find(name: &str) -> &'static Self {
+        CIRCUITS
+            .iter()
+            .find(|entry| entry.name == name)
+            .expect("`--circuit` and `--help-circuit` only accept registered names")
+    }
 
-// === PRIMARY ===
+    /// Details printed by `--help-circuit`, with the parameters of `args`
+    fn help(&self, args: &Args) -> String {
+        let spec = (self.spec)(args);
+        let step_inputs = match self.step_inputs {
+            Some(flag) if spec.with_step_inputs(vec![]).is_some() => format!(
+                "`{flag}`, one per step, cycled if fewer than the steps, or streamed with \
+                 `--input-stream`"
+            ),
+            Some(flag) => format!("`{flag}`, one per step, cycled if fewer than the steps"),
+            None => "none, every step is the same".to_owned(),
+        };
 
-/// Arity : Input/output size per fold-step for primary step-circuit
-const A1: usize = 2;
+        format!(
+            "{}: {}\n  arity:       {}, z_0 {:?} by default\n  gate:        {}\n  step inputs: \
+             {step_inputs}\n  example:     cargo run --release -- --circuit {} {}\n",
+            self.name,
+            self.about,
+            spec.arity(),
+            self.default_z_0,
+            self.gate,
+            self.name,
+            self.example
+        )
+    }
+}
 
-/// Key size for Primary Circuit
-///
-/// This is the minimum value, for your circuit you may get the output that the key size is
-/// insufficient, then increase this constant
-const PRIMARY_COMMITMENT_KEY_SIZE: usize = 20;
+/// Folding schedules of `--fold-order`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum FoldOrder {
+    /// Every fold step folds one primary step and one secondary step, in turn
+    Interleaved,
+    /// Several primary steps per secondary step
+    PrimaryBatched,
+}
 
-/// Table size for Primary Circuit
-///
-/// Requires at least 17, for service purposes, but if the primary requires more, increase the
-/// constant
-const PRIMARY_CIRCUIT_TABLE_SIZE: usize = 17;
+impl FoldOrder {
+    /// `sirius` v0.1.1 only folds interleaved: `IVC::fold_step` always takes both circuits and
+    /// folds one step of each, with no way to fold either side alone
+    fn ensure_supported(self) -> Result<(), ExampleError> {
+        match self {
+            Self::Interleaved => Ok(()),
+            Self::PrimaryBatched => Err(ExampleError::Unsupported(
+                "--fold-order primary-batched: sirius v0.1.1 folds exactly one primary and one \
+                 secondary step per fold step"
+                    .to_owned(),
+            )),
+        }
+    }
+}
 
-// === SECONDARY ===
+/// Profilers of `--profile`
+#[cfg(feature = "profiling")]
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Profile {
+    /// Sample the CPU during the fold loop and write a flamegraph SVG to `--profile-output`
+    Cpu,
+}
 
-/// Arity : Input/output size per fold-step for secondary step-circuit
-/// For tivial case it can be any number
-const A2: usize = 1;
+/// Command line options of the example
+#[derive(Debug, Parser)]
+#[command(
+    version,
+    long_version = build_info::LONG_VERSION,
+    about = "Prove Fibonacci numbers over multiple folding steps with Sirius"
+)]
+struct Args {
+    /// Read the run parameters from this TOML file instead of the parameter flags
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+    /// Print the run parameters as TOML, ready to be used with `--config`, and exit
+    #[arg(long)]
+    print_config: bool,
+    /// Check one step of the fibonacci circuit with the mock prover, at the primary table size and
+    /// from `z_0`, report any violated constraint with its row, and exit. Needs no commitment keys
+    #[arg(long)]
+    validate_only: bool,
+    /// Print a SHA-256 digest of what the public parameters are built from, to confirm two setups
+    /// are identical, and exit. Proof bundles record it too
+    #[arg(long)]
+    dump_pp_digest: bool,
+    /// Print the polynomial degree of every gate of the step circuit, and of its whole constraint
+    /// system, and exit
+    #[arg(long)]
+    dump_gate_degree: bool,
+    /// Write the columns and gates of the step circuit as a graphviz DOT graph to this file, and
+    /// exit
+    #[arg(long, value_name = "FILE")]
+    dump_constraint_dot: Option<PathBuf>,
+    /// Print a rough estimate of the run's rows, commitment key memory and time, from the config
+    /// alone, and exit
+    #[arg(long)]
+    estimate: bool,
+    /// Print an example config file with every field at its default, commented with its meaning
+    /// and allowed range, and exit
+    #[arg(long)]
+    dump_config_schema: bool,
+    /// Primary step circuit to fold
+    #[arg(
+        long,
+        default_value = "fibonacci",
+        value_parser = PossibleValuesParser::new(CIRCUITS.iter().map(|entry| entry.name)),
+    )]
+    circuit: String,
+    /// Print every circuit `--circuit` takes, with its arity and parameter flags, and exit
+    #[arg(long)]
+    list_circuits: bool,
+    /// Print the details of one circuit `--circuit` takes: its arity, the gate it enforces, its
+    /// per-step inputs and an example run, and exit
+    #[arg(
+        long,
+        value_name = "NAME",
+        value_parser = PossibleValuesParser::new(CIRCUITS.iter().map(|entry| entry.name)),
+    )]
+    help_circuit: Option<String>,
+    /// Modulus of the `mod-counter` circuit
+    #[arg(long, default_value_t = 3)]
+    modulus: u64,
+    /// Base of the `digit-root` circuit
+    #[arg(long, default_value_t = 10)]
+    base: u64,
+    /// Width of the `bit-accum` state
+    #[arg(long, default_value_t = 8)]
+    bit_width: usize,
+    /// Value XOR-ed into the `bit-accum` state every step
+    #[arg(long, default_value_t = 0b0110_0101)]
+    xor_input: u64,
+    /// Addends of the `sum` and `saturating-sum` circuits, one per step, cycled if fewer than the
+    /// steps
+    #[arg(long, value_delimiter = ',', default_values_t = [1, 2, 3, 4, 5])]
+    addends: Vec<u64>,
+    /// Maximum of the `saturating-sum` total
+    #[arg(long, default_value_t = 10)]
+    cap: u64,
+    /// Modulus of the `fibo-mod` circuit
+    #[arg(long, default_value_t = 7)]
+    prime: u64,
+    /// Largest value the `bounded-fibonacci` circuit keeps going after
+    #[arg(long, default_value_t = 3)]
+    bound: u64,
+    /// Widest digit bracket of the `digit-count-fibonacci` circuit, the sequence must stay below
+    /// `10^max_digits`
+    #[arg(long, default_value_t = circuits::MAX_DECIMAL_DIGITS)]
+    max_digits: u32,
+    /// Advice columns of the `wide-fibonacci` circuit, `1`, `2`, `4` or `8`
+    #[arg(long, default_value_t = 2)]
+    columns: usize,
+    /// Errors of the `pid` circuit, one per step, cycled if fewer than the steps
+    #[arg(
+        long,
+        value_delimiter = ',',
+        allow_negative_numbers = true,
+        default_values_t = [10, 7, 3, -2, -4]
+    )]
+    errors: Vec<i64>,
+    /// Proportional gain of the `pid` circuit, rounded to 16 fractional bits
+    #[arg(long, allow_negative_numbers = true, default_value_t = 0.6)]
+    kp: f64,
+    /// Integral gain of the `pid` circuit, rounded to 16 fractional bits
+    #[arg(long, allow_negative_numbers = true, default_value_t = 0.1)]
+    ki: f64,
+    /// Derivative gain of the `pid` circuit, rounded to 16 fractional bits
+    #[arg(long, allow_negative_numbers = true, default_value_t = 0.05)]
+    kd: f64,
+    /// Operations of the `set-accumulator` circuit, one per step, `add:<element>`
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_values = ["add:3", "add:5", "add:8", "add:13"]
+    )]
+    set_ops: Vec<circuits::SetOp>,
+    /// Instructions of the `stack-machine` circuit, one per step, `push:<value>`, `pop` or `add`,
+    /// cycled if fewer than the steps
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_values = ["push:2", "push:3", "add", "push:4", "add"]
+    )]
+    program: Vec<circuits::StackOp>,
+    /// Decimal digits of the `long-division` dividend, most significant first, one per step,
+    /// cycled if fewer than the steps
+    #[arg(long, value_delimiter = ',', default_values_t = [9, 8, 7, 6, 5])]
+    dividend_digits: Vec<u64>,
+    /// Divisor of the `long-division` circuit, at least `1`
+    #[arg(long, default_value_t = 7)]
+    divisor: u64,
+    /// Readings of the `range-guard` circuit, one per step, cycled if fewer than the steps
+    #[arg(long, value_delimiter = ',', default_values_t = [21, 23, 22, 25, 24])]
+    readings: Vec<u64>,
+    /// Lowest reading allowed by the `range-guard` circuit
+    #[arg(long, default_value_t = 0)]
+    lo: u64,
+    /// Highest reading allowed by the `range-guard` circuit
+    #[arg(long, default_value_t = 100)]
+    hi: u64,
+    /// Claims of the `challenge-accum` circuit, one per step, cycled if fewer than the steps
+    #[arg(long, value_delimiter = ',', default_values_t = [3, 1, 4, 1, 5])]
+    claims: Vec<u64>,
+    /// Inputs of the `input-commitment` circuit, one per step, cycled if fewer than the steps
+    #[arg(long, value_delimiter = ',', default_values_t = [7, 1, 8, 2, 8])]
+    inputs: Vec<u64>,
+    /// Coefficients of the `coeff-recurrence` circuit, one per step, cycled if fewer than the
+    /// steps. The default is the continued fraction of pi
+    #[arg(long, value_delimiter = ',', default_values_t = [3, 7, 15, 1, 292])]
+    coefficients: Vec<u64>,
+    /// Opcodes of the `opcode-lookup` circuit, one per step, cycled if fewer than the steps
+    #[arg(long, value_delimiter = ',', default_values_t = [0x01, 0x02, 0x10, 0x56, 0x00])]
+    opcodes: Vec<u64>,
+    /// Values of the `sorted-pair`, `average` and `window-average` circuits, one per step, cycled
+    /// if fewer than the steps. Cycling breaks the order, which fails the `sorted-pair` step that
+    /// wraps around
+    #[arg(long, value_delimiter = ',', default_values_t = [2, 3, 5, 8, 13])]
+    values: Vec<u64>,
+    /// Weights of the `window-average` circuit, one per window position, oldest first
+    #[arg(long, value_delimiter = ',', default_values_t = [1, 2, 3, 4])]
+    window_weights: Vec<u32>,
+    /// Weight matrix of the `dense-layer` circuit, row-major. The default `[[1, 1], [1, 0]]`
+    /// advances the Fibonacci pair `[e(n), e(n - 1)]`
+    #[arg(long, value_delimiter = ',', default_values_t = [1, 1, 1, 0])]
+    weights: Vec<u64>,
+    /// Bias of the `dense-layer` circuit, one per state element
+    #[arg(long, value_delimiter = ',', default_values_t = [0, 0])]
+    bias: Vec<u64>,
+    /// Data words of the `checksum` circuit, one per step, comma separated decimal or `0x` hex
+    /// field elements, cycled if fewer than the steps
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_parser = field::parse_field::<C1Scalar>,
+        default_values = ["0x68656c6c6f", "0x2c", "0x776f726c64"]
+    )]
+    words: Vec<C1Scalar>,
+    /// Fail unless the `checksum` circuit ends at this checksum, decimal or `0x` hex
+    #[arg(long, value_name = "FIELD", value_parser = field::parse_field::<C1Scalar>)]
+    assert_checksum: Option<C1Scalar>,
+    /// Fail unless the run folded this many steps, including the base step
+    #[arg(long, value_name = "N")]
+    assert_steps: Option<usize>,
+    /// Number of folding steps, including the base step
+    #[arg(long, default_value_t = FOLD_STEP_COUNT)]
+    fold_steps: usize,
+    /// Number of Fibonacci elements proven by each step
+    #[arg(long, default_value_t = ELEMENTS_NUM)]
+    elements_num: usize,
+    /// Prove the first `L` Fibonacci elements: picks `--elements-num` and `--fold-steps` so that
+    /// `steps * elements_num >= L`
+    #[arg(long, value_name = "L", conflicts_with_all = ["fold_steps", "elements_num"])]
+    prove_length: Option<usize>,
+    /// Input of the primary circuit on the zero step, comma separated decimal or `0x` hex field
+    /// elements [default: depends on the circuit, `0,1` for fibonacci]
+    #[arg(long, value_delimiter = ',', value_parser = field::parse_field::<C1Scalar>)]
+    z0: Vec<C1Scalar>,
+    /// Input of the secondary circuit on the zero step, comma separated decimal or `0x` hex field
+    /// elements [default: `A2` zeros]
+    #[arg(long, value_delimiter = ',', value_parser = field::parse_field::<C2Scalar>)]
+    secondary_z0: Vec<C2Scalar>,
+    /// Step circuit folded on the secondary curve, alongside the primary one
+    #[arg(
+        long,
+        default_value = "trivial",
+        value_parser = PossibleValuesParser::new(SecondaryCircuit::ALL.map(SecondaryCircuit::name)),
+    )]
+    secondary_circuit: String,
+    /// How field elements are printed: `auto` is decimal up to `u128` and hex above
+    #[arg(
+        long,
+        default_value = "auto",
+        value_parser = PossibleValuesParser::new(FieldFormat::ALL.map(FieldFormat::name)),
+    )]
+    field_format: String,
+    /// Print only the final primary `z_out` in this encoding, on a single line, instead of the
+    /// human-readable output, for a script to capture
+    #[arg(
+        long,
+        value_name = "ENCODING",
+        value_parser = PossibleValuesParser::new(ValueEncoding::ALL.map(ValueEncoding::name)),
+    )]
+    print_z_out: Option<String>,
+    #[arg(long, default_value_t = PRIMARY_COMMITMENT_KEY_SIZE)]
+    primary_key_size: usize,
+    #[arg(long, default_value_t = PRIMARY_CIRCUIT_TABLE_SIZE)]
+    primary_table_size: usize,
+    #[arg(long, default_value_t = SECONDARY_COMMITMENT_KEY_SIZE)]
+    secondary_key_size: usize,
+    #[arg(long, default_value_t = SECONDARY_CIRCUIT_TABLE_SIZE)]
+    secondary_table_size: usize,
+    /// Folder to cache the commitment keys in
+    #[arg(long, default_value = ".cache")]
+    cache_dir: PathBuf,
+    /// How many times to retry a commitment-key cache read/write that failed with a transient IO
+    /// error, with exponential backoff between attempts
+    #[arg(long, default_value_t = 3)]
+    cache_retries: u32,
+    /// Write a Chrome trace (for `chrome://tracing`) of the setup, fold step and verify spans to
+    /// this file
+    #[arg(long, value_name = "FILE")]
+    trace_chrome: Option<PathBuf>,
+    /// Serve Prometheus metrics of the run on this port, until the program exits
+    #[arg(long)]
+    metrics_port: Option<u16>,
+    /// Stop folding early once a step leaves the state unchanged
+    #[arg(long)]
+    halt_on_fixpoint: bool,
+    /// Read the per-step inputs of the circuit from this file, `-` for stdin, one decimal value per
+    /// line, instead of its list flag. Each step waits for its line, and folding stops at the end
+    /// of the input, after at most `--fold-steps` steps
+    #[arg(long, value_name = "SOURCE", conflicts_with = "repeat")]
+    input_stream: Option<PathBuf>,
+    /// Folding schedule of the primary and secondary circuits
+    #[arg(long, value_enum, default_value_t = FoldOrder::Interleaved)]
+    fold_order: FoldOrder,
+    /// Run the whole prove-and-verify pipeline this many times in sequence and report the min,
+    /// median and max wall time, the outputs are written for the last iteration
+    #[arg(long, default_value_t = NonZeroUsize::MIN)]
+    repeat: NonZeroUsize,
+    /// Draw `z_0` from OS entropy instead of `--z0`, anew for every `--repeat` iteration, and print
+    /// it as the `--z0` that reproduces the run
+    #[arg(long, conflicts_with = "z0")]
+    seed_from_entropy: bool,
+    /// Profile the fold loop of the run, without the setup and the verification
+    #[cfg(feature = "profiling")]
+    #[arg(long, value_name = "KIND")]
+    profile: Option<Profile>,
+    /// Where `--profile` writes its output
+    #[cfg(feature = "profiling")]
+    #[arg(long, value_name = "FILE", default_value = "flamegraph.svg")]
+    profile_output: PathBuf,
+    /// Record the host of the run (CPUs, RAM, OS, thread pool) in the `--output` bundle
+    #[cfg(feature = "env-dump")]
+    #[arg(long, requires = "output")]
+    env_dump: bool,
+    /// Treat every warning as an error and exit with a failure, e.g. to keep CI runs clean
+    #[arg(long)]
+    strict: bool,
+    /// Abort once more than `N` warnings were emitted in total, with a count of each, e.g. to
+    /// keep a long sweep from burying its output in warnings
+    #[arg(long, value_name = "N")]
+    abort_on_warning_budget: Option<usize>,
+    /// Fold every step but skip the final verification, e.g. to benchmark proving alone; the run
+    /// is reported as unverified. Refused with `--strict`
+    #[arg(long)]
+    no_verify: bool,
+    /// On a failed fold step or verification, verify what was folded before it and dump the
+    /// config, the final states and every check, then fail
+    #[arg(long)]
+    keep_going_on_verify_fail: bool,
+    /// Hide the setup messages (commitment keys, cache, IVC creation), keep the fold step and
+    /// verification output
+    #[arg(long)]
+    quiet_setup: bool,
+    /// Also append every status line, warning and error to this file, each with a timestamp
+    #[arg(long, value_name = "FILE")]
+    log_file: Option<PathBuf>,
+    /// Print a summary of the IVC structure once it is created
+    #[arg(long)]
+    describe_ivc: bool,
+    /// Stop folding after the step that exceeds this wall time budget, still verifying the steps
+    /// folded so far
+    #[arg(long, value_name = "SECONDS")]
+    max_time: Option<f64>,
+    /// Debugging only: print the values the fibonacci circuit assigns to its column `e` on every
+    /// step before running, with what constrains each cell
+    #[arg(long)]
+    emit_witness: bool,
+    /// After a successful `fibonacci` run, print every element of the sequence up to the last one
+    /// proven
+    #[arg(long)]
+    print_sequence: bool,
+    /// Only run the configuration checks and print a report, without any setup or proving
+    #[arg(long)]
+    validate_config: bool,
+    /// Run a batch, one configuration per listed `--elements-num` value, and print its summary
+    #[arg(long, value_delimiter = ',', value_name = "LIST")]
+    sweep_elements_num: Vec<usize>,
+    /// Try each listed primary commitment key size with the shortest run, report a table of the
+    /// sizes that work with their setup time, and exit
+    #[arg(long, value_delimiter = ',', value_name = "LIST")]
+    compare_key_sizes: Vec<usize>,
+    /// Cycle of curves to fold on, `primary/secondary`
+    #[arg(
+        long,
+        value_name = "CYCLE",
+        default_value = "bn256/grumpkin",
+        value_parser = PossibleValuesParser::new(
+            curve::supported_curves()
+                .iter()
+                .map(|info| PossibleValue::new(info.name).help(info.to_string())),
+        ),
+    )]
+    curve: String,
+    /// Run the circuit on every supported curve cycle and print a table of their setup and fold
+    /// times, with whether `z_out` agrees across cycles, then exit
+    #[arg(long)]
+    compare_curves: bool,
+    /// Fold the fibonacci circuit to this index with every split into steps and elements per
+    /// step, print a table of their table size, setup and fold times, and exit
+    #[arg(long, value_name = "INDEX")]
+    compare_block_sizes: Option<usize>,
+    /// Abort the batch on the first failing configuration
+    #[arg(long, conflicts_with = "keep_going")]
+    fail_fast: bool,
+    /// Run every configuration of the batch, even after a failure [default]
+    #[arg(long = "continue")]
+    keep_going: bool,
+    /// Write a JSON proof bundle of the run to this file, to be checked with `--verify`
+    #[arg(long, value_name = "FILE")]
+    output: Option<PathBuf>,
+    /// After writing the `--output` bundle, verify it with `--verify` in a fresh process of this
+    /// binary, which shares only the bundle and the key cache with this one
+    #[arg(long, requires = "output")]
+    prove_then_verify_separate_process: bool,
+    /// After a successful run, print the size of its proof bundle in bytes, by component, without
+    /// needing `--output`
+    #[arg(long)]
+    output_proof_size: bool,
+    /// After a successful run, write the public values its verification checks (step count, `z_0`
+    /// and `z_out`) as JSON to this file
+    #[arg(long, value_name = "FILE")]
+    verify_transcript: Option<PathBuf>,
+    /// Verify the JSON proof bundles written by `--output`, in order, and exit, the circuit and run
+    /// parameters are taken from each bundle
+    #[arg(long, value_name = "FILE", num_args = 1..)]
+    verify: Vec<PathBuf>,
+    /// How many verified bundles `--verify` remembers, a repeated bundle is not verified again
+    #[arg(long, default_value_t = 16)]
+    verify_cache_size: usize,
+    /// Run the default Fibonacci configuration and write its final state and verification status
+    /// to this golden file, then exit. Only the cache flags are taken into account
+    #[arg(long, value_name = "FILE")]
+    snapshot: Option<PathBuf>,
+    /// With `--snapshot`, compare against the golden file instead of writing it
+    #[arg(long, requires = "snapshot")]
+    check: bool,
+    /// Compare the bundle `BUNDLE` against the baseline bundle `BASELINE`, e.g. one written by a
+    /// previous version, print their differences by section and exit without running. Exits with
+    /// `1` if they differ
+    #[arg(long, num_args = 2, value_names = ["BASELINE", "BUNDLE"])]
+    compare_against: Option<Vec<PathBuf>>,
+}
 
-/// Input to be passed on the zero step to the secondary circuit
-const SECONDARY_Z_0: [C2Scalar; A2] = [C2Scalar::ZERO];
+impl Args {
+    /// Circuit from `--circuit` with its parameter flags
+    fn circuit_spec(&self) -> CircuitSpec {
+        (CircuitEntry::find(&self.circuit).spec)(self)
+    }
+
+    /// Config from `--config`, or else from the parameter flags
+    fn run_config(&self) -> Result<RunConfig, ExampleError> {
+        if let Some(path) = &self.config {
+            return RunConfig::from_toml_file(path);
+        }
+
+        let z0 = if self.z0.is_empty() {
+            CircuitEntry::find(&self.circuit)
+                .default_z_0
+                .iter()
+                .copied()
+                .map(C1Scalar::from)
+                .collect()
+        } else {
+            self.z0.clone()
+        };
+
+        let (elements_num, fold_step_count) = match self.prove_length {
+            Some(len) => plan_sequence(len),
+            None => (self.elements_num, self.fold_steps),
+        };
+
+        Ok(RunConfig {
+            fold_step_count,
+            elements_num,
+            primary_commitment_key_size: self.primary_key_size,
+            primary_circuit_table_size: self.primary_table_size,
+            primary_z_0: z0,
+            secondary_commitment_key_size: self.secondary_key_size,
+            secondary_circuit_table_size: self.secondary_table_size,
+            secondary_z_0: if self.secondary_z0.is_empty() {
+                SECONDARY_Z_0.to_vec()
+            } else {
+                self.secondary_z0.clone()
+            },
+            secondary_circuit: SecondaryCircuit::from_name(&self.secondary_circuit)
+                .expect("`--secondary-circuit` only accepts known names"),
+            cache_dir: self.cache_dir.clone(),
+            cache_retries: self.cache_retries,
+            halt_on_fixpoint: self.halt_on_fixpoint,
+            describe_ivc: self.describe_ivc,
+            max_time: self.max_time,
+            no_verify: self.no_verify,
+            field_format: FieldFormat::from_name(&self.field_format)
+                .expect("`--field-format` only accepts known names"),
+            keep_going_on_verify_fail: self.keep_going_on_verify_fail,
+            // Rewinding is for interactive use of the runner, a command line run only folds forward
+            checkpoints: 0,
+        })
+    }
+}
 
-/// Table size for Primary Circuit
+/// Re-run the proof bundle at `path` and check its claims, unless it is in `cache`
 ///
-/// Requires at least 17, for service purposes, but if the primary requires more, increase the
-/// constant
-const SECONDARY_CIRCUIT_TABLE_SIZE: usize = 17;
+/// Only the bundle and the local key cache of `args` are used, see [`ProofBundle::verify_config`].
+/// Returns whether it was a cache hit
+fn verify_bundle(args: &Args, cache: &VerifyCache, path: &Path) -> Result<bool, ExampleError> {
+    let bundle = ProofBundle::read(path)?;
 
-/// Key size for Secondary Circuit
+    let current = BuildInfo::current();
+    match &bundle.built_with {
+        Some(built_with) if *built_with != current => warning::warn(format_args!(
+            "bundle produced by {built_with}, verifying with {current}"
+        ))?,
+        Some(_) => {}
+        None => warning::warn("bundle does not record the versions that produced it")?,
+    }
+    if let Some(environment) = &bundle.environment {
+        output::log(format_args!("bundle proven on {environment}"));
+    }
+
+    cache.verify(
+        &bundle,
+        &args.cache_dir,
+        args.cache_retries,
+        Some(&mut progress::print),
+    )
+}
+
+/// Write the [`ConstraintGraph`] of the step circuit of `circuit` to `path`
+fn dump_constraint_dot(circuit: &CircuitSpec, path: &Path) -> Result<(), ExampleError> {
+    let graph = ConstraintGraph::from_constraint_system(&circuit.constraint_system()?);
+    fs::write(path, graph.to_string()).map_err(|err| ExampleError::ConstraintDot {
+        path: path.to_path_buf(),
+        err,
+    })
+}
+
+/// Print the [`BundleDiff`] of the bundle at `path` against the one at `baseline`, `true` if they
+/// match
+fn compare_bundles(baseline: &Path, path: &Path) -> Result<bool, ExampleError> {
+    let diff = BundleDiff::of(&ProofBundle::read(baseline)?, &ProofBundle::read(path)?);
+    print!("{diff}");
+    Ok(diff.is_empty())
+}
+
+/// Verify the bundle at `path` with `--verify` in a child process of this binary
 ///
-/// This is the minimum value, for your circuit you may get the output that the key size is
-/// insufficient, then increase this constant
-const SECONDARY_COMMITMENT_KEY_SIZE: usize = 20;
+/// The child starts from nothing but the bundle and the key cache of `args`, so this catches a
+/// bundle that only verifies thanks to state left in this process
+fn verify_in_separate_process(args: &Args, path: &Path) -> Result<(), ExampleError> {
+    let process_err = |err: io::Error| ExampleError::VerifierProcess(err.to_string());
+
+    let mut command = Command::new(env::current_exe().map_err(process_err)?);
+    command
+        .arg("--verify")
+        .arg(path)
+        .arg("--cache-dir")
+        .arg(&args.cache_dir)
+        .arg("--cache-retries")
+        .arg(args.cache_retries.to_string());
+    if args.strict {
+        command.arg("--strict");
+    }
+
+    output::info(
+        output::Phase::Fold,
+        format_args!("verifying {path:?} in a separate process"),
+    );
+    let status = command.status().map_err(process_err)?;
+    if !status.success() {
+        return Err(ExampleError::VerifierProcess(format!(
+            "bundle {path:?} rejected ({status})"
+        )));
+    }
+
+    output::info(
+        output::Phase::Fold,
+        format_args!("bundle {path:?} verified by a separate process"),
+    );
+    Ok(())
+}
+
+/// Run the fixed snapshot configuration, then write its snapshot to `path` or, with `check`,
+/// compare it with the one there
+fn snapshot(args: &Args, path: &Path) -> Result<(), ExampleError> {
+    let config = RunConfig {
+        cache_dir: args.cache_dir.clone(),
+        cache_retries: args.cache_retries,
+        ..RunConfig::default()
+    };
+    let result = run_circuit(&CircuitSpec::Fibonacci, &config, Some(&mut progress::print));
+
+    if let Err(err) = &result {
+        report(err);
+    }
+
+    let snapshot = Snapshot::of(&result);
+    if args.check {
+        snapshot.check(path)
+    } else {
+        snapshot.write(path)
+    }
+}
+
+/// Print the values [`FibonacciCircuit`] assigns to its column `e` on every step of `config`
+fn emit_witness(config: &RunConfig) -> Result<(), ExampleError> {
+    config.ensure_valid(A1, A2)?;
+
+    let circuit = FibonacciCircuit::new(config.elements_num);
+    let Ok(mut z_i) = <[C1Scalar; A1]>::try_from(config.primary_z_0.clone()) else {
+        return warning::warn(format_args!("no witness: z_0 must have {A1} elements"));
+    };
+
+    for step in 0..config.fold_step_count {
+        println!("step {step} witness of column `e`:");
+
+        let block = circuit.witness(&z_i);
+        let z_out_start = block.len() - A1;
+        for (offset, value) in block.iter().enumerate() {
+            let role = match offset {
+                0 | 1 => format!("copy of z_i[{offset}]"),
+                _ if offset >= z_out_start => format!("gate, z_out[{}]", offset - z_out_start),
+                _ => "gate".to_owned(),
+            };
+            println!(
+                "  {offset:>4}: {} ({role})",
+                config.field_format.display(value)
+            );
+        }
+
+        z_i = circuit.reference_step(&z_i);
+    }
+
+    Ok(())
+}
+
+/// Run one step of `circuit` with [`mock::check_step`], only the fibonacci circuit is supported
+fn mock_validate(circuit: &CircuitSpec, config: &RunConfig) -> Result<(), ExampleError> {
+    let CircuitSpec::Fibonacci = circuit else {
+        return Err(ExampleError::InvalidConfig(
+            "--validate-only is only supported by the fibonacci circuit".to_owned(),
+        ));
+    };
+
+    let z_0: [C1Scalar; A1] = config
+        .primary_z_0
+        .clone()
+        .try_into()
+        .map_err(|z_0: Vec<_>| {
+            ExampleError::InvalidConfig(format!(
+                "got {} elements of z_0, but the primary arity is {A1}",
+                z_0.len()
+            ))
+        })?;
+
+    mock::check_step(
+        config.primary_circuit_table_size as u32,
+        &FibonacciCircuit::new(config.elements_num),
+        z_0,
+    )
+    .map(drop)
+}
+
+/// Check that the checksum circuit of `summary` ended at `expected`
+fn assert_checksum(
+    expected: C1Scalar,
+    format: FieldFormat,
+    summary: &RunSummary,
+) -> Result<(), ExampleError> {
+    let [got] = summary.z_out[..] else {
+        unreachable!("checksum arity is 1")
+    };
+    if got != expected {
+        return Err(ExampleError::ChecksumMismatch {
+            expected: format.display(&expected).to_string(),
+            got: format.display(&got).to_string(),
+        });
+    }
 
-/// Iterator for generating Fibonacci sequence values.
+    output::info(
+        output::Phase::Fold,
+        format_args!("checksum {} matches", format.display(&got)),
+    );
+    Ok(())
+}
+
+/// Check that the run of `summary` folded `expected` steps
+fn assert_steps(expected: usize, summary: &RunSummary) -> Result<(), ExampleError> {
+    let got = summary.steps;
+    if got != expected {
+        return Err(ExampleError::StepCountMismatch { expected, got });
+    }
+
+    output::info(
+        output::Phase::Fold,
+        format_args!("{got} steps folded, as expected"),
+    );
+    Ok(())
+}
+
+/// Print the `[integral, prev_error, output]` state of [`circuits::PidCircuit`] in the `z_out` of
+/// `summary`, with the fixed-point output as a decimal
+fn print_pid(format: FieldFormat, summary: &RunSummary) {
+    let [integral, prev_error, output] =
+        [0, 1, 2].map(|index| field::to_i128(&summary.z_out[index]));
+    match integral.zip(prev_error).zip(output) {
+        Some(((integral, prev_error), output)) => println!(
+            "pid: integral {integral}, previous error {prev_error}, output {output} / {} = {:.6}",
+            circuits::PID_SCALE,
+            output as f64 / circuits::PID_SCALE as f64
+        ),
+        None => println!(
+            "pid: the state {} is out of the signed range",
+            format.display_all(&summary.z_out)
+        ),
+    }
+}
+
+/// Print the Pisano period of `p`, and warn if `steps` is more than it, as the state of the
+/// [`circuits::FiboModCircuit`] repeats with it
 ///
-/// Given two initial values, it produces subsequent Fibonacci numbers by
-/// summing the two preceding numbers.
+/// The period takes time linear in `p`, so it is skipped above [`PISANO_PERIOD_MAX_PRIME`]
+fn report_pisano_period(p: u64, steps: usize) -> Result<(), ExampleError> {
+    if p > PISANO_PERIOD_MAX_PRIME {
+        return Ok(());
+    }
+
+    let period = circuits::FiboModCircuit::new(p).pisano_period();
+    output::info(
+        output::Phase::Setup,
+        format_args!("pisano period of {p}: the state repeats every {period} steps"),
+    );
+    if steps as u64 > period {
+        warning::warn(format_args!(
+            "{steps} steps is more than the pisano period {period} of {p}, the later steps only \
+             repeat states already folded"
+        ))?;
+    }
+    Ok(())
+}
+
+/// Print the fixed-point average of the `[sum, count]` state in the `z_out` of `summary`, the one
+/// the last step of [`circuits::AverageCircuit`] proved
+fn print_average(format: FieldFormat, summary: &RunSummary) {
+    let [sum, count] = [0, 1].map(|index| field::to_u128(&summary.z_out[index]));
+    match sum
+        .zip(count)
+        .and_then(|(sum, count)| circuits::fixed_point_average(sum, count))
+    {
+        Some(average) => println!(
+            "average: {average} / {} = {:.6}",
+            circuits::AVERAGE_SCALE,
+            average as f64 / circuits::AVERAGE_SCALE as f64
+        ),
+        None => println!(
+            "average: undefined for the state {}",
+            format.display_all(&summary.z_out)
+        ),
+    }
+}
+
+/// Print the weighted average of the window in the `z_out` of `summary`, the one the last step of
+/// [`circuits::WindowAverageCircuit`] proved
+fn print_window_average(format: FieldFormat, weights: &[u32], summary: &RunSummary) {
+    // The run already checked the weights
+    let average = <[u32; WINDOW_AVERAGE_WIDTH]>::try_from(weights)
+        .ok()
+        .and_then(|weights| {
+            circuits::WindowAverageCircuit::new(0, weights).average(&summary.z_out)
+        });
+    match average {
+        Some(average) => println!(
+            "window average: {average} / {} = {:.6}",
+            circuits::AVERAGE_SCALE,
+            average as f64 / circuits::AVERAGE_SCALE as f64
+        ),
+        None => println!(
+            "window average: undefined for the state {}",
+            format.display_all(&summary.z_out)
+        ),
+    }
+}
+
+/// Print the Fibonacci sequence from `z_0` of `config` up to the `z_out` of `summary`, see
+/// [`FibonacciCircuit::sequence`]
 ///
-/// # Example
-/// ```
-/// const EXPECTED: [u64; 20] = [
-///     0, 1, 1, 2, 3, 5, 8, 13, 21, 34,
-///     55, 89, 144, 233, 377, 610, 987,
-///     1597, 2584, 4181,
-/// ];
-/// let actual = FibonacciIter(0, 1).take(20).collect::<Vec<_>>();
-/// assert_eq!(&actual, &EXPECTED);
-/// ```
-struct FibonacciIter<F>(F, F);
+/// These are the witness values of the steps recomputed out of circuit, sirius v0.1.1 doesn't
+/// expose them from the proof
+fn print_sequence(config: &RunConfig, summary: &RunSummary) -> Result<(), ExampleError> {
+    let Ok(z_0) = <[C1Scalar; A1]>::try_from(config.primary_z_0.clone()) else {
+        return warning::warn(format_args!("no sequence: z_0 must have {A1} elements"));
+    };
 
-impl<F: Add<Output = F> + Copy> Iterator for FibonacciIter<F> {
-    type Item = F;
+    output::info(
+        output::Phase::Fold,
+        "folded sequence (witness values, recomputed):",
+    );
+    let sequence = FibonacciCircuit::new(config.elements_num).sequence(&z_0, summary.steps);
+    for (index, value) in sequence.iter().enumerate() {
+        output::info(
+            output::Phase::Fold,
+            format_args!("  #{index} = {}", config.field_format.display(value)),
+        );
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        let cur = self.0;
+    Ok(())
+}
 
-        self.0 = self.1;
-        self.1 = cur + self.1;
+/// With `--seed-from-entropy`, replace the `z_0` of `config` with a random seed and print it
+fn reseed(args: &Args, config: &mut RunConfig, arity: usize) -> Result<(), ExampleError> {
+    if args.seed_from_entropy {
+        let seed = Seed::from_entropy(arity)?;
+        output::info(output::Phase::Fold, format_args!("seed: {seed}"));
+        config.primary_z_0 = seed.z_0();
+    }
+    Ok(())
+}
 
-        Some(cur)
+/// Reader of `--input-stream`: stdin for `-`, otherwise the file at `source`
+fn open_input_stream(source: &Path) -> Result<Box<dyn BufRead>, ExampleError> {
+    if source == Path::new("-") {
+        return Ok(Box::new(io::stdin().lock()));
     }
+
+    let file = File::open(source)
+        .map_err(|err| ExampleError::InputStream(format!("{source:?}: {err}")))?;
+    Ok(Box::new(BufReader::new(file)))
 }
 
-/// Configuration for the Fibonacci circuit, which includes a selector and an advice column to hold
-/// intermediate values of the Fibonacci sequence.
-#[derive(Debug, Clone)]
-struct FibonacciConfig {
-    /// Selector used to activate the gate that enforces the Fibonacci relation.
-    s: Selector,
-    /// Advice column to store the current and previous Fibonacci numbers.
-    e: Column<Advice>,
+/// Print `err`, with a remediation line if it is an insufficient size, and return the exit code
+/// to fail with
+fn report(err: &ExampleError) -> ExitCode {
+    eprintln!("error: {err}");
+    output::log(format_args!("error: {err}"));
+    if let Some(undersized) = err.undersized() {
+        eprintln!("hint: {undersized}");
+        output::log(format_args!("hint: {undersized}"));
+    }
+    ExitCode::from(err.exit_code())
 }
 
-/// Circuit that generates Fibonacci numbers over multiple folding steps.
-///
-/// The circuit is configured to prove a specified number of Fibonacci sequence elements in each
-/// step, defined by `ELEMENTS_NUM`.
-struct FibonacciCircuit<const ELEMENTS_NUM: usize> {}
+fn main() -> ExitCode {
+    let args = Args::parse();
+    warning::set_strict(args.strict);
+    warning::set_budget(args.abort_on_warning_budget);
+    let print_z_out = args.print_z_out.as_deref().map(|name| {
+        ValueEncoding::from_name(name).expect("`--print-z-out` only accepts known encodings")
+    });
+    output::set_quiet(
+        output::Phase::Setup,
+        args.quiet_setup || print_z_out.is_some(),
+    );
+    output::set_quiet(output::Phase::Fold, print_z_out.is_some());
+    if let Some(path) = &args.log_file {
+        if let Err(err) = output::set_log_file(path) {
+            return report(&ExampleError::LogFile {
+                path: path.clone(),
+                err,
+            });
+        }
+    }
+
+    // Flushes the trace when dropped at the end of `main`
+    let _trace_guard = args.trace_chrome.as_ref().map(|path| {
+        let (layer, guard) = ChromeLayerBuilder::new().file(path).build();
+        tracing_subscriber::registry().with(layer).init();
+        guard
+    });
 
-impl<F: PrimeField, const N: usize> StepCircuit<A1, F> for FibonacciCircuit<N> {
-    /// This is a configuration object that stores things like columns.
-    type Config = FibonacciConfig;
+    if !args.verify.is_empty() {
+        let cache = VerifyCache::new(args.verify_cache_size);
+        let mut exit_code = ExitCode::SUCCESS;
+        for path in &args.verify {
+            match verify_bundle(&args, &cache, path) {
+                Ok(true) => println!("bundle {path:?} verified (cached)"),
+                Ok(false) => println!("bundle {path:?} verified"),
+                Err(err) => {
+                    exit_code = report(&err);
+                }
+            }
+        }
 
-    /// Configure the step circuit. This method initializes necessary
-    /// fixed columns and advice columns
-    fn configure(cs: &mut ConstraintSystem<F>) -> Self::Config {
-        let config = Self::Config {
-            s: cs.selector(),
-            e: cs.advice_column(),
+        return exit_code;
+    }
+
+    if let Some([baseline, bundle]) = args.compare_against.as_deref() {
+        return match compare_bundles(baseline, bundle) {
+            Ok(true) => ExitCode::SUCCESS,
+            Ok(false) => ExitCode::FAILURE,
+            Err(err) => report(&err),
         };
+    }
 
-        cs.enable_equality(config.e);
+    if let Some(path) = &args.snapshot {
+        return match snapshot(&args, path) {
+            Ok(()) => {
+                println!(
+                    "snapshot {}",
+                    if args.check { "matches" } else { "written" }
+                );
+                ExitCode::SUCCESS
+            }
+            Err(err) => report(&err),
+        };
+    }
 
-        cs.create_gate("fibo-block", |meta| {
-            let s = meta.query_selector(config.s);
+    if args.dump_config_schema {
+        print!("{}", RunConfig::schema());
+        return ExitCode::SUCCESS;
+    }
 
-            let e1 = meta.query_advice(config.e, Rotation(-2));
-            let e2 = meta.query_advice(config.e, Rotation(-1));
-            let e3 = meta.query_advice(config.e, Rotation(0));
+    if args.list_circuits {
+        for entry in CIRCUITS {
+            let arity = (entry.spec)(&args).arity();
+            println!("{:<18} arity {arity}  {}", entry.name, entry.about);
+        }
+        return ExitCode::SUCCESS;
+    }
 
-            vec![s * (e1 + e2 - e3)]
-        });
+    if let Some(name) = &args.help_circuit {
+        print!("{}", CircuitEntry::find(name).help(&args));
+        return ExitCode::SUCCESS;
+    }
 
-        config
-    }
-
-    /// Sythesize the circuit for a computation step and return variable
-    /// that corresponds to the output of the step z_{i+1}
-    /// this method will be called when we synthesize the IVC_Circuit
-    ///
-    /// Return `z_out` result
-    fn synthesize_step(
-        &self,
-        config: Self::Config,
-        layouter: &mut impl Layouter<F>,
-        z_i: &[AssignedCell<F, F>; 2],
-    ) -> Result<[AssignedCell<F, F>; 2], SynthesisError> {
-        let z_out = layouter.assign_region(
-            || "main",
-            |mut region| {
-                let [a, b] = z_i;
-
-                FibonacciIter(a.value().copied(), b.value().copied())
-                    .enumerate()
-                    .map(|(offset, value)| {
-                        let assigned = region.assign_advice(
-                            || "element of sequence",
-                            config.e,
-                            offset,
-                            || value,
-                        )?;
-
-                        // Enforce equality constraints on the first two elements.
-                        //
-                        // For all other - enable gate with check. Note that the gate starts work
-                        // at index 2, because the gate references the -2 cell internally
-                        match offset {
-                            0 => {
-                                region.constrain_equal(a.cell(), assigned.cell())?;
-                            }
-                            1 => {
-                                region.constrain_equal(b.cell(), assigned.cell())?;
-                            }
-                            _ => {
-                                config.s.enable(&mut region, offset)?;
-                            }
-                        }
-
-                        Ok(assigned)
-                    })
-                    .take(N + A1)
-                    .skip(N) // We only need the last two elements (A1 := 2)
-                    .collect::<Result<Vec<_>, _>>()
-            },
-        )?;
+    let mut config = match args.run_config() {
+        Ok(config) => config,
+        Err(err) => return report(&err),
+    };
+    let cycle = Cycle::from_name(&args.curve).expect("`--curve` only accepts supported cycles");
+    // Every supported cycle is folded by the same pipeline, the one of `sirius::prelude::bn256`
+    match cycle {
+        Cycle::Bn256Grumpkin => output::log(format_args!(
+            "curve cycle {}: {}",
+            cycle.name(),
+            cycle.info()
+        )),
+    }
+
+    let circuit = args.circuit_spec();
 
-        Ok(z_out.try_into().unwrap())
+    if let Err(err) = args.fold_order.ensure_supported() {
+        return report(&err);
     }
-}
 
-fn main() {
-    let sc1 = FibonacciCircuit::<10> {};
-    let sc2 = trivial::Circuit::<A2, C2Scalar>::default();
+    if config.no_verify && args.strict {
+        return report(&ExampleError::InvalidConfig(
+            "skipping the verification leaves the IVC unverified, which `--strict` refuses"
+                .to_owned(),
+        ));
+    }
 
-    // This folder will store the commitment key so that we don't have to generate it every time.
-    //
-    // NOTE: since the key files are not serialized, but reflected directly from memory, the
-    // functions to load them is `unsafe`
-    let key_cache = Path::new(".cache");
+    if args.print_config {
+        print!("{}", config.to_toml());
+        return ExitCode::SUCCESS;
+    }
 
-    println!("start setup primary commitment key: bn256");
+    if args.estimate {
+        print!("{}", CostEstimate::of(&config));
+        return ExitCode::SUCCESS;
+    }
 
-    // Safety: because the cache file is correct
-    let primary_commitment_key = unsafe {
-        CommitmentKey::<C1Affine>::load_or_setup_cache(
-            key_cache,
-            "bn256",
-            PRIMARY_COMMITMENT_KEY_SIZE,
-        )
-        .unwrap()
+    if args.dump_pp_digest {
+        println!("{}", pp_digest::pp_digest(&circuit, &config));
+        return ExitCode::SUCCESS;
+    }
+
+    if args.dump_gate_degree {
+        return match circuit.gate_degrees() {
+            Ok(degrees) => {
+                print!("{degrees}");
+                ExitCode::SUCCESS
+            }
+            Err(err) => report(&err),
+        };
+    }
+
+    if let Some(path) = &args.dump_constraint_dot {
+        return match dump_constraint_dot(&circuit, path) {
+            Ok(()) => {
+                println!("constraint system graph written to {path:?}");
+                ExitCode::SUCCESS
+            }
+            Err(err) => report(&err),
+        };
+    }
+
+    if args.validate_only {
+        return match mock_validate(&circuit, &config) {
+            Ok(()) => {
+                println!("mock prover: every constraint of the step is satisfied");
+                ExitCode::SUCCESS
+            }
+            Err(err) => report(&err),
+        };
+    }
+
+    if let Some(len) = args.prove_length {
+        // The plan may round up to whole steps, so the last pair proven may be past `len`
+        let index = config.fold_step_count * config.elements_num;
+        output::info(
+            output::Phase::Setup,
+            format_args!(
+                "proving the first {len} elements, up to elements #{index} and #{}: {} steps of \
+                 {} elements",
+                index + 1,
+                config.fold_step_count,
+                config.elements_num
+            ),
+        );
+    }
+
+    if args.validate_config {
+        let checks = config.validate(circuit.arity(), A2);
+        for check in &checks {
+            println!("{check}");
+        }
+
+        return if checks.iter().all(|check| check.result.is_ok()) {
+            println!("configuration is valid");
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        };
+    }
+
+    if args.emit_witness {
+        let emitted = match circuit {
+            CircuitSpec::Fibonacci => emit_witness(&config),
+            _ => warning::warn("--emit-witness is only supported by the fibonacci circuit"),
+        };
+        if let Err(err) = emitted {
+            return report(&err);
+        }
+    }
+
+    // Shut down when dropped at the end of `main`
+    let _metrics_server = match args.metrics_port.map(metrics::serve).transpose() {
+        Ok(server) => server,
+        Err(err) => return report(&ExampleError::Metrics(err)),
     };
 
-    println!("start setup secondary commitment key: grumpkin");
+    if !args.compare_key_sizes.is_empty() {
+        let report = key_sizes::compare_key_sizes(&circuit, &config, &args.compare_key_sizes);
+        print!("{report}");
 
-    // Safety: because the cache file is correct
-    let secondary_commitment_key = unsafe {
-        CommitmentKey::<C2Affine>::load_or_setup_cache(
-            key_cache,
-            "grumpkin",
-            SECONDARY_COMMITMENT_KEY_SIZE,
-        )
-        .unwrap()
+        return if report.smallest_working().is_some() {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        };
+    }
+
+    if args.compare_curves {
+        let report = compare_curves::compare_curves(&circuit, &config);
+        print!("{report}");
+
+        return if report.z_out_agrees() {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        };
+    }
+
+    if let Some(index) = args.compare_block_sizes {
+        if !matches!(circuit, CircuitSpec::Fibonacci) {
+            if let Err(err) =
+                warning::warn("--compare-block-sizes always folds the fibonacci circuit")
+            {
+                return report(&err);
+            }
+        }
+        let report = block_sizes::compare_block_sizes(&config, index);
+        print!("{report}");
+
+        return if report.z_out_agrees() {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        };
+    }
+
+    if !args.sweep_elements_num.is_empty() {
+        let configs = args
+            .sweep_elements_num
+            .iter()
+            .map(|&elements_num| RunConfig {
+                elements_num,
+                ..config.clone()
+            })
+            .collect();
+        let policy = if args.fail_fast {
+            BatchPolicy::FailFast
+        } else {
+            BatchPolicy::Continue
+        };
+
+        let summary = batch::run_batch(configs, policy, |config| {
+            run_circuit(&circuit, config, Some(&mut progress::print)).map(drop)
+        });
+        print!("{summary}");
+
+        return if summary.all_succeeded() {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        };
+    }
+
+    if let CircuitSpec::FiboMod { p } = &circuit {
+        if let Err(err) = report_pisano_period(*p, config.fold_step_count) {
+            return report(&err);
+        }
+    }
+
+    #[cfg(feature = "profiling")]
+    let mut profiler = args
+        .profile
+        .map(|Profile::Cpu| FoldProfiler::new(args.profile_output.clone()));
+    let mut on_event = |event: StepEvent| {
+        #[cfg(feature = "profiling")]
+        if let Some(profiler) = profiler.as_mut() {
+            profiler.on_event(event);
+        }
+        progress::print(event);
     };
 
-    let pp = new_default_pp::<A1, _, A2, _>(
-        SECONDARY_CIRCUIT_TABLE_SIZE as u32,
-        &primary_commitment_key,
-        &sc1,
-        PRIMARY_CIRCUIT_TABLE_SIZE as u32,
-        &secondary_commitment_key,
-        &sc2,
-    );
+    let no_verify = config.no_verify;
+    let mut circuit = circuit;
+    let result = if let Some(source) = &args.input_stream {
+        let mut consumed = vec![];
+        let result = reseed(&args, &mut config, circuit.arity())
+            .and_then(|()| open_input_stream(source))
+            .and_then(|reader| {
+                let mut inputs = input_stream::read_inputs(reader).inspect(|input| {
+                    if let Ok(input) = input {
+                        consumed.push(*input);
+                    }
+                });
+                run_circuit_streamed(&circuit, &config, &mut inputs, Some(&mut on_event))
+            });
+
+        // So that the outputs, e.g. the bundle, describe the steps actually folded
+        if let Some(streamed) = circuit.with_step_inputs(consumed) {
+            circuit = streamed;
+        }
+        result
+    } else {
+        // The commitment keys are cached after the first iteration, so the later ones mostly time
+        // the public parameters, folding and verification
+        let (timings, result) = repeat::repeat(args.repeat.get(), |iteration| {
+            if args.repeat.get() > 1 {
+                output::info(
+                    output::Phase::Fold,
+                    format_args!("iteration {}/{}", iteration + 1, args.repeat),
+                );
+            }
+            reseed(&args, &mut config, circuit.arity())?;
+            run_circuit(&circuit, &config, Some(&mut on_event))
+        });
+        if args.repeat.get() > 1 {
+            output::info(output::Phase::Fold, timings.to_string().trim_end());
+        }
+        result
+    };
+
+    let result = result.and_then(|summary| {
+        if args.print_sequence {
+            match circuit {
+                CircuitSpec::Fibonacci => print_sequence(&config, &summary)?,
+                _ => warning::warn("--print-sequence is only supported by the fibonacci circuit")?,
+            }
+        }
+
+        if let Some(expected) = args.assert_checksum {
+            match circuit {
+                CircuitSpec::Checksum { .. } => {
+                    assert_checksum(expected, config.field_format, &summary)?
+                }
+                _ => warning::warn("--assert-checksum is only supported by the checksum circuit")?,
+            }
+        }
+        if let Some(expected) = args.assert_steps {
+            assert_steps(expected, &summary)?;
+        }
 
-    // Input to be passed on the zero step to the primary circuit
-    let primary_z_0: [C1Scalar; A1] = [C1Scalar::ZERO, C1Scalar::ONE];
+        match (print_z_out, &circuit) {
+            (Some(encoding), _) => println!("{}", encoding.encode(&summary.z_out)),
+            (None, CircuitSpec::Average { .. }) => print_average(config.field_format, &summary),
+            (None, CircuitSpec::Pid { .. }) => print_pid(config.field_format, &summary),
+            (None, CircuitSpec::WindowAverage { weights, .. }) => {
+                print_window_average(config.field_format, weights, &summary)
+            }
+            (None, _) => {}
+        }
 
-    let mut ivc = IVC::new(&pp, &sc1, primary_z_0, &sc2, SECONDARY_Z_0, true)
-        .expect("failed to create `IVC`");
-    println!("ivc created");
+        if let Some(path) = &args.verify_transcript {
+            PublicTranscript::new(&circuit, &config, &summary).write(path)?;
+        }
 
-    for step in 1..FOLD_STEP_COUNT {
-        // you can modify circuit data here
-        ivc.fold_step(&pp, &sc1, &sc2)
-            .expect("failed to run fold step");
+        if args.output.is_none() && !args.output_proof_size {
+            return Ok(());
+        }
+        let bundle = ProofBundle::new(circuit, config, summary);
+        #[cfg(feature = "env-dump")]
+        let bundle = ProofBundle {
+            environment: args.env_dump.then(EnvInfo::current),
+            ..bundle
+        };
+        if args.output_proof_size {
+            output::info(output::Phase::Fold, bundle.size().to_string().trim_end());
+        }
+        match &args.output {
+            Some(path) => {
+                bundle.write(path)?;
+                if args.prove_then_verify_separate_process {
+                    verify_in_separate_process(&args, path)?;
+                }
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    });
 
-        println!("folding step {step} was successful");
+    match result {
+        Ok(()) if no_verify => {
+            output::info(
+                output::Phase::Fold,
+                "success, UNVERIFIED: the verification was skipped",
+            );
+            ExitCode::SUCCESS
+        }
+        Ok(()) => {
+            output::info(output::Phase::Fold, "success");
+            ExitCode::SUCCESS
+        }
+        Err(err) => report(&err),
     }
+}
 
-    ivc.verify(&pp).expect("failed to verify ivc");
-    println!("verification successful");
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(flags: &[&str]) -> Result<Args, clap::Error> {
+        Args::try_parse_from(["sirius-fibonacci-example"].iter().chain(flags))
+    }
+
+    #[test]
+    fn only_interleaved_fold_order_is_supported() {
+        let args = parse(&[]).unwrap();
+        assert_eq!(args.fold_order, FoldOrder::Interleaved);
+        args.fold_order.ensure_supported().unwrap();
+
+        let args = parse(&["--fold-order", "primary-batched"]).unwrap();
+        let err = args.fold_order.ensure_supported().unwrap_err();
+        assert!(matches!(err, ExampleError::Unsupported(_)), "{err}");
+    }
 
-    println!("success");
+    #[test]
+    fn every_registered_circuit_builds() {
+        for entry in CIRCUITS {
+            let args = parse(&["--circuit", entry.name]).unwrap();
+            assert!(std::ptr::eq(CircuitEntry::find(entry.name), entry));
+
+            let spec = args.circuit_spec();
+            assert_eq!(spec.arity(), entry.default_z_0.len(), "{}", entry.name);
+            spec.constraint_system()
+                .unwrap_or_else(|err| panic!("{}: {err}", entry.name));
+        }
+    }
+
+    #[test]
+    fn help_circuit_lists_the_registered_names_on_an_unknown_one() {
+        let err = parse(&["--help-circuit", "fibonaci"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::InvalidValue);
+
+        let message = err.to_string();
+        assert!(message.contains("fibonaci"), "{message}");
+        for entry in CIRCUITS {
+            assert!(message.contains(entry.name), "{} in {message}", entry.name);
+        }
+
+        let args = parse(&["--help-circuit", "sum"]).unwrap();
+        let help = CircuitEntry::find(args.help_circuit.as_deref().unwrap()).help(&args);
+        assert!(help.starts_with("sum: "), "{help}");
+        assert!(help.contains("arity:       1"), "{help}");
+        assert!(
+            help.contains("cargo run --release -- --circuit sum"),
+            "{help}"
+        );
+    }
 }
@@ -0,0 +1,124 @@
+//! Prometheus metrics of the folding run, served over HTTP with `--metrics-port`
+
+use std::{
+    io,
+    sync::{Arc, LazyLock},
+    thread::{self, JoinHandle},
+};
+
+use prometheus::{Encoder, Gauge, GaugeVec, IntCounter, Opts, Registry, TextEncoder};
+use tiny_http::{Header, Response, Server};
+
+/// Metrics of this process, recorded whether or not they are served
+pub struct Metrics {
+    registry: Registry,
+    pub folds_total: IntCounter,
+    pub last_step_seconds: Gauge,
+    /// Labeled by curve name
+    pub setup_seconds: GaugeVec,
+    pub verifications_succeeded_total: IntCounter,
+    pub verify_cache_hits_total: IntCounter,
+}
+
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(|| {
+    let registry = Registry::new();
+
+    let folds_total = IntCounter::new(
+        "folds_total",
+        "Fold steps performed, the base step excluded",
+    )
+    .unwrap();
+    let last_step_seconds =
+        Gauge::new("last_step_seconds", "Duration of the last fold step").unwrap();
+    let setup_seconds = GaugeVec::new(
+        Opts::new("setup_seconds", "Duration of the commitment key setup"),
+        &["curve"],
+    )
+    .unwrap();
+    let verifications_succeeded_total = IntCounter::new(
+        "verifications_succeeded_total",
+        "Successful IVC verifications",
+    )
+    .unwrap();
+    let verify_cache_hits_total = IntCounter::new(
+        "verify_cache_hits_total",
+        "Bundle verifications skipped as the bundle was verified before",
+    )
+    .unwrap();
+
+    registry.register(Box::new(folds_total.clone())).unwrap();
+    registry
+        .register(Box::new(last_step_seconds.clone()))
+        .unwrap();
+    registry.register(Box::new(setup_seconds.clone())).unwrap();
+    registry
+        .register(Box::new(verifications_succeeded_total.clone()))
+        .unwrap();
+    registry
+        .register(Box::new(verify_cache_hits_total.clone()))
+        .unwrap();
+
+    Metrics {
+        registry,
+        folds_total,
+        last_step_seconds,
+        setup_seconds,
+        verifications_succeeded_total,
+        verify_cache_hits_total,
+    }
+});
+
+impl Metrics {
+    /// Metrics in the Prometheus text format
+    fn encode(&self) -> String {
+        let mut buffer = vec![];
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("encoding into a vec can't fail");
+        String::from_utf8(buffer).expect("text format is utf8")
+    }
+}
+
+/// HTTP endpoint serving [`METRICS`], shut down on drop
+pub struct MetricsServer {
+    server: Arc<Server>,
+    handle: Option<JoinHandle<()>>,
+}
+
+/// Start serving [`METRICS`] on `port` of all interfaces, on every path
+pub fn serve(port: u16) -> io::Result<MetricsServer> {
+    let server = Arc::new(Server::http(("0.0.0.0", port)).map_err(io::Error::other)?);
+
+    let handle = thread::spawn({
+        let server = server.clone();
+        move || {
+            let content_type =
+                Header::from_bytes("Content-Type", "text/plain; version=0.0.4").unwrap();
+
+            // Ends when the server is unblocked on drop
+            for request in server.incoming_requests() {
+                let response =
+                    Response::from_string(METRICS.encode()).with_header(content_type.clone());
+
+                if let Err(err) = request.respond(response) {
+                    eprintln!("failed to respond with metrics: {err}");
+                }
+            }
+        }
+    });
+
+    Ok(MetricsServer {
+        server,
+        handle: Some(handle),
+    })
+}
+
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
@@ -0,0 +1,224 @@
+//! Synthesis check of a single step circuit with halo2's `MockProver`
+//!
+//! The mock prover evaluates every constraint on the assigned witness directly, without the
+//! commitment keys, the public parameters or the folding around the step. That makes a circuit
+//! bug show up in milliseconds with the row it is on, instead of as a failed fold step after the
+//! whole setup.
+//!
+//! Satisfied constraints alone don't show that a step computes what it should: an
+//! underconstrained circuit can be satisfied with the wrong output. So the values the step
+//! assigns to its `z_out` cells are also checked against its [`ReferenceStep`].
+
+use std::cell::RefCell;
+
+use sirius::{
+    halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        dev::MockProver,
+        plonk::{Advice, Circuit, Column, ConstraintSystem, Error},
+    },
+    prelude::{bn256::C1Scalar, StepCircuit},
+};
+
+use crate::{circuits::ReferenceStep, error::ExampleError, field::FieldFormat};
+
+/// Plain halo2 circuit of one step of `SC`: `z_i` is assigned as a witness, then the step is
+/// synthesized on it
+struct MockStep<'sc, const ARITY: usize, SC> {
+    sc: &'sc SC,
+    z_i: [C1Scalar; ARITY],
+    /// Values of the `z_out` cells returned by the step, once synthesized
+    z_out: RefCell<[Option<C1Scalar>; ARITY]>,
+}
+
+impl<const ARITY: usize, SC> Circuit<C1Scalar> for MockStep<'_, ARITY, SC>
+where
+    SC: StepCircuit<ARITY, C1Scalar>,
+{
+    /// Config of the step, and the column holding `z_i`
+    type Config = (SC::Config, Column<Advice>);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            sc: self.sc,
+            z_i: self.z_i,
+            z_out: RefCell::new([None; ARITY]),
+        }
+    }
+
+    fn configure(cs: &mut ConstraintSystem<C1Scalar>) -> Self::Config {
+        let z_i = cs.advice_column();
+        cs.enable_equality(z_i);
+
+        (SC::configure(cs), z_i)
+    }
+
+    fn synthesize(
+        &self,
+        (config, column): Self::Config,
+        mut layouter: impl Layouter<C1Scalar>,
+    ) -> Result<(), Error> {
+        let z_i = layouter.assign_region(
+            || "z_i",
+            |mut region| {
+                let z_i = (0..ARITY)
+                    .map(|row| {
+                        region.assign_advice(|| "z_i", column, row, || Value::known(self.z_i[row]))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(z_i.try_into().unwrap())
+            },
+        )?;
+
+        // halo2 errors carry no message, the mock prover reports the failed synthesis as such
+        let z_out = self
+            .sc
+            .synthesize_step(config, &mut layouter, &z_i)
+            .map_err(|_| Error::Synthesis)?;
+
+        let mut assigned = self.z_out.borrow_mut();
+        for (slot, cell) in assigned.iter_mut().zip(&z_out) {
+            cell.value().map(|value| *slot = Some(*value));
+        }
+
+        Ok(())
+    }
+}
+
+/// Check one step of `sc` from `z_i` with `MockProver`, in a table of `2^k` rows, and return
+/// the values it assigns to its `z_out` cells
+///
+/// Fails with [`ExampleError::MockProver`] listing every violated constraint, each with the
+/// region and row it is on, and with [`ExampleError::StepOutputMismatch`] if the constraints hold
+/// but `z_out` is not the [`ReferenceStep::reference_step`] of `z_i`.
+///
+/// ```
+/// # use sirius::{
+/// #     halo2_proofs::{
+/// #         circuit::Value,
+/// #         plonk::{Advice, Column, Expression, Selector},
+/// #         poly::Rotation,
+/// #     },
+/// #     ivc::{
+/// #         step_circuit::{AssignedCell, ConstraintSystem, Layouter},
+/// #         SynthesisError,
+/// #     },
+/// #     prelude::{bn256::C1Scalar, StepCircuit},
+/// # };
+/// # use sirius_fibonacci_example::{
+/// #     circuits::ReferenceStep, error::ExampleError, mock, FibonacciCircuit,
+/// # };
+/// let z_0 = [0, 1].map(C1Scalar::from);
+/// let z_out = mock::check_step(5, &FibonacciCircuit::new(3), z_0).unwrap();
+/// assert_eq!(z_out, [2, 3].map(C1Scalar::from));
+///
+/// /// `z_out = z_i + 1` in a gate, assigning `z_i + offset`, with `z_i + reference` as the
+/// /// reference step
+/// struct Increment {
+///     offset: u64,
+///     reference: u64,
+/// }
+///
+/// impl StepCircuit<1, C1Scalar> for Increment {
+///     type Config = (Selector, Column<Advice>);
+///
+///     fn configure(cs: &mut ConstraintSystem<C1Scalar>) -> Self::Config {
+///         let s = cs.selector();
+///         let z = cs.advice_column();
+///         cs.enable_equality(z);
+///         cs.create_gate("increment", |meta| {
+///             let s = meta.query_selector(s);
+///             let z_i = meta.query_advice(z, Rotation::cur());
+///             let z_out = meta.query_advice(z, Rotation::next());
+///             [s * (z_out - z_i - Expression::Constant(C1Scalar::from(1)))]
+///         });
+///         (s, z)
+///     }
+///
+///     fn synthesize_step(
+///         &self,
+///         (s, z): Self::Config,
+///         layouter: &mut impl Layouter<C1Scalar>,
+///         [z_i]: &[AssignedCell<C1Scalar, C1Scalar>; 1],
+///     ) -> Result<[AssignedCell<C1Scalar, C1Scalar>; 1], SynthesisError> {
+///         let z_out = layouter.assign_region(
+///             || "increment",
+///             |mut region| {
+///                 s.enable(&mut region, 0)?;
+///                 z_i.copy_advice(|| "z_i", &mut region, z, 0)?;
+///                 let z_out = z_i.value().map(|z_i| *z_i + C1Scalar::from(self.offset));
+///                 region.assign_advice(|| "z_out", z, 1, || z_out)
+///             },
+///         )?;
+///         Ok([z_out])
+///     }
+/// }
+///
+/// impl ReferenceStep<1, C1Scalar> for Increment {
+///     fn reference_step(&self, [z_i]: &[C1Scalar; 1]) -> [C1Scalar; 1] {
+///         [*z_i + C1Scalar::from(self.reference)]
+///     }
+/// }
+///
+/// let z_0 = [C1Scalar::from(5)];
+/// let step = |offset, reference| mock::check_step(4, &Increment { offset, reference }, z_0);
+/// assert_eq!(step(1, 1).unwrap(), [C1Scalar::from(6)]);
+///
+/// // Assigning `z_i + 2` violates the gate, on the row of the region it is enabled on
+/// let err = step(2, 2).unwrap_err();
+/// assert!(matches!(err, ExampleError::MockProver(_)), "{err}");
+/// let err = err.to_string();
+/// assert!(err.contains("'increment'") && err.contains("not satisfied"), "{err}");
+/// assert!(err.contains("at offset 0"), "{err}");
+///
+/// // Satisfied, but not the function the reference step computes
+/// let err = step(1, 2).unwrap_err();
+/// assert!(matches!(err, ExampleError::StepOutputMismatch { .. }), "{err}");
+/// ```
+pub fn check_step<const ARITY: usize, SC>(
+    k: u32,
+    sc: &SC,
+    z_i: [C1Scalar; ARITY],
+) -> Result<[C1Scalar; ARITY], ExampleError>
+where
+    SC: StepCircuit<ARITY, C1Scalar> + ReferenceStep<ARITY, C1Scalar>,
+{
+    let _span = tracing::info_span!("mock_prover", k).entered();
+
+    let step = MockStep {
+        sc,
+        z_i,
+        z_out: RefCell::new([None; ARITY]),
+    };
+    let prover = MockProver::run(k, &step, vec![])
+        .map_err(|err| ExampleError::MockProver(format!("synthesis failed: {err:?}")))?;
+
+    prover.verify().map_err(|failures| {
+        ExampleError::MockProver(
+            failures
+                .iter()
+                .map(|failure| format!("  {failure}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    })?;
+
+    let assigned = step.z_out.into_inner();
+    let mut z_out = [C1Scalar::default(); ARITY];
+    for (index, (value, assigned)) in z_out.iter_mut().zip(assigned).enumerate() {
+        *value = assigned.ok_or_else(|| {
+            ExampleError::MockProver(format!("  z_out[{index}] has no assigned value"))
+        })?;
+    }
+
+    let expected = sc.reference_step(&z_i);
+    if z_out != expected {
+        return Err(ExampleError::StepOutputMismatch {
+            got: FieldFormat::Auto.display_all(&z_out),
+            expected: FieldFormat::Auto.display_all(&expected),
+        });
+    }
+
+    Ok(z_out)
+}
@@ -0,0 +1,117 @@
+//! Informational output of the example, each line tagged with the phase of the run it is about,
+//! so that phases can be silenced separately, and optionally teed to a log file
+
+use std::{
+    fmt,
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// File every output line is also appended to, see [`set_log_file`]
+static LOG_FILE: Mutex<Option<File>> = Mutex::new(None);
+
+/// Phase of a run an output line is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Commitment key setup and IVC creation, instant on a warm cache
+    Setup,
+    /// Fold steps and verification
+    Fold,
+}
+
+impl Phase {
+    fn quiet(self) -> &'static AtomicBool {
+        static SETUP: AtomicBool = AtomicBool::new(false);
+        static FOLD: AtomicBool = AtomicBool::new(false);
+
+        match self {
+            Self::Setup => &SETUP,
+            Self::Fold => &FOLD,
+        }
+    }
+}
+
+/// Hide (or show again) every later [`info`] about `phase`
+pub fn set_quiet(phase: Phase, quiet: bool) {
+    phase.quiet().store(quiet, Ordering::Relaxed);
+}
+
+/// Print `message` about `phase`, unless that phase is quiet
+///
+/// Warnings and errors don't go through here, they are never hidden. The log file gets `message`
+/// even if the phase is quiet.
+pub fn info(phase: Phase, message: impl fmt::Display) {
+    if !phase.quiet().load(Ordering::Relaxed) {
+        println!("{message}");
+    }
+    log(message);
+}
+
+/// From now on, also append every output line to the file at `path`, each with a timestamp
+///
+/// The file is created if missing, and every line is written through to it at once, so the log
+/// of a crashed run ends at its last line
+///
+/// ```
+/// # use sirius_fibonacci_example::{
+/// #     config::RunConfig, output::{self, Phase}, run_circuit, testing::test_cache_dir,
+/// #     CircuitSpec,
+/// # };
+/// let dir = tempfile::tempdir().unwrap();
+/// let path = dir.path().join("run.log");
+/// output::set_log_file(&path).unwrap();
+/// // Logged even though it isn't printed
+/// output::set_quiet(Phase::Fold, true);
+///
+/// let config = RunConfig {
+///     fold_step_count: 2,
+///     cache_dir: test_cache_dir(),
+///     ..RunConfig::default()
+/// };
+/// run_circuit(&CircuitSpec::Fibonacci, &config, None).unwrap();
+///
+/// let log = std::fs::read_to_string(&path).unwrap();
+/// assert!(log.lines().all(|line| line.starts_with('[')), "{log}");
+/// let position = |marker: &str| log.find(marker).unwrap_or_else(|| panic!("{marker:?} in {log}"));
+/// let setup = position("start setup primary commitment key");
+/// let secondary = position("start setup secondary commitment key");
+/// let proven = position("proven elements #20");
+/// let verification = position("verification: ");
+/// assert!(setup < secondary && secondary < proven && proven < verification);
+/// ```
+pub fn set_log_file(path: &Path) -> io::Result<()> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    *LOG_FILE.lock().unwrap_or_else(|err| err.into_inner()) = Some(file);
+    Ok(())
+}
+
+/// Append `message` to the log file, if there is one, without printing it
+///
+/// For the lines printed without [`info`], e.g. warnings and errors. A failed write is reported
+/// once on stderr, and the log file dropped: the run itself goes on.
+pub fn log(message: impl fmt::Display) {
+    let mut log_file = LOG_FILE.lock().unwrap_or_else(|err| err.into_inner());
+    let Some(file) = log_file.as_mut() else {
+        return;
+    };
+
+    // Seconds since the epoch with milliseconds, no calendar: enough to order and time the lines
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs_f64();
+    let written = message
+        .to_string()
+        .lines()
+        .try_for_each(|line| writeln!(file, "[{timestamp:.3}] {line}"));
+    if let Err(err) = written.and_then(|()| file.flush()) {
+        eprintln!("failed to write the log file, no longer logging: {err}");
+        *log_file = None;
+    }
+}
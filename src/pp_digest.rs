@@ -0,0 +1,86 @@
+//! Stable digest of the public parameters, to confirm two setups built the same ones
+//!
+//! `sirius` v0.1.1 neither serializes the public parameters nor exposes their digest, so the
+//! digest covers what `new_default_pp` builds them from instead: the shape of both step circuits,
+//! the table and commitment key sizes of both circuits, their arities and the sirius version. The
+//! commitment keys are generated deterministically from their size, so equal inputs give equal
+//! public parameters. `z_0` and the step count are run data, not part of them.
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::{build_info, config::RunConfig, secondary::SecondaryCircuit, CircuitSpec, A2};
+
+/// Everything [`pp_digest`] covers, serialized as JSON in field order
+#[derive(Serialize)]
+struct PpInputs {
+    sirius_version: &'static str,
+    /// Without its per-step data, which is witness only
+    circuit: CircuitSpec,
+    primary_arity: usize,
+    secondary_circuit: SecondaryCircuit,
+    secondary_arity: usize,
+    /// Shape of the Fibonacci circuit
+    elements_num: usize,
+    primary_circuit_table_size: usize,
+    primary_commitment_key_size: usize,
+    secondary_circuit_table_size: usize,
+    secondary_commitment_key_size: usize,
+}
+
+/// SHA-256 of the inputs of the public parameters of `circuit` run with `config`, as hex
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{config::RunConfig, pp_digest::pp_digest, CircuitSpec};
+/// let config = RunConfig::default();
+/// let digest = pp_digest(&CircuitSpec::Fibonacci, &config);
+/// assert_eq!(digest.len(), 64);
+/// assert_eq!(pp_digest(&CircuitSpec::Fibonacci, &config.clone()), digest);
+///
+/// // Run data leaves it unchanged
+/// let run_data = RunConfig {
+///     primary_z_0: vec![C1Scalar::from(2), C1Scalar::from(3)],
+///     fold_step_count: 7,
+///     ..RunConfig::default()
+/// };
+/// assert_eq!(pp_digest(&CircuitSpec::Fibonacci, &run_data), digest);
+/// assert_eq!(
+///     pp_digest(&CircuitSpec::Sum { addends: vec![1, 2] }, &config),
+///     pp_digest(&CircuitSpec::Sum { addends: vec![5] }, &config)
+/// );
+///
+/// // A size does not
+/// let resized = RunConfig {
+///     primary_circuit_table_size: config.primary_circuit_table_size + 1,
+///     ..RunConfig::default()
+/// };
+/// assert_ne!(pp_digest(&CircuitSpec::Fibonacci, &resized), digest);
+/// let resized = RunConfig {
+///     secondary_commitment_key_size: config.secondary_commitment_key_size + 1,
+///     ..RunConfig::default()
+/// };
+/// assert_ne!(pp_digest(&CircuitSpec::Fibonacci, &resized), digest);
+/// ```
+pub fn pp_digest(circuit: &CircuitSpec, config: &RunConfig) -> String {
+    let inputs = PpInputs {
+        sirius_version: build_info::SIRIUS_VERSION,
+        circuit: circuit
+            .with_step_inputs(vec![])
+            .unwrap_or_else(|| circuit.clone()),
+        primary_arity: circuit.arity(),
+        secondary_circuit: config.secondary_circuit,
+        secondary_arity: A2,
+        elements_num: config.elements_num,
+        primary_circuit_table_size: config.primary_circuit_table_size,
+        primary_commitment_key_size: config.primary_commitment_key_size,
+        secondary_circuit_table_size: config.secondary_circuit_table_size,
+        secondary_commitment_key_size: config.secondary_commitment_key_size,
+    };
+    let json = serde_json::to_vec(&inputs).expect("inputs are always representable");
+
+    Sha256::digest(json)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
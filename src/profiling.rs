@@ -0,0 +1,68 @@
+//! CPU profiling of the fold loop, enabled by the `profiling` feature
+
+use std::{fs::File, path::PathBuf};
+
+use pprof::ProfilerGuard;
+
+use crate::{
+    output::{self, Phase},
+    progress::StepEvent,
+};
+
+/// Samples per second
+const FREQUENCY: i32 = 997;
+
+/// Profiles the fold loop of every run it gets the events of, and writes the flamegraph of the
+/// last one on [`StepEvent::FoldingFinished`]
+///
+/// The setup is left out, it would dominate the profile on a cold commitment key cache, and so is
+/// the verification
+pub struct FoldProfiler {
+    output: PathBuf,
+    guard: Option<ProfilerGuard<'static>>,
+}
+
+impl FoldProfiler {
+    /// Write the flamegraph SVG to `output`
+    pub fn new(output: PathBuf) -> Self {
+        Self {
+            output,
+            guard: None,
+        }
+    }
+
+    /// Progress callback, to be chained with the one printing the progress
+    pub fn on_event(&mut self, event: StepEvent) {
+        match event {
+            StepEvent::SetupFinished => match ProfilerGuard::new(FREQUENCY) {
+                Ok(guard) => self.guard = Some(guard),
+                Err(err) => eprintln!("failed to start the cpu profiler: {err}"),
+            },
+            StepEvent::FoldingFinished { .. } => {
+                if let Some(guard) = self.guard.take() {
+                    self.write(&guard);
+                }
+            }
+            StepEvent::SetupStarted | StepEvent::StepFolded { .. } => {}
+        }
+    }
+
+    fn write(&self, guard: &ProfilerGuard<'static>) {
+        let written = guard
+            .report()
+            .build()
+            .map_err(|err| err.to_string())
+            .and_then(|report| {
+                let file = File::create(&self.output).map_err(|err| err.to_string())?;
+                report.flamegraph(file).map_err(|err| err.to_string())
+            });
+
+        match written {
+            Ok(()) => output::info(
+                Phase::Fold,
+                format_args!("fold loop flamegraph written to {:?}", self.output),
+            ),
+            Err(err) => eprintln!("failed to write the flamegraph {:?}: {err}", self.output),
+        }
+    }
+}
@@ -0,0 +1,56 @@
+//! Progress reporting of a run, decoupled from the run itself
+
+use crate::output::{self, Phase};
+
+/// Phase of a run, reported to the progress callback of [`crate::run_ivc_per_step`]
+///
+/// ```
+/// # use sirius_fibonacci_example::{
+/// #     config::RunConfig, progress::StepEvent, run_circuit, testing::test_cache_dir,
+/// #     CircuitSpec,
+/// # };
+/// let config = RunConfig {
+///     fold_step_count: 3,
+///     cache_dir: test_cache_dir(),
+///     ..RunConfig::default()
+/// };
+///
+/// let mut events = vec![];
+/// let mut record = |event: StepEvent| events.push(event);
+/// run_circuit(&CircuitSpec::Fibonacci, &config, Some(&mut record)).unwrap();
+/// assert_eq!(
+///     events,
+///     [
+///         StepEvent::SetupStarted,
+///         StepEvent::SetupFinished,
+///         StepEvent::StepFolded { step: 1, steps: 3 },
+///         StepEvent::StepFolded { step: 2, steps: 3 },
+///         StepEvent::FoldingFinished { steps: 3 },
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepEvent {
+    /// Commitment key setup is about to start
+    SetupStarted,
+    /// Commitment keys and public parameters are ready, and the IVC is created with its base step
+    SetupFinished,
+    /// Fold step `step` succeeded, out of `steps` planned, both counting the base step `0`
+    StepFolded { step: usize, steps: usize },
+    /// Folding is over after `steps` steps, counting the base step, and the verification starts
+    FoldingFinished { steps: usize },
+}
+
+/// Progress callback of the command line: prints every phase as a line of output
+pub fn print(event: StepEvent) {
+    match event {
+        StepEvent::SetupStarted => output::info(Phase::Setup, "start setup"),
+        StepEvent::SetupFinished => output::info(Phase::Setup, "ivc created"),
+        StepEvent::StepFolded { step, steps } => output::info(
+            Phase::Fold,
+            format_args!("folding step {step} was successful ({}/{steps})", step + 1),
+        ),
+        // Followed by the verification result
+        StepEvent::FoldingFinished { .. } => {}
+    }
+}
@@ -0,0 +1,174 @@
+//! Targeted advice for runs that failed because a table or commitment key is too small
+//!
+//! Sirius errors are only available as their debug representation, so they are classified by the
+//! names of the errors reporting an insufficient size. This is best effort: an unrecognized error
+//! gets no advice, and an error that doesn't tell the side gets the flags of both.
+
+use std::fmt;
+
+/// Circuit of the IVC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Primary,
+    Secondary,
+}
+
+/// What turned out to be too small
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    /// The circuit needs more rows than the table has
+    CircuitTable,
+    /// The commitment key is shorter than what it has to commit to
+    CommitmentKey,
+}
+
+/// Insufficient size reported by a sirius error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Undersized {
+    pub resource: Resource,
+    /// `None` if the error doesn't tell which circuit it comes from
+    pub side: Option<Side>,
+}
+
+impl Undersized {
+    /// Classify the debug representation of a sirius error
+    ///
+    /// ```
+    /// # use sirius_fibonacci_example::remedy::{Resource, Side, Undersized};
+    /// let cases = [
+    ///     (
+    ///         "Primary(Plonk(NotEnoughRowsAvailable { current_k: 17 }))",
+    ///         Resource::CircuitTable,
+    ///         Some(Side::Primary),
+    ///     ),
+    ///     (
+    ///         "Secondary(Plonk(NotEnoughRowsAvailable { current_k: 17 }))",
+    ///         Resource::CircuitTable,
+    ///         Some(Side::Secondary),
+    ///     ),
+    ///     (
+    ///         "Primary(Commitment(TooLongInput { len: 2097152, ck_len: 1048576 }))",
+    ///         Resource::CommitmentKey,
+    ///         Some(Side::Primary),
+    ///     ),
+    ///     (
+    ///         "Secondary(Commitment(TooLongInput { len: 2097152, ck_len: 1048576 }))",
+    ///         Resource::CommitmentKey,
+    ///         Some(Side::Secondary),
+    ///     ),
+    ///     // No side named, the hint then names the flags of both
+    ///     (
+    ///         "NotEnoughRowsAvailable { current_k: 17 }",
+    ///         Resource::CircuitTable,
+    ///         None,
+    ///     ),
+    ///     ("WhileCommit(TooLongInput)", Resource::CommitmentKey, None),
+    /// ];
+    /// for (err, resource, side) in cases {
+    ///     assert_eq!(
+    ///         Undersized::classify(err),
+    ///         Some(Undersized { resource, side }),
+    ///         "{err}"
+    ///     );
+    /// }
+    ///
+    /// let hint = Undersized::classify("NotEnoughRowsAvailable").unwrap().to_string();
+    /// assert!(hint.contains("--primary-table-size"), "{hint}");
+    /// assert!(hint.contains("--secondary-table-size"), "{hint}");
+    ///
+    /// // Failures that aren't about a size get no hint
+    /// assert_eq!(Undersized::classify("Primary(Verify(InvalidProof))"), None);
+    /// ```
+    pub fn classify(err: &str) -> Option<Self> {
+        let err = err.to_lowercase();
+
+        let resource = if err.contains("notenoughrowsavailable") {
+            Resource::CircuitTable
+        } else if [
+            "toolonginput",
+            "commitmentkey",
+            "commitment key",
+            "key size",
+        ]
+        .iter()
+        .any(|marker| err.contains(marker))
+        {
+            Resource::CommitmentKey
+        } else {
+            return None;
+        };
+
+        Some(Self {
+            resource,
+            side: Side::mentioned_in(&err),
+        })
+    }
+
+    /// Constant and flag to increase for `side`
+    fn knob(resource: Resource, side: Side) -> (&'static str, &'static str) {
+        match (resource, side) {
+            (Resource::CircuitTable, Side::Primary) => {
+                ("PRIMARY_CIRCUIT_TABLE_SIZE", "--primary-table-size")
+            }
+            (Resource::CircuitTable, Side::Secondary) => {
+                ("SECONDARY_CIRCUIT_TABLE_SIZE", "--secondary-table-size")
+            }
+            (Resource::CommitmentKey, Side::Primary) => {
+                ("PRIMARY_COMMITMENT_KEY_SIZE", "--primary-key-size")
+            }
+            (Resource::CommitmentKey, Side::Secondary) => {
+                ("SECONDARY_COMMITMENT_KEY_SIZE", "--secondary-key-size")
+            }
+        }
+    }
+}
+
+impl fmt::Display for Undersized {
+    /// The remediation line
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let what = match self.resource {
+            Resource::CircuitTable => "circuit table",
+            Resource::CommitmentKey => "commitment key",
+        };
+
+        match self.side {
+            Some(side) => {
+                let (constant, flag) = Self::knob(self.resource, side);
+                write!(f, "the {side} {what} is too small, increase `{constant}` or pass a larger `{flag}`")
+            }
+            None => {
+                let (primary, primary_flag) = Self::knob(self.resource, Side::Primary);
+                let (secondary, secondary_flag) = Self::knob(self.resource, Side::Secondary);
+                write!(
+                    f,
+                    "a {what} is too small, increase `{primary}` (`{primary_flag}`) or `{secondary}` \
+                     (`{secondary_flag}`)"
+                )
+            }
+        }
+    }
+}
+
+impl Side {
+    /// The side the debug representation of a sirius error names, if any
+    pub fn mentioned_in(err: &str) -> Option<Self> {
+        let err = err.to_lowercase();
+
+        if err.contains("secondary") {
+            Some(Self::Secondary)
+        } else if err.contains("primary") {
+            Some(Self::Primary)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Primary => "primary",
+            Self::Secondary => "secondary",
+        })
+    }
+}
@@ -0,0 +1,105 @@
+//! Timing statistics over repeated runs of the whole prove-and-verify pipeline
+
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use crate::error::ExampleError;
+
+/// Wall time of every finished iteration, in the order run
+pub struct RepeatReport {
+    pub durations: Vec<Duration>,
+}
+
+impl RepeatReport {
+    pub fn min(&self) -> Option<Duration> {
+        self.durations.iter().min().copied()
+    }
+
+    /// Lower median for an even number of iterations
+    pub fn median(&self) -> Option<Duration> {
+        let mut sorted = self.durations.clone();
+        sorted.sort();
+        sorted.get(sorted.len().saturating_sub(1) / 2).copied()
+    }
+
+    pub fn max(&self) -> Option<Duration> {
+        self.durations.iter().max().copied()
+    }
+}
+
+impl fmt::Display for RepeatReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.min(), self.median(), self.max()) {
+            (Some(min), Some(median), Some(max)) => writeln!(
+                f,
+                "{} iterations: min {min:.2?}, median {median:.2?}, max {max:.2?}",
+                self.durations.len()
+            ),
+            _ => writeln!(f, "no iteration finished"),
+        }
+    }
+}
+
+/// Call `run` with the iteration index `iterations` times in sequence, timing every call
+///
+/// Stops at the first failure. Returns the timings of the successful iterations with the result
+/// of the last one run.
+///
+/// ```
+/// # use sirius_fibonacci_example::{
+/// #     config::RunConfig, error::ExampleError, metrics::METRICS, repeat::repeat, run_circuit,
+/// #     testing::test_cache_dir, CircuitSpec,
+/// # };
+/// let config = RunConfig {
+///     fold_step_count: 2,
+///     cache_dir: test_cache_dir(),
+///     ..RunConfig::default()
+/// };
+///
+/// // As `--repeat 2`: the whole pipeline, verification included, runs twice
+/// let mut verified = vec![];
+/// let (report, last) = repeat(2, |iteration| {
+///     let summary = run_circuit(&CircuitSpec::Fibonacci, &config, None)?;
+///     verified.push((iteration, summary.verification.unwrap().all_passed()));
+///     Ok(summary)
+/// });
+/// assert_eq!(last.unwrap().steps, 2);
+/// assert_eq!(report.durations.len(), 2);
+/// assert_eq!(verified, [(0, true), (1, true)]);
+/// assert_eq!(METRICS.verifications_succeeded_total.get(), 2);
+///
+/// // The iterations after a failed one are not run
+/// let mut runs = 0;
+/// let (report, last) = repeat(3, |iteration| {
+///     runs += 1;
+///     match iteration {
+///         0 => Ok(()),
+///         _ => Err(ExampleError::Verify("broken".to_owned())),
+///     }
+/// });
+/// assert!(last.is_err());
+/// assert_eq!((runs, report.durations.len()), (2, 1));
+/// ```
+pub fn repeat<T>(
+    iterations: usize,
+    mut run: impl FnMut(usize) -> Result<T, ExampleError>,
+) -> (RepeatReport, Result<T, ExampleError>) {
+    assert!(iterations >= 1, "at least one iteration is required");
+
+    let mut durations = Vec::with_capacity(iterations);
+    for iteration in 0..iterations {
+        let start = Instant::now();
+        let result = run(iteration);
+
+        if result.is_ok() {
+            durations.push(start.elapsed());
+        }
+        if result.is_err() || iteration + 1 == iterations {
+            return (RepeatReport { durations }, result);
+        }
+    }
+
+    unreachable!("the last iteration returns")
+}
@@ -0,0 +1,421 @@
+//! Step-by-step control over the IVC lifecycle
+
+use std::{collections::VecDeque, time::Instant};
+
+use sirius::{
+    ivc::step_circuit::trivial,
+    prelude::{
+        bn256::{new_default_pp, C1Scalar, C2Scalar},
+        CommitmentKey, StepCircuit, IVC,
+    },
+};
+
+use crate::{
+    circuits::{ReferenceStep, SumCircuit},
+    config::RunConfig,
+    curve::{Bn256, CurveSetup, Grumpkin},
+    error::ExampleError,
+    metrics::METRICS,
+    output::{self, Phase},
+    remedy::Side,
+    secondary::SecondaryCircuit,
+    A2,
+};
+
+/// Commitment keys of both curves of the cycle, borrowed by every [`FoldRunner`] made with them
+pub struct CommitmentKeys {
+    primary: CommitmentKey<<Bn256 as CurveSetup>::Affine>,
+    secondary: CommitmentKey<<Grumpkin as CurveSetup>::Affine>,
+}
+
+impl CommitmentKeys {
+    /// Load the keys of the sizes in `config` from its cache dir, or generate and cache them
+    pub fn setup(config: &RunConfig) -> Result<Self, ExampleError> {
+        let _span = tracing::info_span!("setup").entered();
+
+        // This folder will store the commitment key so that we don't have to generate it every
+        // time.
+        //
+        // NOTE: since the key files are not serialized, but reflected directly from memory,
+        // loading them is `unsafe`, see [`CurveSetup::load_or_setup`]
+        let key_cache = config.cache_dir.as_path();
+
+        output::info(
+            Phase::Setup,
+            format_args!("start setup primary commitment key: {}", Bn256::NAME),
+        );
+        let setup_start = Instant::now();
+        let primary =
+            tracing::info_span!("commitment_key", curve = Bn256::NAME).in_scope(|| {
+                Bn256::load_or_setup(
+                    key_cache,
+                    config.primary_commitment_key_size,
+                    config.cache_retries,
+                )
+            })?;
+        METRICS
+            .setup_seconds
+            .with_label_values(&[Bn256::NAME])
+            .set(setup_start.elapsed().as_secs_f64());
+
+        output::info(
+            Phase::Setup,
+            format_args!("start setup secondary commitment key: {}", Grumpkin::NAME),
+        );
+        let setup_start = Instant::now();
+        let secondary =
+            tracing::info_span!("commitment_key", curve = Grumpkin::NAME).in_scope(|| {
+                Grumpkin::load_or_setup(
+                    key_cache,
+                    config.secondary_commitment_key_size,
+                    config.cache_retries,
+                )
+            })?;
+        METRICS
+            .setup_seconds
+            .with_label_values(&[Grumpkin::NAME])
+            .set(setup_start.elapsed().as_secs_f64());
+
+        Ok(Self { primary, secondary })
+    }
+}
+
+/// Operation on the type-erased public parameters & IVC of a [`FoldRunner`]
+enum Op<'sc, SC> {
+    Fold(&'sc SC),
+    /// Create the IVC anew from `z_0`, with the base step of `SC`
+    Restart(&'sc SC),
+    Verify,
+}
+
+/// State of a [`FoldRunner`] after one of its steps, see [`FoldRunner::rewind`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint<const ARITY: usize> {
+    /// Steps folded, including the base step
+    pub index: usize,
+    pub z_out: [C1Scalar; ARITY],
+    pub secondary_z_out: [C2Scalar; A2],
+}
+
+/// IVC of the primary step circuit `SC`, folded one step at a time
+///
+/// Owns the public parameters, the IVC and the secondary circuit, the commitment keys are
+/// borrowed from [`CommitmentKeys`]. `sirius` types are generic over many parameters, so they are
+/// kept in a closure rather than named here.
+pub struct FoldRunner<'key, const ARITY: usize, SC> {
+    ivc: Box<dyn FnMut(Op<'_, SC>) -> Result<(), String> + 'key>,
+    /// Output of the last folded step, computed with [`ReferenceStep`]
+    z_out: [C1Scalar; ARITY],
+    /// Output of the last folded step of the secondary circuit, computed with
+    /// [`SecondaryCircuit::reference_step`]
+    secondary_z_out: [C2Scalar; A2],
+    secondary_circuit: SecondaryCircuit,
+    index: usize,
+    /// The last [`RunConfig::checkpoints`] states, the current one last
+    checkpoints: VecDeque<Checkpoint<ARITY>>,
+    checkpoint_capacity: usize,
+    /// Every step circuit folded, the base step first, to re-fold on [`FoldRunner::rewind`];
+    /// empty without checkpoints
+    history: Vec<SC>,
+}
+
+impl<'key, const ARITY: usize, SC> FoldRunner<'key, ARITY, SC>
+where
+    SC: StepCircuit<ARITY, C1Scalar> + ReferenceStep<ARITY, C1Scalar> + Clone + 'key,
+{
+    /// Build the public parameters and create the IVC with the base step of `sc1`, paired with
+    /// the secondary circuit of `config`
+    ///
+    /// Only the data of later step circuits may differ from `sc1`, the shape must stay the same,
+    /// as the public parameters are built from it
+    pub fn new(
+        keys: &'key CommitmentKeys,
+        config: &RunConfig,
+        sc1: &SC,
+    ) -> Result<Self, ExampleError> {
+        match config.secondary_circuit {
+            SecondaryCircuit::Trivial => Self::with_secondary(
+                keys,
+                config,
+                sc1,
+                trivial::Circuit::<A2, C2Scalar>::default(),
+            ),
+            SecondaryCircuit::Counter => {
+                Self::with_secondary(keys, config, sc1, SumCircuit::new(1))
+            }
+        }
+    }
+
+    /// [`FoldRunner::new`] with `sc2` as the secondary circuit, the same on every step
+    fn with_secondary<SC2>(
+        keys: &'key CommitmentKeys,
+        config: &RunConfig,
+        sc1: &SC,
+        sc2: SC2,
+    ) -> Result<Self, ExampleError>
+    where
+        SC2: StepCircuit<A2, C2Scalar> + 'key,
+    {
+        let _span = tracing::info_span!("ivc_create").entered();
+
+        config.ensure_valid(ARITY, A2)?;
+
+        let pp = new_default_pp::<ARITY, _, A2, _>(
+            config.secondary_circuit_table_size as u32,
+            &keys.primary,
+            sc1,
+            config.primary_circuit_table_size as u32,
+            &keys.secondary,
+            &sc2,
+        );
+
+        // Lengths are checked by `ensure_valid`
+        let primary_z_0: [C1Scalar; ARITY] = config.primary_z_0.clone().try_into().unwrap();
+        let secondary_z_0: [C2Scalar; A2] = config.secondary_z_0.clone().try_into().unwrap();
+
+        // `IVC::new` performs the base step itself: after it the state is `z_1 = F(z_0)`. The
+        // last argument is the debug mode, which additionally checks that each step is satisfied
+        // while folding, it is not an extra step.
+        let debug_mode = true;
+        let mut ivc = IVC::new(&pp, sc1, primary_z_0, &sc2, secondary_z_0, debug_mode)
+            .map_err(|err| ExampleError::IvcCreate(format!("{err:?}")))?;
+
+        let mut runner = Self {
+            ivc: Box::new(move |op| match op {
+                Op::Fold(sc1) => ivc
+                    .fold_step(&pp, sc1, &sc2)
+                    .map_err(|err| format!("{err:?}")),
+                Op::Restart(sc1) => {
+                    ivc = IVC::new(&pp, sc1, primary_z_0, &sc2, secondary_z_0, debug_mode)
+                        .map_err(|err| format!("{err:?}"))?;
+                    Ok(())
+                }
+                Op::Verify => ivc.verify(&pp).map_err(|err| format!("{err:?}")),
+            }),
+            z_out: sc1.reference_step(&primary_z_0),
+            secondary_z_out: config.secondary_circuit.reference_step(&secondary_z_0),
+            secondary_circuit: config.secondary_circuit,
+            index: 1,
+            checkpoints: VecDeque::with_capacity(config.checkpoints),
+            checkpoint_capacity: config.checkpoints,
+            history: vec![],
+        };
+        runner.checkpoint(sc1);
+
+        Ok(runner)
+    }
+
+    /// Keep the current state as a checkpoint, with `sc1`, the step circuit that got to it
+    fn checkpoint(&mut self, sc1: &SC) {
+        if self.checkpoint_capacity == 0 {
+            return;
+        }
+
+        if self.checkpoints.len() == self.checkpoint_capacity {
+            self.checkpoints.pop_front();
+        }
+        self.checkpoints.push_back(Checkpoint {
+            index: self.index,
+            z_out: self.z_out,
+            secondary_z_out: self.secondary_z_out,
+        });
+        self.history.push(sc1.clone());
+    }
+
+    /// Fold one step of `sc1`
+    pub fn step(&mut self, sc1: &SC) -> Result<(), ExampleError> {
+        let _span = tracing::info_span!("fold_step", step = self.index).entered();
+
+        (self.ivc)(Op::Fold(sc1)).map_err(|err| ExampleError::FoldStep {
+            step: self.index,
+            err,
+        })?;
+
+        self.z_out = sc1.reference_step(&self.z_out);
+        self.secondary_z_out = self.secondary_circuit.reference_step(&self.secondary_z_out);
+        self.index += 1;
+        self.checkpoint(sc1);
+
+        Ok(())
+    }
+
+    /// Restore the IVC to its state `n` steps earlier, one of the last [`RunConfig::checkpoints`]
+    /// states, and return its checkpoint
+    ///
+    /// The steps after it are dropped, so they can be folded again, e.g. with other data.
+    /// `sirius` v0.1.1 can neither clone nor serialize an IVC, so a checkpoint is only the state of
+    /// the runner: restoring creates the IVC anew from `z_0` and re-folds the step circuits up to
+    /// the checkpoint, which takes as long as folding them did.
+    ///
+    /// ```
+    /// # use sirius::prelude::bn256::C1Scalar;
+    /// # use sirius_fibonacci_example::{
+    /// #     circuits::SumCircuit, config::RunConfig, runner::{CommitmentKeys, FoldRunner},
+    /// # };
+    /// # fn main() -> Result<(), sirius_fibonacci_example::error::ExampleError> {
+    /// let cache = tempfile::tempdir().unwrap();
+    /// let config = RunConfig {
+    ///     primary_z_0: vec![C1Scalar::from(0)],
+    ///     checkpoints: 4,
+    ///     cache_dir: cache.path().to_path_buf(),
+    ///     ..RunConfig::default()
+    /// };
+    /// let keys = CommitmentKeys::setup(&config)?;
+    /// let mut runner = FoldRunner::new(&keys, &config, &SumCircuit::new(1))?;
+    /// for addend in [2, 3, 4] {
+    ///     runner.step(&SumCircuit::new(addend))?;
+    /// }
+    /// assert_eq!(runner.current_index(), 4);
+    ///
+    /// let checkpoint = runner.rewind(2)?;
+    /// assert_eq!(checkpoint.index, 2);
+    /// assert_eq!(checkpoint.z_out, [C1Scalar::from(1 + 2)]);
+    /// assert_eq!(runner.current_index(), 2);
+    /// assert_eq!(*runner.z_out(), checkpoint.z_out);
+    /// runner.assert_sound()?;
+    ///
+    /// // Refold with other data
+    /// runner.step(&SumCircuit::new(10))?;
+    /// assert_eq!(*runner.z_out(), [C1Scalar::from(1 + 2 + 10)]);
+    /// runner.assert_sound()?;
+    ///
+    /// // There is no state before the base step
+    /// assert!(runner.rewind(3).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn rewind(&mut self, n: usize) -> Result<Checkpoint<ARITY>, ExampleError> {
+        let kept = self.checkpoints.len();
+        if n >= kept {
+            return Err(ExampleError::Rewind {
+                steps: n,
+                available: kept.saturating_sub(1),
+            });
+        }
+
+        self.checkpoints.truncate(kept - n);
+        let checkpoint = self
+            .checkpoints
+            .back()
+            .cloned()
+            .expect("at least one is kept");
+        self.history.truncate(checkpoint.index);
+
+        let (base, rest) = self.history.split_first().expect("the base step is kept");
+        (self.ivc)(Op::Restart(base)).map_err(ExampleError::IvcCreate)?;
+        for (step, sc1) in (1..).zip(rest) {
+            let _span = tracing::info_span!("refold_step", step).entered();
+            (self.ivc)(Op::Fold(sc1)).map_err(|err| ExampleError::FoldStep { step, err })?;
+        }
+
+        self.index = checkpoint.index;
+        self.z_out = checkpoint.z_out;
+        self.secondary_z_out = checkpoint.secondary_z_out;
+
+        Ok(checkpoint)
+    }
+
+    /// Fold `n` steps of `sc1`
+    pub fn steps(&mut self, n: usize, sc1: &SC) -> Result<(), ExampleError> {
+        (0..n).try_for_each(|_| self.step(sc1))
+    }
+
+    /// Fold steps of `sc1` until `done` holds for the output of the last step, or `max_steps`
+    /// steps were folded
+    ///
+    /// `done` is checked before every step, so no step is folded if it already holds. Returns
+    /// the number of steps folded by this call; less than `max_steps` means `done` was reached.
+    /// E.g. fold the [`crate::FibonacciCircuit`] until its last element exceeds a threshold:
+    ///
+    /// ```
+    /// # use sirius::prelude::bn256::C1Scalar;
+    /// # use sirius_fibonacci_example::{
+    /// #     config::RunConfig, field, runner::{CommitmentKeys, FoldRunner}, testing::*,
+    /// #     FibonacciCircuit,
+    /// # };
+    /// # fn main() -> Result<(), sirius_fibonacci_example::error::ExampleError> {
+    /// let config = RunConfig {
+    ///     primary_commitment_key_size: TEST_COMMITMENT_KEY_SIZE,
+    ///     primary_circuit_table_size: TEST_TABLE_SIZE,
+    ///     secondary_commitment_key_size: TEST_COMMITMENT_KEY_SIZE,
+    ///     secondary_circuit_table_size: TEST_TABLE_SIZE,
+    ///     cache_dir: test_cache_dir(),
+    ///     ..RunConfig::default()
+    /// };
+    /// let keys = CommitmentKeys::setup(&config)?;
+    /// let sc1 = FibonacciCircuit::new(config.elements_num);
+    /// let mut runner = FoldRunner::new(&keys, &config, &sc1)?;
+    ///
+    /// // The base step ends at elements #10 and #11 = 89, every step adds 10 more
+    /// let exceeds = |[_, b]: &[C1Scalar; 2]| field::to_u128(b).map_or(true, |b| b > 1_000_000);
+    /// assert_eq!(runner.fold_until(&sc1, 100, exceeds)?, 2);
+    /// assert_eq!(runner.current_index(), 3);
+    /// assert_eq!(*runner.z_out(), [C1Scalar::from(832_040), C1Scalar::from(1_346_269)]);
+    /// runner.assert_sound()?;
+    ///
+    /// // Already done, nothing is folded
+    /// assert_eq!(runner.fold_until(&sc1, 100, exceeds)?, 0);
+    /// // Never done, `max_steps` are folded
+    /// assert_eq!(runner.fold_until(&sc1, 1, |_| false)?, 1);
+    /// assert_eq!(runner.current_index(), 4);
+    /// runner.assert_sound()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fold_until(
+        &mut self,
+        sc1: &SC,
+        max_steps: usize,
+        mut done: impl FnMut(&[C1Scalar; ARITY]) -> bool,
+    ) -> Result<usize, ExampleError> {
+        let mut steps = 0;
+        while steps < max_steps && !done(&self.z_out) {
+            self.step(sc1)?;
+            steps += 1;
+        }
+
+        Ok(steps)
+    }
+
+    /// Number of steps folded so far, including the base step, which is also the index of the
+    /// next step
+    pub fn current_index(&self) -> usize {
+        self.index
+    }
+
+    /// Output of the last folded step, as its [`ReferenceStep`] computes it: sirius v0.1.1 doesn't
+    /// expose the state of the IVC, so this is not read from it
+    pub fn z_out(&self) -> &[C1Scalar; ARITY] {
+        &self.z_out
+    }
+
+    /// Output of the last folded step of the secondary circuit, as
+    /// [`SecondaryCircuit::reference_step`] computes it, not read from the IVC either
+    pub fn secondary_z_out(&self) -> &[C2Scalar; A2] {
+        &self.secondary_z_out
+    }
+
+    /// Verify the IVC of the steps folded so far
+    pub fn verify(&mut self) -> Result<(), ExampleError> {
+        let _span = tracing::info_span!("verify").entered();
+
+        (self.ivc)(Op::Verify).map_err(ExampleError::Verify)
+    }
+
+    /// The one answer to "is this IVC valid": [`FoldRunner::verify`] with diagnostics
+    ///
+    /// `IVC::verify` is the only consistency check sirius v0.1.1 exposes. Passing it guarantees
+    /// that the accumulated instances of both the primary and the secondary circuit are satisfied,
+    /// and that their public inputs bind the public parameters, the step count, `z_0` and the
+    /// current state of both circuits, so a passing IVC can't have skipped or altered a step. On
+    /// failure the error names the circuit that failed, when sirius tells it.
+    pub fn assert_sound(&mut self) -> Result<(), ExampleError> {
+        self.verify().map_err(|err| match err {
+            ExampleError::Verify(err) => match Side::mentioned_in(&err) {
+                Some(side) => ExampleError::Verify(format!("{side} circuit: {err}")),
+                None => ExampleError::Verify(err),
+            },
+            err => err,
+        })
+    }
+}
@@ -0,0 +1,82 @@
+//! Secondary step circuits, folded on the other curve of the cycle alongside the primary one
+//!
+//! Every fold step folds one step of each circuit, so a non-trivial secondary circuit proves a
+//! second computation next to the primary one, in the scalar field of the secondary curve.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use sirius::{ivc::step_circuit::trivial, prelude::bn256::C2Scalar};
+
+use crate::{
+    circuits::{ReferenceStep, SumCircuit},
+    describe::StepCircuitShape,
+    field, A2,
+};
+
+/// Secondary step circuit of a run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SecondaryCircuit {
+    /// Keeps its state unchanged, only there because the cycle needs a second circuit
+    #[default]
+    Trivial,
+    /// Counts the fold steps: `z' = z + 1`
+    Counter,
+}
+
+impl SecondaryCircuit {
+    pub const ALL: [Self; 2] = [Self::Trivial, Self::Counter];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Trivial => "trivial",
+            Self::Counter => "counter",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|circuit| circuit.name() == name)
+    }
+
+    pub fn arity(self) -> usize {
+        match self {
+            Self::Trivial => A2,
+            Self::Counter => 1,
+        }
+    }
+
+    pub(crate) fn shape(self) -> StepCircuitShape {
+        match self {
+            Self::Trivial => StepCircuitShape::of::<A2, C2Scalar, trivial::Circuit<A2, C2Scalar>>(),
+            Self::Counter => StepCircuitShape::of::<1, C2Scalar, SumCircuit>(),
+        }
+    }
+
+    /// State after one step of the circuit from `z_i`
+    pub fn reference_step(self, z_i: &[C2Scalar; A2]) -> [C2Scalar; A2] {
+        match self {
+            Self::Trivial => *z_i,
+            Self::Counter => SumCircuit::new(1).reference_step(z_i),
+        }
+    }
+
+    /// Steps the circuit counted from `z_0` to `z_out`, `None` if it doesn't count them
+    ///
+    /// The state of the [`SecondaryCircuit::Counter`] after `n` steps is `z_0 + n`; the step
+    /// count doesn't fit into `usize` if `z_out` is below `z_0` in the field.
+    pub fn counted_steps(self, [z_0]: &[C2Scalar; A2], [z_out]: &[C2Scalar; A2]) -> Option<usize> {
+        match self {
+            Self::Trivial => None,
+            Self::Counter => {
+                field::to_u128(&(*z_out - z_0)).and_then(|steps| steps.try_into().ok())
+            }
+        }
+    }
+}
+
+impl fmt::Display for SecondaryCircuit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
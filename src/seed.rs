@@ -0,0 +1,66 @@
+//! Random `z_0` seeds from OS entropy, to fuzz a circuit over many inputs
+
+use std::fmt;
+
+use sirius::prelude::bn256::C1Scalar;
+
+use crate::error::ExampleError;
+
+/// `z_0` of one run, drawn from OS entropy
+///
+/// Each element is a random `u64`, rather than a random field element, so that the seeds also
+/// suit the circuits whose state must fit into `u64`. Displays as the `--z0` argument that
+/// reproduces the run:
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{
+/// #     circuits::ReferenceStep, field, seed::Seed, FibonacciCircuit,
+/// # };
+/// let seed = Seed::from_entropy(2).unwrap();
+/// let logged = seed.to_string();
+///
+/// // Fed back as `--z0`, the logged seed is the same `z_0`...
+/// let replayed = logged
+///     .strip_prefix("--z0 ")
+///     .unwrap()
+///     .split(',')
+///     .map(|value| field::parse_field::<C1Scalar>(value).unwrap())
+///     .collect::<Vec<_>>();
+/// assert_eq!(replayed, seed.z_0());
+///
+/// // ...so the run folds to the same `z_out`
+/// let sc = FibonacciCircuit::new(10);
+/// let z_out = |z_0: &[C1Scalar]| {
+///     (0..5).fold([z_0[0], z_0[1]], |z, _| sc.reference_step(&z))
+/// };
+/// assert_eq!(z_out(&replayed), z_out(&seed.z_0()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Seed(pub Vec<u64>);
+
+impl Seed {
+    /// `arity` random elements
+    pub fn from_entropy(arity: usize) -> Result<Self, ExampleError> {
+        let mut bytes = vec![0; arity * 8];
+        getrandom::getrandom(&mut bytes).map_err(|err| ExampleError::Entropy(err.to_string()))?;
+
+        Ok(Self(
+            bytes
+                .chunks_exact(8)
+                .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+                .collect(),
+        ))
+    }
+
+    pub fn z_0(&self) -> Vec<C1Scalar> {
+        self.0.iter().copied().map(C1Scalar::from).collect()
+    }
+}
+
+impl fmt::Display for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values = self.0.iter().map(u64::to_string).collect::<Vec<_>>();
+        write!(f, "--z0 {}", values.join(","))
+    }
+}
@@ -0,0 +1,111 @@
+//! Golden-file snapshot of a fixed run, to catch behavior changes across sirius versions
+
+use std::{fmt::Write, fs, path::Path};
+
+use crate::{error::ExampleError, field, RunSummary};
+
+/// What a run is compared by: its final state and whether it verified, a run that skipped the
+/// verification did not
+#[derive(Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Steps folded, `0` if the run failed
+    pub steps: usize,
+    /// Hex of every `z_out` element, empty if the run failed
+    pub z_out: Vec<String>,
+    pub verified: bool,
+}
+
+impl Snapshot {
+    pub fn of(result: &Result<RunSummary, ExampleError>) -> Self {
+        match result {
+            Ok(summary) => Self {
+                steps: summary.steps,
+                z_out: summary.z_out.iter().map(field::to_hex).collect(),
+                verified: summary.verification.is_some(),
+            },
+            Err(_) => Self {
+                steps: 0,
+                z_out: vec![],
+                verified: false,
+            },
+        }
+    }
+
+    /// One `key = value` line per field, so a mismatch diffs line by line
+    pub fn render(&self) -> String {
+        let mut out = format!("steps = {}\n", self.steps);
+        for (index, value) in self.z_out.iter().enumerate() {
+            writeln!(out, "z_out[{index}] = {value}").unwrap();
+        }
+        writeln!(out, "verified = {}", self.verified).unwrap();
+
+        out
+    }
+
+    /// Write the golden file, which [`Snapshot::check`] then matches
+    ///
+    /// ```
+    /// # use sirius::prelude::bn256::C1Scalar;
+    /// # use sirius_fibonacci_example::{
+    /// #     error::ExampleError, snapshot::Snapshot, verification::VerificationReport, RunSummary,
+    /// # };
+    /// let dir = tempfile::tempdir().unwrap();
+    /// let path = dir.path().join("run.golden");
+    ///
+    /// let verified = Ok(RunSummary {
+    ///     steps: 5,
+    ///     z_out: vec![C1Scalar::from(89)],
+    ///     verification: Some(VerificationReport::default()),
+    ///     recorded_steps: None,
+    /// });
+    /// // A failed run is a snapshot without steps
+    /// let failed = Err(ExampleError::Verify("unsatisfied".to_owned()));
+    /// for result in [verified, failed] {
+    ///     let snapshot = Snapshot::of(&result);
+    ///     snapshot.write(&path).unwrap();
+    ///     snapshot.check(&path).unwrap();
+    /// }
+    /// ```
+    pub fn write(&self, path: &Path) -> Result<(), ExampleError> {
+        fs::write(path, self.render()).map_err(|err| snapshot_err(path, err.to_string()))
+    }
+
+    /// Compare with the golden file at `path`, the error lists the differing lines
+    pub fn check(&self, path: &Path) -> Result<(), ExampleError> {
+        let golden = fs::read_to_string(path).map_err(|err| snapshot_err(path, err.to_string()))?;
+        let actual = self.render();
+        if golden == actual {
+            return Ok(());
+        }
+
+        let golden = golden.lines().collect::<Vec<_>>();
+        let actual = actual.lines().collect::<Vec<_>>();
+
+        let mut diff = String::new();
+        for index in 0..golden.len().max(actual.len()) {
+            match (golden.get(index), actual.get(index)) {
+                (Some(expected), Some(got)) if expected == got => {}
+                (expected, got) => {
+                    if let Some(expected) = expected {
+                        writeln!(diff, "- {expected}").unwrap();
+                    }
+                    if let Some(got) = got {
+                        writeln!(diff, "+ {got}").unwrap();
+                    }
+                }
+            }
+        }
+
+        Err(ExampleError::SnapshotMismatch {
+            path: path.to_path_buf(),
+            diff,
+        })
+    }
+}
+
+fn snapshot_err(path: &Path, err: String) -> ExampleError {
+    ExampleError::Snapshot {
+        path: path.to_path_buf(),
+        err,
+    }
+}
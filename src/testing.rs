@@ -0,0 +1,89 @@
+//! Folding a step circuit end to end in a test, with one set of small parameters for all of them
+//!
+//! Only for the tests of the crate, its doctests and `tests/`: it is public so that they can
+//! reach it, but hidden from the docs and not part of the API.
+
+use std::{env, path::PathBuf};
+
+use sirius::prelude::{bn256::C1Scalar, StepCircuit};
+
+use crate::{
+    circuits::ReferenceStep,
+    config::{RunConfig, MIN_CIRCUIT_TABLE_SIZE},
+    mock,
+    runner::{CommitmentKeys, FoldRunner},
+};
+
+/// Table size of both circuits, the minimum sirius needs for the folding part of the step
+/// circuit; the step circuits of the example all fit in the rows it leaves
+pub const TEST_TABLE_SIZE: usize = MIN_CIRCUIT_TABLE_SIZE;
+
+/// Commitment key size of both circuits, the smallest that commits the folding circuit built on
+/// [`TEST_TABLE_SIZE`] tables
+pub const TEST_COMMITMENT_KEY_SIZE: usize = 20;
+
+/// Folder of the commitment keys of the tests, under the OS temp dir
+///
+/// Shared by every test rather than one temp dir each, so that the keys are only generated once:
+/// it takes seconds, far more than folding a few steps. The cache locks its files, so concurrent
+/// tests are fine.
+pub fn test_cache_dir() -> PathBuf {
+    env::temp_dir().join("sirius-fibonacci-example-test-keys")
+}
+
+/// Fold `steps` steps of `circuit` from `z_0`, the base step included, verify the IVC and
+/// return the values the circuit assigns to the `z_out` cells of its last step
+///
+/// sirius v0.1.1 doesn't expose the state of an IVC, so every step is also run through
+/// [`mock::check_step`] in a [`TEST_TABLE_SIZE`] table, each from the `z_out` the circuit
+/// assigned on the step before: the result is what the circuit computes, checked against its
+/// [`ReferenceStep`] on every step, not the reference recomputed on its own.
+///
+/// Uses the [`TEST_TABLE_SIZE`] tables and [`TEST_COMMITMENT_KEY_SIZE`] keys from the
+/// [`test_cache_dir`], with the trivial secondary circuit. Panics if any step fails, in the mock
+/// prover or folded, or the IVC doesn't verify, as a test would:
+///
+/// ```
+/// # use sirius::prelude::bn256::C1Scalar;
+/// # use sirius_fibonacci_example::{circuits::SumCircuit, testing::prove_and_verify};
+/// let z_out = prove_and_verify(&SumCircuit::new(3), [C1Scalar::from(1)], 4);
+/// assert_eq!(z_out, [C1Scalar::from(1 + 4 * 3)]);
+/// ```
+pub fn prove_and_verify<const ARITY: usize, SC>(
+    circuit: &SC,
+    z_0: [C1Scalar; ARITY],
+    steps: usize,
+) -> [C1Scalar; ARITY]
+where
+    SC: StepCircuit<ARITY, C1Scalar> + ReferenceStep<ARITY, C1Scalar> + Clone,
+{
+    assert!(steps >= 1, "the base step is always folded");
+
+    let mut z_out = z_0;
+    for step in 0..steps {
+        z_out = mock::check_step(TEST_TABLE_SIZE as u32, circuit, z_out)
+            .unwrap_or_else(|err| panic!("mock step {step}: {err}"));
+    }
+
+    let config = RunConfig {
+        fold_step_count: steps,
+        primary_commitment_key_size: TEST_COMMITMENT_KEY_SIZE,
+        primary_circuit_table_size: TEST_TABLE_SIZE,
+        primary_z_0: z_0.to_vec(),
+        secondary_commitment_key_size: TEST_COMMITMENT_KEY_SIZE,
+        secondary_circuit_table_size: TEST_TABLE_SIZE,
+        cache_dir: test_cache_dir(),
+        ..RunConfig::default()
+    };
+
+    let keys = CommitmentKeys::setup(&config)
+        .unwrap_or_else(|err| panic!("commitment key setup failed: {err}"));
+    let mut runner = FoldRunner::new(&keys, &config, circuit)
+        .unwrap_or_else(|err| panic!("base step failed: {err}"));
+    runner
+        .steps(steps - 1, circuit)
+        .unwrap_or_else(|err| panic!("{err}"));
+    runner.assert_sound().unwrap_or_else(|err| panic!("{err}"));
+
+    z_out
+}
@@ -0,0 +1,86 @@
+//! Public values that the verification of a run checks, for cross-checking with another
+//! implementation
+
+use std::{fs, path::Path};
+
+use serde::Serialize;
+use sirius::prelude::bn256::{C1Scalar, C2Scalar};
+
+use crate::{config::RunConfig, error::ExampleError, field, CircuitSpec, RunSummary};
+
+/// Public inputs & outputs of a verified run
+///
+/// `IVC::verify` checks the folded instances against the public parameters, the step count and
+/// the `z_0` & current state of both circuits. sirius v0.1.1 keeps the instance commitments and
+/// the public parameters digest private, so the transcript has only the values the example
+/// knows. Only the `z_0` of the secondary circuit is included: a non-trivial one moves its state,
+/// which the example doesn't follow.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PublicTranscript {
+    pub circuit: CircuitSpec,
+    /// Folded steps, including the base step
+    pub steps: usize,
+    #[serde(with = "field::serde_vec")]
+    pub primary_z_0: Vec<C1Scalar>,
+    #[serde(with = "field::serde_vec")]
+    pub primary_z_out: Vec<C1Scalar>,
+    #[serde(with = "field::serde_vec")]
+    pub secondary_z_0: Vec<C2Scalar>,
+}
+
+impl PublicTranscript {
+    pub fn new(circuit: &CircuitSpec, config: &RunConfig, summary: &RunSummary) -> Self {
+        Self {
+            circuit: circuit.clone(),
+            steps: summary.steps,
+            primary_z_0: config.primary_z_0.clone(),
+            primary_z_out: summary.z_out.clone(),
+            secondary_z_0: config.secondary_z_0.clone(),
+        }
+    }
+
+    /// Write the transcript as JSON to `path`, field elements as hex
+    ///
+    /// ```
+    /// # use sirius::prelude::bn256::C1Scalar;
+    /// # use sirius_fibonacci_example::{
+    /// #     config::RunConfig, field::parse_field, run_circuit, testing::test_cache_dir,
+    /// #     transcript::PublicTranscript, CircuitSpec,
+    /// # };
+    /// let dir = tempfile::tempdir().unwrap();
+    /// let path = dir.path().join("transcript.json");
+    ///
+    /// let config = RunConfig {
+    ///     fold_step_count: 2,
+    ///     primary_z_0: vec![C1Scalar::from(2), C1Scalar::from(3)],
+    ///     cache_dir: test_cache_dir(),
+    ///     ..RunConfig::default()
+    /// };
+    /// let summary = run_circuit(&CircuitSpec::Fibonacci, &config, None).unwrap();
+    /// PublicTranscript::new(&CircuitSpec::Fibonacci, &config, &summary)
+    ///     .write(&path)
+    ///     .unwrap();
+    ///
+    /// let json: serde_json::Value =
+    ///     serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    /// let field = |key: &str| -> Vec<C1Scalar> {
+    ///     json[key]
+    ///         .as_array()
+    ///         .unwrap()
+    ///         .iter()
+    ///         .map(|value| parse_field(value.as_str().unwrap()).unwrap())
+    ///         .collect()
+    /// };
+    /// assert_eq!(json["steps"], 2);
+    /// assert_eq!(field("primary_z_0"), [C1Scalar::from(2), C1Scalar::from(3)]);
+    /// // 20 elements on from 2, 3
+    /// assert_eq!(field("primary_z_out"), [C1Scalar::from(28657), C1Scalar::from(46368)]);
+    /// ```
+    pub fn write(&self, path: &Path) -> Result<(), ExampleError> {
+        let json = serde_json::to_string_pretty(self).expect("transcript is always representable");
+        fs::write(path, json).map_err(|err| ExampleError::Transcript {
+            path: path.to_path_buf(),
+            err,
+        })
+    }
+}
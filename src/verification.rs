@@ -0,0 +1,75 @@
+//! What the verification at the end of a run checked
+
+use std::{fmt, time::Duration};
+
+use crate::{config::Check, error::ExampleError};
+
+/// Every check of the verification of a run, with the time they took
+///
+/// `IVC::verify` of sirius v0.1.1 only passes or fails, so it is a single check here, next to the
+/// consistency checks of the harness around it
+#[derive(Debug, Default)]
+pub struct VerificationReport {
+    pub checks: Vec<Check>,
+    pub duration: Duration,
+}
+
+impl VerificationReport {
+    /// Whether every check passed
+    ///
+    /// A run only returns its report if it did, so this holds for every successful run:
+    ///
+    /// ```
+    /// # use sirius_fibonacci_example::{config::RunConfig, run_circuit, CircuitSpec};
+    /// let cache = tempfile::tempdir().unwrap();
+    /// let config = RunConfig {
+    ///     fold_step_count: 2,
+    ///     cache_dir: cache.path().to_path_buf(),
+    ///     ..RunConfig::default()
+    /// };
+    ///
+    /// let verification = run_circuit(&CircuitSpec::Fibonacci, &config, None)
+    ///     .unwrap()
+    ///     .verification
+    ///     .unwrap();
+    /// assert!(!verification.checks.is_empty());
+    /// assert!(verification.all_passed(), "{verification}");
+    /// ```
+    pub fn all_passed(&self) -> bool {
+        self.checks.iter().all(|check| check.result.is_ok())
+    }
+
+    /// The first failed check as an error
+    pub fn ensure_passed(&self) -> Result<(), ExampleError> {
+        match self.checks.iter().find_map(|check| {
+            check
+                .result
+                .as_ref()
+                .err()
+                .map(|reason| (check.name, reason))
+        }) {
+            Some((name, reason)) => Err(ExampleError::Verify(format!("{name}: {reason}"))),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for VerificationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let passed = self
+            .checks
+            .iter()
+            .filter(|check| check.result.is_ok())
+            .count();
+        write!(
+            f,
+            "verification: {passed}/{} checks passed in {} ms",
+            self.checks.len(),
+            self.duration.as_millis()
+        )?;
+        for check in &self.checks {
+            write!(f, "\n  {check}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,143 @@
+//! Cache of successful bundle verifications, for verifiers that receive the same bundle repeatedly
+
+use std::{
+    collections::{hash_map::DefaultHasher, VecDeque},
+    hash::{Hash, Hasher},
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+use crate::{bundle::ProofBundle, error::ExampleError, metrics::METRICS, progress::StepEvent};
+
+/// Bundles last verified successfully, the least recently used one is evicted first
+///
+/// Entries are looked up by a hash of the bundle, but a hit also compares the whole bundle, so a
+/// colliding bundle is verified rather than trusted. Only successes are cached: a failure may come
+/// from the environment (e.g. the key cache) rather than the bundle. Can be shared between
+/// threads, the lock is not held while verifying, so concurrent misses of the same bundle are all
+/// verified.
+pub struct VerifyCache {
+    capacity: usize,
+    /// Hash and JSON of every cached bundle, most recently used last
+    entries: Mutex<VecDeque<(u64, String)>>,
+    hits: AtomicU64,
+}
+
+impl VerifyCache {
+    /// Cache of at most `capacity` bundles, `0` disables caching
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+            hits: AtomicU64::new(0),
+        }
+    }
+
+    /// [`ProofBundle::verify`], skipped if the same bundle was verified before
+    ///
+    /// Returns whether it was a cache hit
+    ///
+    /// ```
+    /// # use sirius::prelude::bn256::C1Scalar;
+    /// # use sirius_fibonacci_example::{
+    /// #     bundle::ProofBundle, config::RunConfig, progress::StepEvent, run_circuit,
+    /// #     testing::test_cache_dir, verify_cache::VerifyCache, CircuitSpec,
+    /// # };
+    /// let circuit = CircuitSpec::Sum { addends: vec![3, 4] };
+    /// let config = RunConfig {
+    ///     fold_step_count: 2,
+    ///     primary_z_0: vec![C1Scalar::from(0)],
+    ///     cache_dir: test_cache_dir(),
+    ///     ..RunConfig::default()
+    /// };
+    /// let summary = run_circuit(&circuit, &config, None).unwrap();
+    /// let bundle = ProofBundle::new(circuit, config, summary);
+    ///
+    /// let cache = VerifyCache::new(16);
+    /// assert!(!cache.verify(&bundle, &test_cache_dir(), 0, None).unwrap());
+    /// assert_eq!(cache.hits(), 0);
+    ///
+    /// // The second time nothing is re-folded
+    /// let mut events = vec![];
+    /// let mut record = |event: StepEvent| events.push(event);
+    /// assert!(cache.verify(&bundle, &test_cache_dir(), 0, Some(&mut record)).unwrap());
+    /// assert!(events.is_empty());
+    /// assert_eq!(cache.hits(), 1);
+    ///
+    /// // Without capacity every verification is a miss
+    /// let uncached = VerifyCache::new(0);
+    /// for _ in 0..2 {
+    ///     assert!(!uncached.verify(&bundle, &test_cache_dir(), 0, None).unwrap());
+    /// }
+    /// assert_eq!(uncached.hits(), 0);
+    /// ```
+    pub fn verify(
+        &self,
+        bundle: &ProofBundle,
+        cache_dir: &Path,
+        cache_retries: u32,
+        progress: Option<&mut dyn FnMut(StepEvent)>,
+    ) -> Result<bool, ExampleError> {
+        let json = serde_json::to_string(bundle).expect("bundle is always representable");
+        let hash = {
+            let mut hasher = DefaultHasher::new();
+            json.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        if self.touch(hash, &json) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            METRICS.verify_cache_hits_total.inc();
+            return Ok(true);
+        }
+
+        bundle.verify(cache_dir, cache_retries, progress)?;
+        self.insert(hash, json);
+
+        Ok(false)
+    }
+
+    /// Number of verifications skipped so far
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Mark the entry as the most recently used one, if it is cached
+    fn touch(&self, hash: u64, json: &str) -> bool {
+        let mut entries = self.entries.lock().unwrap();
+
+        match entries
+            .iter()
+            .position(|(entry_hash, entry)| *entry_hash == hash && entry == json)
+        {
+            Some(index) => {
+                let entry = entries.remove(index).unwrap();
+                entries.push_back(entry);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn insert(&self, hash: u64, json: String) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+        // A concurrent miss of the same bundle may have inserted it meanwhile
+        if entries
+            .iter()
+            .any(|(entry_hash, entry)| *entry_hash == hash && *entry == json)
+        {
+            return;
+        }
+        if entries.len() == self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back((hash, json));
+    }
+}
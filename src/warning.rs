@@ -0,0 +1,112 @@
+//! Warnings of the example, which strict mode escalates to errors
+
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use crate::{error::ExampleError, output};
+
+static STRICT: AtomicBool = AtomicBool::new(false);
+
+/// Warnings emitted so far, with the budget they may not exceed
+static EMITTED: Mutex<Emitted> = Mutex::new(Emitted {
+    budget: None,
+    counts: WarningCounts(Vec::new()),
+});
+
+struct Emitted {
+    budget: Option<usize>,
+    counts: WarningCounts,
+}
+
+/// How many times each distinct warning was emitted, in the order they first were
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WarningCounts(pub Vec<(String, usize)>);
+
+impl WarningCounts {
+    pub fn total(&self) -> usize {
+        self.0.iter().map(|(_, count)| count).sum()
+    }
+
+    fn record(&mut self, message: String) {
+        match self.0.iter_mut().find(|(emitted, _)| *emitted == message) {
+            Some((_, count)) => *count += 1,
+            None => self.0.push((message, 1)),
+        }
+    }
+}
+
+impl fmt::Display for WarningCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} warnings", self.total())?;
+        for (message, count) in &self.0 {
+            write!(f, "\n  {count}x {message}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Make every later [`warn`] fail, e.g. for CI runs that must be clean
+pub fn set_strict(strict: bool) {
+    STRICT.store(strict, Ordering::Relaxed);
+}
+
+/// Make [`warn`] fail once more than `budget` warnings were emitted in total, `None` for no
+/// budget, and start counting anew
+///
+/// Caps a long sweep whose warnings would otherwise bury its output. The warning over the budget
+/// is still printed, then returned as [`ExampleError::WarningBudget`] with the counts so far:
+///
+/// ```
+/// # use sirius_fibonacci_example::{error::ExampleError, warning};
+/// warning::set_budget(Some(2));
+/// warning::warn("table size above the minimum").unwrap();
+/// warning::warn("table size above the minimum").unwrap();
+///
+/// let err = warning::warn("key size above the table size").unwrap_err();
+/// let ExampleError::WarningBudget { budget, counts } = &err else {
+///     panic!("not over the budget: {err}")
+/// };
+/// assert_eq!(*budget, 2);
+/// assert_eq!(counts.total(), 3);
+/// assert!(err.to_string().contains("2x table size above the minimum"));
+/// assert!(err.to_string().contains("1x key size above the table size"));
+/// ```
+pub fn set_budget(budget: Option<usize>) {
+    let mut emitted = EMITTED.lock().unwrap_or_else(|err| err.into_inner());
+    emitted.budget = budget;
+    emitted.counts = WarningCounts::default();
+}
+
+/// Every warning emitted since the last [`set_budget`]
+pub fn counts() -> WarningCounts {
+    let emitted = EMITTED.lock().unwrap_or_else(|err| err.into_inner());
+    emitted.counts.clone()
+}
+
+/// Print `message` as a warning, or return it as [`ExampleError::Strict`] in strict mode
+///
+/// Every warning of the example goes through here, so that strict mode and the budget of
+/// [`set_budget`] cover all of them
+pub fn warn(message: impl fmt::Display) -> Result<(), ExampleError> {
+    if STRICT.load(Ordering::Relaxed) {
+        return Err(ExampleError::Strict(message.to_string()));
+    }
+
+    eprintln!("warning: {message}");
+    output::log(format_args!("warning: {message}"));
+
+    let mut emitted = EMITTED.lock().unwrap_or_else(|err| err.into_inner());
+    emitted.counts.record(message.to_string());
+    match emitted.budget {
+        Some(budget) if emitted.counts.total() > budget => Err(ExampleError::WarningBudget {
+            budget,
+            counts: emitted.counts.clone(),
+        }),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,105 @@
+//! Coarse check that the fold time grows linearly with the steps, to flag a per-step cost that
+//! regresses into growing with the step count
+//!
+//! Folding is incremental: every step folds one instance into the accumulator, whatever came
+//! before, so the time of a step should not depend on how many were folded. Timing two step
+//! counts and comparing the time per folded step catches a superlinear regression without a
+//! baseline of its own. It is a wall-clock measurement on whatever machine runs it, so it is
+//! machine-dependent and noisy: the tolerance only flags a per-step cost that at least doubles,
+//! not a slowdown of a few percent, and the test is ignored unless asked for with
+//! `cargo test --release --test scaling -- --ignored`.
+
+use std::{
+    env,
+    time::{Duration, Instant},
+};
+
+use sirius_fibonacci_example::{
+    config::RunConfig, progress::StepEvent, run_circuit, testing::test_cache_dir, CircuitSpec,
+};
+
+/// Environment variable overriding the two step counts of the check, e.g. `2,4`
+const SCALING_STEPS_ENV: &str = "SIRIUS_SCALING_STEPS";
+
+/// Step counts when [`SCALING_STEPS_ENV`] is not set
+const DEFAULT_STEPS: [usize; 2] = [2, 4];
+
+/// Largest ratio of the time per folded step of the longer run to the shorter one accepted as
+/// linear
+const SCALING_TOLERANCE: f64 = 2.0;
+
+/// The step counts of [`SCALING_STEPS_ENV`], or [`DEFAULT_STEPS`]
+///
+/// Both include the base step, which is folded during the setup and not timed, so the shorter run
+/// needs at least 2 steps and the longer one more than that.
+fn steps() -> [usize; 2] {
+    let Ok(value) = env::var(SCALING_STEPS_ENV) else {
+        return DEFAULT_STEPS;
+    };
+
+    let steps = value
+        .split(',')
+        .map(|steps| steps.trim().parse::<usize>().ok())
+        .collect::<Option<Vec<_>>>();
+    match steps.as_deref() {
+        Some(&[short, long]) if 2 <= short && short < long => [short, long],
+        _ => panic!(
+            "{SCALING_STEPS_ENV} takes two increasing step counts of at least 2, e.g. `2,4`, got \
+             `{value}`"
+        ),
+    }
+}
+
+/// Fold time of a run of `steps` steps, from the created IVC to the last folded step, per step
+/// folded after the base step
+fn fold_time_per_step(circuit: &CircuitSpec, config: &RunConfig, steps: usize) -> Duration {
+    let config = RunConfig {
+        fold_step_count: steps,
+        max_time: None,
+        halt_on_fixpoint: false,
+        no_verify: true,
+        ..config.clone()
+    };
+
+    let mut fold_start = None;
+    let mut fold = Duration::ZERO;
+    let mut on_event = |event: StepEvent| match event {
+        StepEvent::SetupFinished => fold_start = Some(Instant::now()),
+        StepEvent::StepFolded { .. } => {
+            fold = fold_start.map_or(Duration::ZERO, |start| start.elapsed())
+        }
+        StepEvent::SetupStarted | StepEvent::FoldingFinished { .. } => {}
+    };
+    let summary = run_circuit(circuit, &config, Some(&mut on_event)).unwrap();
+    assert_eq!(summary.steps, steps);
+
+    fold / (steps - 1) as u32
+}
+
+#[test]
+#[ignore = "wall-clock measurement, run with `cargo test --release --test scaling -- --ignored`"]
+fn fold_time_grows_linearly_with_the_steps() {
+    let [short, long] = steps();
+    let config = RunConfig {
+        cache_dir: test_cache_dir(),
+        ..RunConfig::default()
+    };
+    let circuit = CircuitSpec::Fibonacci;
+
+    // Warms the key cache, not measured
+    fold_time_per_step(&circuit, &config, short);
+    let short_per_step = fold_time_per_step(&circuit, &config, short);
+    let long_per_step = fold_time_per_step(&circuit, &config, long);
+
+    let ratio = long_per_step.as_secs_f64() / short_per_step.as_secs_f64();
+    println!(
+        "{short} steps: {} ms per step; {long} steps: {} ms per step; per-step ratio {ratio:.2}, \
+         up to {SCALING_TOLERANCE} is linear",
+        short_per_step.as_millis(),
+        long_per_step.as_millis(),
+    );
+    assert!(
+        ratio <= SCALING_TOLERANCE,
+        "fold time grows superlinearly with the steps, per-step ratio {ratio:.2}"
+    );
+}
@@ -0,0 +1,31 @@
+//! The fixed snapshot run against the golden file checked into `snapshots/`, as
+//! `--snapshot snapshots/fibonacci.golden --check` does
+//!
+//! The run uses the default config, at the default key and table sizes, so the setup takes long
+//! on a cold key cache: the test is ignored unless asked for with
+//! `cargo test --release --test snapshot -- --ignored`, e.g. after bumping sirius.
+
+use std::path::Path;
+
+use sirius_fibonacci_example::{
+    config::RunConfig, run_circuit, snapshot::Snapshot, testing::test_cache_dir, CircuitSpec,
+};
+
+#[test]
+#[ignore = "full-size run, run with `cargo test --release --test snapshot -- --ignored`"]
+fn fibonacci_run_matches_the_golden_file() {
+    let golden = Path::new(env!("CARGO_MANIFEST_DIR")).join("snapshots/fibonacci.golden");
+    let config = RunConfig {
+        cache_dir: test_cache_dir(),
+        ..RunConfig::default()
+    };
+
+    let result = run_circuit(&CircuitSpec::Fibonacci, &config, None);
+    if let Err(err) = &result {
+        panic!("snapshot run failed: {err}");
+    }
+
+    Snapshot::of(&result)
+        .check(&golden)
+        .unwrap_or_else(|err| panic!("{err}"));
+}
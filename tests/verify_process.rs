@@ -0,0 +1,67 @@
+//! The binary proving a run, then verifying its bundle in a separate process of itself, as
+//! `--output <FILE> --prove-then-verify-separate-process` does
+
+use std::{fs, path::Path, process::Command};
+
+use sirius_fibonacci_example::testing::{
+    test_cache_dir, TEST_COMMITMENT_KEY_SIZE, TEST_TABLE_SIZE,
+};
+
+/// The binary with the small test keys and tables, sharing the key cache of the tests
+fn example() -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_sirius-fibonacci-example"));
+    command
+        .arg("--cache-dir")
+        .arg(test_cache_dir())
+        .args(["--primary-key-size", &TEST_COMMITMENT_KEY_SIZE.to_string()])
+        .args(["--primary-table-size", &TEST_TABLE_SIZE.to_string()])
+        .args([
+            "--secondary-key-size",
+            &TEST_COMMITMENT_KEY_SIZE.to_string(),
+        ])
+        .args(["--secondary-table-size", &TEST_TABLE_SIZE.to_string()]);
+    command
+}
+
+fn verify(bundle: &Path) -> bool {
+    example()
+        .arg("--verify")
+        .arg(bundle)
+        .status()
+        .unwrap()
+        .success()
+}
+
+#[test]
+fn proven_bundle_is_verified_by_a_child_process() {
+    let dir = tempfile::tempdir().unwrap();
+    let bundle = dir.path().join("bundle.json");
+
+    let output = example()
+        .args(["--fold-steps", "2", "--output"])
+        .arg(&bundle)
+        .arg("--prove-then-verify-separate-process")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{}\n{stdout}\n{}",
+        output.status,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        stdout.contains("verified by a separate process"),
+        "{stdout}"
+    );
+
+    // The bundle on its own verifies as well
+    assert!(verify(&bundle));
+
+    // A tampered one doesn't
+    let mut json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&bundle).unwrap()).unwrap();
+    json["z_out"][0] = "0x7".into();
+    fs::write(&bundle, json.to_string()).unwrap();
+    assert!(!verify(&bundle));
+}